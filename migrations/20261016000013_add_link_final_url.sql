@@ -0,0 +1,3 @@
+-- Record where a link's URL ended up after following redirects.
+-- NULL when the URL did not redirect or has not been scraped yet.
+ALTER TABLE links ADD COLUMN final_url TEXT;
@@ -0,0 +1,3 @@
+-- GitHub account type of the repository owner ("User" or "Organization").
+-- NULL for non-GitHub links or when the API did not report it.
+ALTER TABLE links ADD COLUMN github_owner_type TEXT;
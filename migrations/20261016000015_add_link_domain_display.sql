@@ -0,0 +1,3 @@
+-- Unicode form of the link's host for display (e.g. "münchen.de").
+-- `domain` keeps the ASCII/punycode form used for filtering and grouping.
+ALTER TABLE links ADD COLUMN domain_display TEXT;
@@ -0,0 +1,3 @@
+-- Previous URL of a GitHub link whose repository was renamed or transferred.
+-- NULL when the repository has never been detected as moved.
+ALTER TABLE links ADD COLUMN renamed_from TEXT;
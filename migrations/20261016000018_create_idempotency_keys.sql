@@ -0,0 +1,12 @@
+-- Idempotency-Key values seen on POST /api/links, mapped to the link they
+-- created so a retried request returns the original link. Rows are purged by
+-- the scheduler once older than the replay window.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    user_id     UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    key         TEXT        NOT NULL,
+    link_id     UUID        NOT NULL REFERENCES links(id) ON DELETE CASCADE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, key)
+);
+
+CREATE INDEX IF NOT EXISTS idempotency_keys_created_at ON idempotency_keys(created_at);
@@ -0,0 +1,7 @@
+-- Names of tags, categories, languages and licenses are unique per user
+-- regardless of case (uq_*_user_name on (user_id, lower(name))). Those
+-- indexes don't cover the global rows, whose user_id is NULL, so "MIT" and
+-- "mit" could both be seeded. Extend the rule to global rows as well.
+CREATE UNIQUE INDEX IF NOT EXISTS uq_tags_global_name ON tags(lower(name)) WHERE user_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_languages_global_name ON languages(lower(name)) WHERE user_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_licenses_global_name ON licenses(lower(name)) WHERE user_id IS NULL;
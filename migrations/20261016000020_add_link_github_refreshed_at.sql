@@ -0,0 +1,4 @@
+-- When the link's GitHub metadata was last fetched. The scheduler only
+-- re-fetches it once GITHUB_REFRESH_INTERVAL_HOURS have passed, independent
+-- of the general health-check interval. NULL means never fetched.
+ALTER TABLE links ADD COLUMN github_refreshed_at TIMESTAMPTZ;
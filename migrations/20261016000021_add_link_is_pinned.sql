@@ -0,0 +1,5 @@
+-- Pinned links sort ahead of all others in link listings. Independent of
+-- status, so archiving a pinned link keeps it pinned.
+ALTER TABLE links ADD COLUMN is_pinned BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX idx_links_user_pinned ON links(user_id) WHERE is_pinned;
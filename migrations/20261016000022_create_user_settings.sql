@@ -0,0 +1,8 @@
+-- Per-user preferences. A missing row means all defaults.
+-- default_category_id: category attached to new links created without any
+-- categories; cleared automatically when the category is deleted.
+CREATE TABLE user_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    default_category_id UUID REFERENCES categories(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
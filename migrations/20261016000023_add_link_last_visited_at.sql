@@ -0,0 +1,3 @@
+-- When the user last opened the link (POST /api/links/:id/visit). NULL means
+-- never; auto-archiving then counts from created_at.
+ALTER TABLE links ADD COLUMN last_visited_at TIMESTAMPTZ;
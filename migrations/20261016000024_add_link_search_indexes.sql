@@ -0,0 +1,17 @@
+-- Indexes for the per-user link filters and the text search.
+--
+-- Every link query is scoped to one user, so status and domain filters (and
+-- grouping by domain) are served by composite indexes led by user_id.
+CREATE INDEX IF NOT EXISTS idx_links_user_status ON links(user_id, status);
+CREATE INDEX IF NOT EXISTS idx_links_user_domain ON links(user_id, domain);
+
+-- Search matches `LOWER(column) LIKE '%term%'`, which a b-tree can't serve.
+-- Trigram GIN indexes on the same expressions let Postgres use a bitmap scan
+-- instead of reading every row. pg_trgm is a trusted extension (PostgreSQL 13+),
+-- so the database owner can create it.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_links_title_trgm ON links USING GIN (LOWER(title) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_links_description_trgm ON links USING GIN (LOWER(description) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_links_url_trgm ON links USING GIN (LOWER(url) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_links_domain_trgm ON links USING GIN (LOWER(domain) gin_trgm_ops);
@@ -0,0 +1,4 @@
+-- Per-user GitHub API token, encrypted with SETTINGS_ENCRYPTION_KEY
+-- (12-byte nonce followed by the ChaCha20-Poly1305 ciphertext). NULL means the
+-- user has no token and the server-wide GITHUB_TOKEN is used.
+ALTER TABLE user_settings ADD COLUMN github_token_encrypted BYTEA;
@@ -0,0 +1,3 @@
+-- Set when the user changes a link's title, so automatic refreshes keep it.
+-- Cleared again by a forced refresh that replaces the title.
+ALTER TABLE links ADD COLUMN title_manually_edited BOOLEAN NOT NULL DEFAULT FALSE;
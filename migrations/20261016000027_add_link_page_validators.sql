@@ -0,0 +1,4 @@
+-- ETag and Last-Modified of the page as last scraped. The scheduler sends them
+-- back as If-None-Match/If-Modified-Since and skips parsing on 304.
+ALTER TABLE links ADD COLUMN http_etag TEXT;
+ALTER TABLE links ADD COLUMN http_last_modified TEXT;
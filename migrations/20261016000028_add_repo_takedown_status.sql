@@ -0,0 +1,5 @@
+-- Allow marking GitHub links whose repository is unavailable for legal reasons
+-- (HTTP 451, e.g. a DMCA takedown), so the scheduler stops retrying them.
+ALTER TABLE links DROP CONSTRAINT IF EXISTS links_status_check;
+ALTER TABLE links ADD CONSTRAINT links_status_check
+    CHECK (status IN ('active', 'archived', 'inaccessible', 'repo_unavailable', 'private_repo', 'repo_takedown'));
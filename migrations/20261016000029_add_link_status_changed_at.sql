@@ -0,0 +1,6 @@
+-- When the link's status last changed, so newly broken links can be listed
+-- (GET /api/links/failed-since). Unknown (NULL) for links whose status
+-- hasn't changed since this migration.
+ALTER TABLE links ADD COLUMN status_changed_at TIMESTAMPTZ;
+
+CREATE INDEX idx_links_user_status_changed_at ON links(user_id, status_changed_at);
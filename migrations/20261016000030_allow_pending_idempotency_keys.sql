@@ -0,0 +1,3 @@
+-- A request reserves its Idempotency-Key before creating the link, so the
+-- key exists with no link while the first request is still in flight.
+ALTER TABLE idempotency_keys ALTER COLUMN link_id DROP NOT NULL;
@@ -0,0 +1,13 @@
+-- When visits started being recorded. Links saved earlier have no
+-- last_visited_at even if they are in use, so auto-archiving counts them from
+-- this time instead of from created_at. Taken from when last_visited_at was
+-- added, or now on databases that predate migration tracking.
+CREATE TABLE link_visit_tracking (
+    started_at TIMESTAMPTZ NOT NULL
+);
+
+INSERT INTO link_visit_tracking (started_at)
+SELECT COALESCE(
+    (SELECT installed_on FROM _sqlx_migrations WHERE version = 20261016000023),
+    NOW()
+);
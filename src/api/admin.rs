@@ -3,9 +3,13 @@
 //! - GET /api/admin/users — list all users
 //! - DELETE /api/admin/users/:user_id — delete a user
 //! - POST /api/admin/users/:user_id/promote — promote user to admin
+//! - POST /api/admin/maintenance/reclassify-github — recompute GitHub repo flags
+//! - POST /api/admin/maintenance/backfill-url-parts — re-derive link domains/paths
 
 use crate::auth::middleware::AdminClaims;
+use crate::config::Config;
 use crate::error::AppError;
+use crate::jobs::{Job, JobQueue};
 use axum::{extract::State, Json};
 use serde::Serialize;
 use sqlx::PgPool;
@@ -95,3 +99,60 @@ pub async fn promote_user(
         serde_json::json!({"message": "User promoted to admin"}),
     ))
 }
+
+#[derive(Debug, Serialize)]
+pub struct ReclassifyGitHubResponse {
+    pub checked: usize,
+    pub reclassified: usize,
+    pub now_github: usize,
+    pub no_longer_github: usize,
+    /// Newly detected repositories queued for a GitHub metadata fetch; the
+    /// rest get it on their next scheduled refresh
+    pub refresh_queued: usize,
+}
+
+/// POST /api/admin/maintenance/reclassify-github — recompute GitHub repo flags
+///
+/// Re-evaluates `is_github_repo` for every user's links with the current
+/// detection and queues a metadata fetch for newly detected repositories.
+pub async fn reclassify_github(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    State(jobs): State<JobQueue>,
+    _admin: AdminClaims,
+) -> Result<Json<ReclassifyGitHubResponse>, AppError> {
+    let result = crate::scheduler::reclassify_github_repos(&pool, &config).await?;
+
+    let refresh_queued = result
+        .now_github
+        .iter()
+        .filter(|(link_id, user_id)| {
+            jobs.enqueue(Job::RefreshLink {
+                link_id: *link_id,
+                user_id: *user_id,
+                options: crate::services::RefreshOptions::default(),
+            })
+        })
+        .count();
+
+    Ok(Json(ReclassifyGitHubResponse {
+        checked: result.checked,
+        reclassified: result.reclassified(),
+        now_github: result.now_github.len(),
+        no_longer_github: result.no_longer_github,
+        refresh_queued,
+    }))
+}
+
+/// POST /api/admin/maintenance/backfill-url-parts — re-derive link domains/paths
+///
+/// Re-parses every user's link URLs and corrects stored `domain`,
+/// `domain_display` and `path` values that don't match.
+pub async fn backfill_url_parts(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    _admin: AdminClaims,
+) -> Result<Json<crate::scheduler::UrlPartsBackfill>, AppError> {
+    let result = crate::scheduler::backfill_url_parts(&pool, &config).await?;
+    Ok(Json(result))
+}
@@ -7,7 +7,7 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{get, post, put},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
@@ -20,9 +20,16 @@ struct CategoryResponse {
     name: String,
     parent_id: Option<Uuid>,
     depth: i32,
+    sort_order: Option<i32>,
     link_count: i64,
 }
 
+#[derive(Debug, Serialize)]
+struct OrphanedCategoriesResponse {
+    count: usize,
+    categories: Vec<Category>,
+}
+
 /// POST /api/categories
 async fn create_category(
     State(pool): State<PgPool>,
@@ -35,47 +42,90 @@ async fn create_category(
         name: category.name,
         parent_id: category.parent_id,
         depth: category.depth,
+        sort_order: category.sort_order,
         link_count: 0,
     };
     Ok((StatusCode::CREATED, Json(response)))
 }
 
-/// GET /api/categories
-async fn list_categories(
-    State(pool): State<PgPool>,
-    auth: AuthenticatedUser,
-) -> Result<Json<Vec<CategoryResponse>>, AppError> {
-    let categories = sqlx::query_as::<_, (Uuid, String, Option<Uuid>, i32, i64)>(
+/// The user's categories with link counts, in display order
+async fn category_responses(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<CategoryResponse>, AppError> {
+    let categories = sqlx::query_as::<_, (Uuid, String, Option<Uuid>, i32, Option<i32>, i64)>(
         r#"
-        SELECT c.id, c.name, c.parent_id, c.depth, COUNT(lc.link_id) as link_count
+        SELECT c.id, c.name, c.parent_id, c.depth, c.sort_order, COUNT(lc.link_id) as link_count
         FROM categories c
         LEFT JOIN link_categories lc ON c.id = lc.category_id
         LEFT JOIN links l ON lc.link_id = l.id AND l.user_id = $1
         WHERE c.user_id = $1
-        GROUP BY c.id, c.name, c.parent_id, c.depth
-        ORDER BY c.depth, c.name
+        GROUP BY c.id, c.name, c.parent_id, c.depth, c.sort_order
+        ORDER BY c.depth, c.sort_order NULLS LAST, c.name
         "#,
     )
-    .bind(auth.user_id)
-    .fetch_all(&pool)
+    .bind(user_id)
+    .fetch_all(pool)
     .await?;
 
-    let response: Vec<CategoryResponse> = categories
+    Ok(categories
         .into_iter()
         .map(
-            |(id, name, parent_id, depth, link_count)| CategoryResponse {
+            |(id, name, parent_id, depth, sort_order, link_count)| CategoryResponse {
                 id,
                 name,
                 parent_id,
                 depth,
+                sort_order,
                 link_count,
             },
         )
-        .collect();
+        .collect())
+}
+
+/// GET /api/categories
+async fn list_categories(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Vec<CategoryResponse>>, AppError> {
+    let response = category_responses(&pool, auth.user_id).await?;
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize)]
+struct ReorderCategoriesRequest {
+    /// Category ids in their new order
+    ids: Vec<Uuid>,
+}
 
+/// PUT /api/categories/reorder
+///
+/// Sets the manual order of the listed categories to their position in
+/// `ids` and returns the full category list in the new order.
+async fn reorder_categories(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Json(request): Json<ReorderCategoriesRequest>,
+) -> Result<Json<Vec<CategoryResponse>>, AppError> {
+    Category::reorder(&pool, auth.user_id, &request.ids).await?;
+    let response = category_responses(&pool, auth.user_id).await?;
     Ok(Json(response))
 }
 
+/// GET /api/categories/orphaned
+///
+/// Lists the user's categories that have no links and no subcategories.
+async fn list_orphaned_categories(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<OrphanedCategoriesResponse>, AppError> {
+    let categories = Category::get_orphaned(&pool, auth.user_id).await?;
+    Ok(Json(OrphanedCategoriesResponse {
+        count: categories.len(),
+        categories,
+    }))
+}
+
 /// GET /api/categories/tree
 async fn get_category_tree(
     State(pool): State<PgPool>,
@@ -91,14 +141,14 @@ async fn get_category(
     auth: AuthenticatedUser,
     Path(id): Path<Uuid>,
 ) -> Result<Json<CategoryResponse>, AppError> {
-    let result = sqlx::query_as::<_, (Uuid, String, Option<Uuid>, i32, i64)>(
+    let result = sqlx::query_as::<_, (Uuid, String, Option<Uuid>, i32, Option<i32>, i64)>(
         r#"
-        SELECT c.id, c.name, c.parent_id, c.depth, COUNT(lc.link_id) as link_count
+        SELECT c.id, c.name, c.parent_id, c.depth, c.sort_order, COUNT(lc.link_id) as link_count
         FROM categories c
         LEFT JOIN link_categories lc ON c.id = lc.category_id
         LEFT JOIN links l ON lc.link_id = l.id AND l.user_id = $1
         WHERE c.id = $2 AND c.user_id = $1
-        GROUP BY c.id, c.name, c.parent_id, c.depth
+        GROUP BY c.id, c.name, c.parent_id, c.depth, c.sort_order
         "#,
     )
     .bind(auth.user_id)
@@ -112,7 +162,8 @@ async fn get_category(
         name: result.1,
         parent_id: result.2,
         depth: result.3,
-        link_count: result.4,
+        sort_order: result.4,
+        link_count: result.5,
     };
 
     Ok(Json(response))
@@ -150,6 +201,7 @@ async fn update_category(
         name: category.name,
         parent_id: category.parent_id,
         depth: category.depth,
+        sort_order: category.sort_order,
         link_count,
     };
 
@@ -171,6 +223,8 @@ pub fn create_router() -> Router<super::AppState> {
     Router::new()
         .route("/", post(create_category).get(list_categories))
         .route("/tree", get(get_category_tree))
+        .route("/orphaned", get(list_orphaned_categories))
+        .route("/reorder", put(reorder_categories))
         .route(
             "/{id}",
             get(get_category)
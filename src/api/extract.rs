@@ -0,0 +1,205 @@
+//! Request extractors shared by the API handlers
+
+use axum::{
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+
+use crate::error::AppError;
+
+/// JSON request body whose parse errors are reported as validation errors
+///
+/// Works like `axum::Json`, but malformed JSON and bodies that don't match the
+/// expected shape are answered with a 400 `VALIDATION_ERROR` naming the
+/// offending field (e.g. `links[2].url`) instead of axum's plain-text
+/// rejection. Other rejections, such as an oversized body, are passed through.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonBody<T>(pub T);
+
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::Json::<T>::from_request(req, state).await {
+            Ok(axum::Json(value)) => Ok(JsonBody(value)),
+            Err(rejection) => Err(json_rejection_error(rejection)),
+        }
+    }
+}
+
+/// Prefix axum puts before the serde error in a data error's message
+const DATA_ERROR_PREFIX: &str = "Failed to deserialize the JSON body into the target type: ";
+
+/// Turn a JSON rejection into the response sent to the client
+fn json_rejection_error(rejection: JsonRejection) -> Response {
+    match rejection {
+        JsonRejection::JsonDataError(e) => {
+            let detail = e.body_text();
+            let detail = detail.strip_prefix(DATA_ERROR_PREFIX).unwrap_or(&detail);
+            let (field, message) = field_error(detail);
+            AppError::validation(&field, &message).into_response()
+        }
+        JsonRejection::JsonSyntaxError(e) => {
+            let detail = e.body_text();
+            let message = detail
+                .split_once(": ")
+                .map_or(detail.as_str(), |(_, message)| message);
+            AppError::validation("body", &format!("Malformed JSON: {}", message)).into_response()
+        }
+        JsonRejection::MissingJsonContentType(_) => {
+            AppError::validation("body", "Expected Content-Type: application/json").into_response()
+        }
+        other => other.into_response(),
+    }
+}
+
+/// Split a serde error into the field it concerns and the message
+///
+/// Errors look like `links[0].url: invalid type: ...` for a nested value and
+/// ``missing field `url` at ...`` at the top level. A missing field is reported
+/// under its own name rather than under the object that lacks it.
+fn field_error(detail: &str) -> (String, String) {
+    let (path, message) = match detail.split_once(": ") {
+        Some((path, message)) if !path.is_empty() && !path.contains(' ') => (Some(path), message),
+        _ => (None, detail),
+    };
+
+    let missing = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split_once('`'))
+        .map(|(name, _)| name);
+
+    let field = match (path, missing) {
+        (Some(path), Some(name)) => format!("{}.{}", path, name),
+        (None, Some(name)) => name.to_string(),
+        (Some(path), None) => path.to_string(),
+        (None, None) => "body".to_string(),
+    };
+
+    (field, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Entry {
+        url: String,
+        tags: Option<Vec<String>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Payload {
+        links: Vec<Entry>,
+    }
+
+    async fn extract<T: DeserializeOwned>(
+        body: &'static str,
+        content_type: &str,
+    ) -> Result<T, (StatusCode, serde_json::Value)> {
+        let req = Request::builder()
+            .method("POST")
+            .header("content-type", content_type)
+            .body(Body::from(body))
+            .unwrap();
+        match JsonBody::<T>::from_request(req, &()).await {
+            Ok(JsonBody(value)) => Ok(value),
+            Err(response) => {
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                Err((status, serde_json::from_slice(&body).unwrap()))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valid_body_is_extracted() {
+        let entry: Entry = extract(r#"{"url": "https://example.com"}"#, "application/json")
+            .await
+            .unwrap();
+        assert_eq!(entry.url, "https://example.com");
+    }
+
+    #[tokio::test]
+    async fn test_missing_required_field() {
+        let (status, json) = extract::<Entry>(r#"{"tags": []}"#, "application/json")
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["code"], "VALIDATION_ERROR");
+        assert!(json["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("url: missing field `url`"));
+
+        let (_, json) = extract::<Payload>(r#"{"links": [{"tags": []}]}"#, "application/json")
+            .await
+            .unwrap_err();
+        assert!(json["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("links[0].url: missing field `url`"));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_type_names_field_path() {
+        let body = r#"{"links": [{"url": "https://a.example"}, {"url": "https://b.example", "tags": "rust"}]}"#;
+        let (status, json) = extract::<Payload>(body, "application/json")
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(json["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("links[1].tags: invalid type: string \"rust\""));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_and_content_type() {
+        let (status, json) = extract::<Entry>("{\"url\": ", "application/json")
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(json["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("body: Malformed JSON:"));
+
+        let (status, json) = extract::<Entry>(r#"{"url": "x"}"#, "text/plain")
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["code"], "VALIDATION_ERROR");
+    }
+
+    #[test]
+    fn test_field_error() {
+        assert_eq!(
+            field_error("invalid type: null, expected a string at line 1 column 11"),
+            (
+                "body".to_string(),
+                "invalid type: null, expected a string at line 1 column 11".to_string()
+            )
+        );
+        assert_eq!(
+            field_error("title: invalid type: integer `1`, expected a string"),
+            (
+                "title".to_string(),
+                "invalid type: integer `1`, expected a string".to_string()
+            )
+        );
+    }
+}
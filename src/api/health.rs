@@ -9,6 +9,11 @@ use crate::api::AppState;
 pub struct SchedulerHealthResponse {
     pub status: String,
     pub running: bool,
+    /// Links currently due for checking (`None` if the count failed)
+    pub links_due: Option<i64>,
+    pub batch_size: usize,
+    /// Batch size actually used by the last cycle (raised when backlogged)
+    pub effective_batch_size: usize,
 }
 
 /// Scheduler health check endpoint
@@ -23,6 +28,11 @@ pub async fn scheduler_health(
     // If shutdown signal is set, scheduler is stopping or stopped
     let running = !state.scheduler_shutdown.load(Ordering::Relaxed);
 
+    let links_due =
+        crate::models::Link::count_needing_check(&state.pool, state.config.update_interval_hours)
+            .await
+            .ok();
+
     let response = SchedulerHealthResponse {
         status: if running {
             "healthy".to_string()
@@ -30,6 +40,9 @@ pub async fn scheduler_health(
             "stopped".to_string()
         },
         running,
+        links_due,
+        batch_size: state.config.batch_size,
+        effective_batch_size: state.scheduler_batch_size.load(Ordering::Relaxed),
     };
 
     Ok(Json(response))
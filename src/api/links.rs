@@ -6,27 +6,37 @@
 //! - PUT /api/links/:id - Update a link
 //! - DELETE /api/links/:id - Delete a link
 
+use crate::api::extract::JsonBody;
 use crate::auth::middleware::AuthenticatedUser;
-use crate::error::AppError;
+use crate::config::Config;
+use crate::error::{ApiErrorResponse, AppError};
+use crate::events::{EventBus, LinkEvent};
+use crate::jobs::{Job, JobQueue};
+use crate::models::category::parse_category_path;
+use crate::models::idempotency_key::MAX_IDEMPOTENCY_KEY_LEN;
+use crate::models::tag::normalize_tag_name;
 use crate::models::{
-    Category, CreateLink, Language, License, Link, LinkSearchParams, LinkWithCategories, Tag,
-    UpdateLink,
+    Category, CreateLink, IdempotencyKey, IdempotencyReservation, Language, License, Link,
+    LinkSearchParams, LinkWithCategories, Tag, TimelineMonth, UpdateLink, UserSettings,
 };
 use crate::scraper;
+use crate::services;
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     routing::post,
     Json, Router,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use utoipa::{OpenApi, ToSchema};
 use uuid::Uuid;
 
 /// Request body for creating a link with optional categorization
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct CreateLinkWithCategories {
     pub url: String,
     pub title: Option<String>,
@@ -59,17 +69,114 @@ struct CreateLinkWithCategories {
 /// }
 /// ```
 ///
+/// Without `category_ids`, the user's default category (see
+/// `PUT /api/settings`) is attached, if one is set.
+///
+/// When `BACKGROUND_SCRAPE` is enabled, metadata is fetched by the background
+/// job worker after the response is sent instead of during the request.
+///
+/// # Idempotency
+/// An optional `Idempotency-Key` header makes retries safe: a repeated key
+/// within 24 hours returns the link created by the first request instead of
+/// creating another one. A repeat that arrives while the first request is
+/// still running gets 409 Conflict.
+///
 /// # Response
-/// - 201 Created: Returns the created link
-/// - 400 Bad Request: Invalid URL format
+/// - 201 Created: Returns the created link (or the original link on replay)
+/// - 400 Bad Request: Invalid URL format or `Idempotency-Key` header
 /// - 401 Unauthorized: No valid session
+/// - 409 Conflict: A request with the same `Idempotency-Key` is in progress
+#[utoipa::path(
+    post,
+    path = "/api/links",
+    request_body = CreateLinkWithCategories,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Makes retries return the first request's link")),
+    responses(
+        (status = 201, description = "Link created, or the original link on replay", body = Link),
+        (status = 400, description = "Invalid URL or Idempotency-Key", body = ApiErrorResponse),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 409, description = "A request with the same Idempotency-Key is in progress", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
 async fn create_link_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
+    State(jobs): State<JobQueue>,
+    State(events): State<EventBus>,
     auth: AuthenticatedUser,
-    Json(request): Json<CreateLinkWithCategories>,
+    headers: HeaderMap,
+    JsonBody(request): JsonBody<CreateLinkWithCategories>,
 ) -> Result<impl IntoResponse, AppError> {
     let user_id = auth.user_id;
 
+    // Reserve the key before creating anything, so a concurrent retry can't
+    // create a second link
+    let idempotency_key = parse_idempotency_key(&headers)?;
+    if let Some(ref key) = idempotency_key {
+        match IdempotencyKey::reserve(&pool, user_id, key).await? {
+            IdempotencyReservation::Reserved => {}
+            IdempotencyReservation::Completed(link_id) => {
+                tracing::info!(
+                    user_id = %user_id,
+                    link_id = %link_id,
+                    "Replaying link creation for repeated idempotency key"
+                );
+                let link = Link::get_by_id(&pool, link_id, user_id).await?;
+                return Ok((StatusCode::CREATED, Json(link)));
+            }
+            IdempotencyReservation::Pending => {
+                return Err(AppError::Conflict(
+                    "A request with this Idempotency-Key is still in progress. Retry shortly."
+                        .to_string(),
+                ));
+            }
+        }
+    }
+
+    let result = create_link_for_user(
+        &pool,
+        &config,
+        &jobs,
+        &events,
+        user_id,
+        request,
+        config.background_scrape,
+    )
+    .await;
+
+    if let Some(ref key) = idempotency_key {
+        let recorded = match &result {
+            Ok(link) => IdempotencyKey::complete(&pool, user_id, key, link.id).await,
+            // Let the client retry the failed request with the same key
+            Err(_) => IdempotencyKey::release(&pool, user_id, key).await,
+        };
+        if let Err(e) = recorded {
+            tracing::warn!(
+                user_id = %user_id,
+                error = %e,
+                "Failed to record idempotency key outcome"
+            );
+        }
+    }
+
+    Ok((StatusCode::CREATED, Json(result?)))
+}
+
+/// Create a link with its initial associations and fetch its metadata
+///
+/// Shared by the JSON and quick-add endpoints. With `background_scrape`,
+/// metadata is left to the job worker; otherwise it's fetched before the
+/// link is stored. Returns the link as stored after all updates.
+async fn create_link_for_user(
+    pool: &PgPool,
+    config: &Config,
+    jobs: &JobQueue,
+    events: &EventBus,
+    user_id: Uuid,
+    request: CreateLinkWithCategories,
+    background_scrape: bool,
+) -> Result<Link, AppError> {
     crate::security::validate_url_for_ssrf(&request.url)?;
 
     tracing::info!(
@@ -80,24 +187,35 @@ async fn create_link_handler(
         "Creating new link"
     );
 
-    // Build the CreateLink struct for the model
-    let mut create_link = CreateLink {
-        url: request.url.clone(),
+    // Metadata fetched below only fills in what the user didn't provide
+    let provided = scraper::ScrapedMetadata {
         title: request.title.clone(),
         description: request.description.clone(),
-        logo: request.logo.clone(),
+        favicon: request.logo.clone(),
+        ..Default::default()
     };
+    let mut fetched = scraper::ScrapedMetadata::default();
 
     // Check if this is a GitHub repository
-    let is_github = crate::github::is_github_repo(&request.url);
+    let is_github = config.github.is_github_repo(&request.url);
     let mut github_metadata = None;
 
-    if is_github {
+    if background_scrape {
+        tracing::debug!(url = %request.url, "Deferring metadata fetch to background worker");
+    } else if is_github {
         tracing::info!(url = %request.url, "Detected GitHub repository URL");
 
         // Try to fetch GitHub metadata
-        if let Some((owner, repo)) = crate::github::parse_repo_from_url(&request.url) {
-            match crate::github::fetch_repo_metadata(&owner, &repo).await {
+        if let Some((owner, repo)) = config.github.parse_repo_from_url(&request.url) {
+            let user_token = UserSettings::github_token(pool, config, user_id).await?;
+            match crate::github::fetch_repo_metadata_as(
+                config,
+                &owner,
+                &repo,
+                user_token.as_deref(),
+            )
+            .await
+            {
                 Ok(metadata) => {
                     tracing::info!(
                         owner = %owner,
@@ -106,11 +224,8 @@ async fn create_link_handler(
                         "Successfully fetched GitHub metadata"
                     );
 
-                    // Use GitHub description if user didn't provide one
-                    if create_link.description.is_none() && metadata.description.is_some() {
-                        create_link.description = metadata.description.clone();
-                        tracing::debug!("Using GitHub description");
-                    }
+                    // GitHub supplies the description; the title stays the user's
+                    fetched.description = metadata.description.clone();
 
                     github_metadata = Some(metadata);
                 }
@@ -126,42 +241,79 @@ async fn create_link_handler(
         }
     } else {
         // Not a GitHub repo - try regular web scraping
-        if let Ok(metadata) = scraper::scrape_url(&request.url).await {
-            // Use scraped data only if user didn't provide it
-            if create_link.title.is_none() && metadata.title.is_some() {
-                create_link.title = metadata.title;
-                tracing::debug!("Using scraped title");
-            }
-            if create_link.description.is_none() && metadata.description.is_some() {
-                create_link.description = metadata.description;
-                tracing::debug!("Using scraped description");
-            }
-            if create_link.logo.is_none() && metadata.favicon.is_some() {
-                create_link.logo = metadata.favicon;
-                tracing::debug!("Using scraped favicon");
-            }
+        if let Ok(metadata) = scraper::scrape_url(&request.url, config).await {
+            fetched = metadata;
         } else {
             tracing::warn!(url = %request.url, "Failed to scrape URL, continuing with user-provided data");
         }
     }
 
+    // The background fetch must not replace what the user supplied either
+    let refresh_options = services::RefreshOptions::filling(&provided);
+    let merged = provided.merge(fetched);
+    let create_link = CreateLink {
+        url: request.url.clone(),
+        title: merged.title,
+        description: merged.description,
+        logo: merged.favicon,
+        final_url: merged.final_url,
+    };
+
     // Create the link
-    let link = Link::create(&pool, user_id, create_link).await?;
+    let link = Link::create(pool, config, user_id, create_link).await?;
 
     // If we have GitHub metadata, update the link with it
-    if let Some(metadata) = github_metadata {
-        if let Err(e) = Link::update_github_metadata(&pool, link.id, user_id, metadata).await {
+    if let Some(mut metadata) = github_metadata {
+        let topics = std::mem::take(&mut metadata.topics);
+
+        if let Err(e) = Link::update_github_metadata(pool, config, link.id, user_id, metadata).await
+        {
             tracing::warn!(
                 link_id = %link.id,
                 error = %e,
                 "Failed to update GitHub metadata after link creation"
             );
         }
+
+        // Attach tags matching the repository topics
+        if config.import_github_topics {
+            for topic in &topics {
+                let result = match Tag::get_or_create_by_name(pool, config, user_id, topic).await {
+                    Ok(tag) => Link::add_tag(pool, link.id, tag.id, user_id).await,
+                    Err(e) => Err(e),
+                };
+
+                if let Err(e) = result {
+                    tracing::warn!(
+                        link_id = %link.id,
+                        topic = %topic,
+                        error = %e,
+                        "Failed to attach GitHub topic tag to new link"
+                    );
+                }
+            }
+        }
     }
 
-    // Add initial categorization
-    for category_id in &request.category_ids {
-        if let Err(e) = Link::add_category(&pool, link.id, *category_id, user_id).await {
+    // Add initial categorization, falling back to the user's default category
+    let category_ids = if request.category_ids.is_empty() {
+        match UserSettings::get(pool, user_id).await {
+            Ok(settings) => settings.default_category_id.into_iter().collect(),
+            Err(e) => {
+                tracing::warn!(
+                    link_id = %link.id,
+                    error = %e,
+                    "Failed to load default category for new link"
+                );
+                Vec::new()
+            }
+        }
+    } else {
+        request.category_ids.clone()
+    };
+
+    for category_id in &category_ids {
+        if let Err(e) = Link::add_category(pool, link.id, *category_id, user_id).await {
             tracing::warn!(
                 link_id = %link.id,
                 category_id = %category_id,
@@ -172,7 +324,7 @@ async fn create_link_handler(
     }
 
     for tag_id in &request.tag_ids {
-        if let Err(e) = Link::add_tag(&pool, link.id, *tag_id, user_id).await {
+        if let Err(e) = Link::add_tag(pool, link.id, *tag_id, user_id).await {
             tracing::warn!(
                 link_id = %link.id,
                 tag_id = %tag_id,
@@ -183,7 +335,7 @@ async fn create_link_handler(
     }
 
     for language_id in &request.language_ids {
-        if let Err(e) = Link::add_language(&pool, link.id, *language_id, user_id).await {
+        if let Err(e) = Link::add_language(pool, link.id, *language_id, user_id).await {
             tracing::warn!(
                 link_id = %link.id,
                 language_id = %language_id,
@@ -194,7 +346,7 @@ async fn create_link_handler(
     }
 
     for license_id in &request.license_ids {
-        if let Err(e) = Link::add_license(&pool, link.id, *license_id, user_id).await {
+        if let Err(e) = Link::add_license(pool, link.id, *license_id, user_id).await {
             tracing::warn!(
                 link_id = %link.id,
                 license_id = %license_id,
@@ -204,20 +356,234 @@ async fn create_link_handler(
         }
     }
 
+    // Infer a language from the file extension for non-GitHub code links
+    if config.auto_detect_language && !is_github && request.language_ids.is_empty() {
+        if let Some(language_name) = scraper::detect_language_from_url(&request.url) {
+            let result = match Language::get_or_create_by_name(pool, user_id, language_name).await {
+                Ok(language) => Link::add_language(pool, link.id, language.id, user_id).await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => {
+                    tracing::debug!(
+                        link_id = %link.id,
+                        language = %language_name,
+                        "Attached language detected from URL"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        link_id = %link.id,
+                        language = %language_name,
+                        error = %e,
+                        "Failed to attach detected language to new link"
+                    );
+                }
+            }
+        }
+    }
+
+    if background_scrape {
+        // A full queue only delays the fetch: the scheduler picks up unchecked links
+        jobs.enqueue(Job::RefreshLink {
+            link_id: link.id,
+            user_id,
+            options: refresh_options,
+        });
+    }
+
     // Fetch the updated link to return with GitHub metadata
-    let updated_link = Link::get_by_id(&pool, link.id, user_id).await?;
+    let updated_link = Link::get_by_id(pool, link.id, user_id).await?;
 
-    Ok((StatusCode::CREATED, Json(updated_link)))
+    events.publish(user_id, LinkEvent::LinkCreated { id: link.id });
+
+    Ok(updated_link)
+}
+
+/// Query parameters for quick add
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct QuickAddQuery {
+    /// URL to save; the request body is used when absent
+    url: Option<String>,
+}
+
+/// POST /api/links/quick
+///
+/// Saves a URL given either as the `url` query parameter or as the raw
+/// request body (`text/plain`), for bookmarklets and browser extensions that
+/// can't easily send JSON. Metadata is always fetched in the background, so
+/// the link comes back right away without a title or description. The
+/// user's default category is attached as for `POST /api/links`.
+///
+/// # Examples
+/// - POST /api/links/quick?url=https%3A%2F%2Fexample.com
+/// - POST /api/links/quick with body `https://example.com`
+///
+/// # Response
+/// - 201 Created: The created link
+/// - 400 Bad Request: Missing or invalid URL
+/// - 401 Unauthorized: No valid session
+/// - 409 Conflict: A link with this URL already exists
+#[utoipa::path(
+    post,
+    path = "/api/links/quick",
+    params(QuickAddQuery),
+    request_body(content = String, content_type = "text/plain", description = "URL to save, if not given as `url`"),
+    responses(
+        (status = 201, description = "Link created", body = Link),
+        (status = 400, description = "Missing or invalid URL", body = ApiErrorResponse),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 409, description = "A link with this URL already exists", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn quick_add_handler(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    State(jobs): State<JobQueue>,
+    State(events): State<EventBus>,
+    auth: AuthenticatedUser,
+    Query(query): Query<QuickAddQuery>,
+    body: String,
+) -> Result<impl IntoResponse, AppError> {
+    let url = quick_add_url(query.url.as_deref(), &body)
+        .ok_or_else(|| AppError::validation("url", "A URL is required"))?;
+
+    let request = CreateLinkWithCategories {
+        url,
+        title: None,
+        description: None,
+        logo: None,
+        category_ids: Vec::new(),
+        tag_ids: Vec::new(),
+        language_ids: Vec::new(),
+        license_ids: Vec::new(),
+    };
+    let link =
+        create_link_for_user(&pool, &config, &jobs, &events, auth.user_id, request, true).await?;
+
+    Ok((StatusCode::CREATED, Json(link)))
+}
+
+/// The URL to quick-add: the query parameter if given, else the body
+fn quick_add_url(query_url: Option<&str>, body: &str) -> Option<String> {
+    query_url
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .or_else(|| Some(body.trim()).filter(|url| !url.is_empty()))
+        .map(str::to_string)
+}
+
+/// Read the optional `Idempotency-Key` header
+///
+/// Blank values are treated as absent; values that are not visible ASCII or
+/// longer than `MAX_IDEMPOTENCY_KEY_LEN` are rejected.
+fn parse_idempotency_key(headers: &HeaderMap) -> Result<Option<String>, AppError> {
+    let Some(value) = headers.get("idempotency-key") else {
+        return Ok(None);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| AppError::validation("Idempotency-Key", "Must be visible ASCII text"))?
+        .trim();
+
+    if key.is_empty() {
+        return Ok(None);
+    }
+    if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(AppError::validation(
+            "Idempotency-Key",
+            &format!("Must be at most {} characters", MAX_IDEMPOTENCY_KEY_LEN),
+        ));
+    }
+
+    Ok(Some(key.to_string()))
 }
 
 /// Response structure for paginated links with metadata
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, ToSchema)]
 struct PaginatedResponse {
     links: Vec<LinkWithCategories>,
     total: i64,
     page: u32,
     per_page: u32,
     total_pages: u32,
+    /// Relative URLs of neighbouring pages, keeping the current filters and sort
+    #[serde(rename = "_links")]
+    pagination_links: PaginationLinks,
+}
+
+/// Ready-to-use URLs for navigating a paginated listing
+#[derive(Debug, PartialEq, serde::Serialize, ToSchema)]
+struct PaginationLinks {
+    first: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next: Option<String>,
+    last: String,
+}
+
+impl PaginationLinks {
+    /// Build page URLs for `/api/links` from the request's search parameters
+    fn new(params: &LinkSearchParams, page: u32, per_page: u32, total_pages: u32) -> Self {
+        let last_page = total_pages.max(1);
+        let page_url = |page: u32| {
+            let mut query = url::form_urlencoded::Serializer::new(String::new());
+            if let Some(ref q) = params.query {
+                query.append_pair("query", q);
+            }
+            if let Some(ref status) = params.status {
+                query.append_pair("status", status);
+            }
+            if let Some(is_github) = params.is_github {
+                query.append_pair("is_github", &is_github.to_string());
+            }
+            if let Some(is_pinned) = params.is_pinned {
+                query.append_pair("is_pinned", &is_pinned.to_string());
+            }
+            for (key, id) in [
+                ("category_id", params.category_id),
+                ("tag_id", params.tag_id),
+                ("language_id", params.language_id),
+                ("license_id", params.license_id),
+            ] {
+                if let Some(id) = id {
+                    query.append_pair(key, &id.to_string());
+                }
+            }
+            if let Some(ref sort_by) = params.sort_by {
+                query.append_pair("sort_by", sort_by);
+            }
+            if let Some(ref sort_order) = params.sort_order {
+                query.append_pair("sort_order", sort_order);
+            }
+            if let Some(highlight) = params.highlight {
+                query.append_pair("highlight", &highlight.to_string());
+            }
+            for (key, time) in [
+                ("created_after", params.created_after),
+                ("created_before", params.created_before),
+            ] {
+                if let Some(time) = time {
+                    query.append_pair(key, &time.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+                }
+            }
+            query.append_pair("page", &page.to_string());
+            query.append_pair("per_page", &per_page.to_string());
+            format!("/api/links?{}", query.finish())
+        };
+
+        Self {
+            first: page_url(1),
+            prev: (page > 1).then(|| page_url((page - 1).min(last_page))),
+            next: (page < last_page).then(|| page_url(page + 1)),
+            last: page_url(last_page),
+        }
+    }
 }
 
 /// GET /api/links
@@ -227,16 +593,23 @@ struct PaginatedResponse {
 ///
 /// # Query Parameters
 /// - `query`: Optional text search across title, description, url, domain
-/// - `status`: Optional filter by status (active, archived, inaccessible, repo_unavailable)
+/// - `status`: Optional filter by status (active, archived, inaccessible, repo_unavailable, private_repo, repo_takedown)
 /// - `is_github`: Optional filter for GitHub repositories only (true/false)
+/// - `is_pinned`: Optional filter by pinned state (true/false)
 /// - `category_id`: Optional filter by category UUID
 /// - `tag_id`: Optional filter by tag UUID
 /// - `language_id`: Optional filter by programming language UUID
 /// - `license_id`: Optional filter by software license UUID
-/// - `sort_by`: Optional sort field (created_at, updated_at, title, github_stars, status) - default: created_at
-/// - `sort_order`: Optional sort order (asc, desc) - default: desc
+/// - `sort_by`: Optional sort field (created_at, updated_at, title, github_stars, status) - default: created_at.
+///   Pinned links always come first.
+/// - `sort_order`: Optional sort order (asc, desc) - default: asc for title and
+///   status, desc for created_at, updated_at and github_stars
+/// - `created_after`: Optional RFC 3339 time; only links created at or after it
+/// - `created_before`: Optional RFC 3339 time; only links created before it
 /// - `page`: Optional page number (default: 1)
 /// - `per_page`: Optional items per page (default: 20, max: 100)
+/// - `highlight`: Optional; when true with `query`, each link carries a
+///   `matches` object flagging which of title/description/url/domain matched
 ///
 /// # Examples
 /// - GET /api/links - All links page 1 (sorted by created_at desc)
@@ -245,15 +618,31 @@ struct PaginatedResponse {
 /// - GET /api/links?query=rust - Search for "rust"
 /// - GET /api/links?status=active - Only active links
 /// - GET /api/links?is_github=true - Only GitHub repos
+/// - GET /api/links?is_pinned=true - Only pinned links
 /// - GET /api/links?sort_by=title&sort_order=asc - Sort by title A-Z
 /// - GET /api/links?sort_by=github_stars&sort_order=desc - Sort by stars (highest first)
+/// - GET /api/links?created_after=2026-03-01T00:00:00Z&created_before=2026-04-01T00:00:00Z - Links added in March 2026
 /// - GET /api/links?query=rust&status=active&page=2 - Combined filters and pagination
 ///
 /// # Response
-/// - 200 OK: Returns paginated links with metadata (total, page, per_page, total_pages)
+/// - 200 OK: Returns paginated links with metadata (total, page, per_page,
+///   total_pages) and `_links` with first/prev/next/last page URLs
 /// - 401 Unauthorized: No valid session
+/// - 504 Gateway Timeout: The search ran longer than `QUERY_TIMEOUT_MS`
+#[utoipa::path(
+    get,
+    path = "/api/links",
+    params(LinkSearchParams),
+    responses(
+        (status = 200, description = "Page of links with their metadata", body = PaginatedResponse),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 504, description = "The search took longer than the query timeout", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
 async fn list_links_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
     Query(params): Query<LinkSearchParams>,
 ) -> Result<Json<PaginatedResponse>, AppError> {
@@ -264,6 +653,7 @@ async fn list_links_handler(
         query = ?params.query,
         status = ?params.status,
         is_github = ?params.is_github,
+        is_pinned = ?params.is_pinned,
         category_id = ?params.category_id,
         tag_id = ?params.tag_id,
         language_id = ?params.language_id,
@@ -276,7 +666,8 @@ async fn list_links_handler(
     );
 
     // Use search_paginated function which handles all filtering and pagination
-    let paginated = Link::search_paginated(&pool, user_id, &params).await?;
+    let paginated =
+        Link::search_paginated(&pool, user_id, &params, config.query_timeout_ms).await?;
 
     tracing::debug!(
         user_id = %user_id,
@@ -293,6 +684,12 @@ async fn list_links_handler(
     let mut tags_map = Link::get_tags_batch(&pool, &link_ids).await?;
     let mut languages_map = Link::get_languages_batch(&pool, &link_ids).await?;
     let mut licenses_map = Link::get_licenses_batch(&pool, &link_ids).await?;
+    let mut matches_map = match params.query.as_deref() {
+        Some(query) if params.highlight == Some(true) => {
+            Link::search_matches(&pool, user_id, &link_ids, query).await?
+        }
+        _ => HashMap::new(),
+    };
 
     let mut links_with_metadata = Vec::with_capacity(paginated.links.len());
     for link in paginated.links {
@@ -300,6 +697,7 @@ async fn list_links_handler(
         let tags = tags_map.remove(&link.id).unwrap_or_default();
         let languages = languages_map.remove(&link.id).unwrap_or_default();
         let licenses = licenses_map.remove(&link.id).unwrap_or_default();
+        let matches = matches_map.remove(&link.id);
 
         links_with_metadata.push(LinkWithCategories {
             link,
@@ -307,18 +705,196 @@ async fn list_links_handler(
             tags,
             languages,
             licenses,
+            matches,
         });
     }
 
+    let pagination_links = PaginationLinks::new(
+        &params,
+        paginated.page,
+        paginated.per_page,
+        paginated.total_pages,
+    );
+
     Ok(Json(PaginatedResponse {
         links: links_with_metadata,
         total: paginated.total,
         page: paginated.page,
         per_page: paginated.per_page,
         total_pages: paginated.total_pages,
+        pagination_links,
     }))
 }
 
+/// GET /api/links/status-counts
+///
+/// Returns the number of the user's links in each status, e.g.
+/// `{"active": 12, "archived": 3, ...}`. Every known status is included, with
+/// 0 when unused.
+///
+/// # Response
+/// - 200 OK: Map of status to link count
+/// - 401 Unauthorized: No valid session
+#[utoipa::path(
+    get,
+    path = "/api/links/status-counts",
+    responses(
+        (status = 200, description = "Number of links per status", body = BTreeMap<String, i64>),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn status_counts_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<BTreeMap<String, i64>>, AppError> {
+    let counts = Link::status_counts(&pool, auth.user_id).await?;
+    Ok(Json(counts))
+}
+
+/// GET /api/links/timeline
+///
+/// Returns how many links the user added in each calendar month (UTC), oldest
+/// first, e.g. `[{"year": 2026, "month": 3, "count": 12}]`. Months without
+/// links are omitted. Pass a month's bounds as `created_after` /
+/// `created_before` to `GET /api/links` to list its links.
+///
+/// # Response
+/// - 200 OK: Link counts per month
+/// - 401 Unauthorized: No valid session
+#[utoipa::path(
+    get,
+    path = "/api/links/timeline",
+    responses(
+        (status = 200, description = "Number of links added per month", body = [TimelineMonth]),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn timeline_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Vec<TimelineMonth>>, AppError> {
+    let months = Link::timeline(&pool, auth.user_id).await?;
+    Ok(Json(months))
+}
+
+/// Query parameters for `GET /api/links/failed-since`
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+struct FailedSinceQuery {
+    /// Only links that broke after this time (RFC 3339)
+    since: DateTime<Utc>,
+}
+
+/// GET /api/links/failed-since?since=...
+///
+/// Returns the user's links whose status changed to `inaccessible`,
+/// `repo_unavailable` or `repo_takedown` after `since` and is still failed,
+/// most recently broken first. Meant for digests and "newly broken" badges.
+///
+/// # Response
+/// - 200 OK: The newly broken links
+/// - 400 Bad Request: `since` is missing or not an RFC 3339 timestamp
+/// - 401 Unauthorized: No valid session
+#[utoipa::path(
+    get,
+    path = "/api/links/failed-since",
+    params(FailedSinceQuery),
+    responses(
+        (status = 200, description = "Links that broke after `since`", body = [Link]),
+        (status = 400, description = "Missing or invalid `since`", body = ApiErrorResponse),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn failed_since_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Query(query): Query<FailedSinceQuery>,
+) -> Result<Json<Vec<Link>>, AppError> {
+    let links = Link::recently_failed(&pool, auth.user_id, query.since).await?;
+    Ok(Json(links))
+}
+
+/// Most links listed in a feed
+const FEED_ITEM_LIMIT: i64 = 50;
+
+/// JSON Feed 1.1 version URL
+const JSON_FEED_VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+/// A JSON Feed 1.1 document (<https://jsonfeed.org/version/1.1>)
+#[derive(Debug, Serialize, ToSchema)]
+struct JsonFeed {
+    version: String,
+    title: String,
+    home_page_url: String,
+    feed_url: String,
+    items: Vec<JsonFeedItem>,
+}
+
+/// One link in a JSON Feed
+#[derive(Debug, Serialize, ToSchema)]
+struct JsonFeedItem {
+    /// The link's URL, which stays stable across edits of other fields
+    id: String,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    /// The description, empty when there is none (the spec requires content)
+    content_text: String,
+    date_published: String,
+}
+
+/// Build the JSON Feed for `links`, served from `host_url`
+fn json_feed(links: Vec<Link>, host_url: &str) -> JsonFeed {
+    let host_url = host_url.trim_end_matches('/');
+    JsonFeed {
+        version: JSON_FEED_VERSION.to_string(),
+        title: "Rusty Links".to_string(),
+        home_page_url: format!("{}/", host_url),
+        feed_url: format!("{}/api/links/feed.json", host_url),
+        items: links
+            .into_iter()
+            .map(|link| JsonFeedItem {
+                id: link.url.clone(),
+                url: link.url,
+                title: link.title,
+                content_text: link.description.unwrap_or_default(),
+                date_published: link.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            })
+            .collect(),
+    }
+}
+
+/// GET /api/links/feed.json
+///
+/// Returns the user's most recently added links (up to 50, archived links
+/// excluded) as a JSON Feed 1.1 document, for feed readers.
+///
+/// # Response
+/// - 200 OK: `application/feed+json` document
+/// - 401 Unauthorized: No valid session
+#[utoipa::path(
+    get,
+    path = "/api/links/feed.json",
+    responses(
+        (status = 200, description = "JSON Feed 1.1 of recent links", body = JsonFeed, content_type = "application/feed+json"),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn json_feed_handler(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    auth: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    let links = Link::recent_for_feed(&pool, auth.user_id, FEED_ITEM_LIMIT).await?;
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "application/feed+json")],
+        Json(json_feed(links, &config.host_url)),
+    ))
+}
+
 /// GET /api/links/:id
 ///
 /// Gets a single link by ID with all its metadata.
@@ -327,6 +903,17 @@ async fn list_links_handler(
 /// - 200 OK: Returns the link with categories, tags, languages, and licenses
 /// - 401 Unauthorized: No valid session
 /// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    get,
+    path = "/api/links/{id}",
+    params(("id" = Uuid, Path, description = "Link id")),
+    responses(
+        (status = 200, description = "The link with its metadata", body = LinkWithCategories),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
 async fn get_link_handler(
     State(pool): State<PgPool>,
     auth: AuthenticatedUser,
@@ -341,18 +928,63 @@ async fn get_link_handler(
     );
 
     let link = Link::get_by_id(&pool, id, user_id).await?;
-    let categories = Link::get_categories(&pool, id, user_id).await?;
-    let tags = Link::get_tags(&pool, id, user_id).await?;
-    let languages = Link::get_languages(&pool, id, user_id).await?;
-    let licenses = Link::get_licenses(&pool, id, user_id).await?;
 
-    Ok(Json(LinkWithCategories {
+    Ok(Json(with_associations(&pool, user_id, link).await?))
+}
+
+/// Load a link's categories, tags, languages and licenses
+async fn with_associations(
+    pool: &PgPool,
+    user_id: Uuid,
+    link: Link,
+) -> Result<LinkWithCategories, AppError> {
+    let id = link.id;
+    let categories = Link::get_categories(pool, id, user_id).await?;
+    let tags = Link::get_tags(pool, id, user_id).await?;
+    let languages = Link::get_languages(pool, id, user_id).await?;
+    let licenses = Link::get_licenses(pool, id, user_id).await?;
+
+    Ok(LinkWithCategories {
         link,
         categories,
         tags,
         languages,
         licenses,
-    }))
+        matches: None,
+    })
+}
+
+/// GET /api/links/by-url?url=...
+///
+/// Finds the user's saved link for a URL, e.g. so a browser extension can
+/// offer to edit the current tab's link. Matching ignores a `www.` prefix,
+/// the scheme and the query string, as duplicate detection does.
+///
+/// # Response
+/// - 200 OK: The link with categories, tags, languages, and licenses
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: The URL isn't saved
+#[utoipa::path(
+    get,
+    path = "/api/links/by-url",
+    params(("url" = String, Query, description = "URL to look up")),
+    responses(
+        (status = 200, description = "The saved link with its metadata", body = LinkWithCategories),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "The URL isn't saved", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn get_link_by_url_handler(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    auth: AuthenticatedUser,
+    Query(query): Query<CheckDuplicateQuery>,
+) -> Result<Json<LinkWithCategories>, AppError> {
+    let user_id = auth.user_id;
+    let link = Link::get_by_normalized_url(&pool, &config, user_id, query.url.trim()).await?;
+
+    Ok(Json(with_associations(&pool, user_id, link).await?))
 }
 
 /// PUT /api/links/:id
@@ -373,11 +1005,26 @@ async fn get_link_handler(
 /// - 400 Bad Request: Invalid status value
 /// - 401 Unauthorized: No valid session
 /// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    put,
+    path = "/api/links/{id}",
+    params(("id" = Uuid, Path, description = "Link id")),
+    request_body = UpdateLink,
+    responses(
+        (status = 200, description = "The updated link", body = Link),
+        (status = 400, description = "Invalid field value", body = ApiErrorResponse),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
 async fn update_link_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
+    State(events): State<EventBus>,
     auth: AuthenticatedUser,
     Path(id): Path<Uuid>,
-    Json(request): Json<UpdateLink>,
+    JsonBody(request): JsonBody<UpdateLink>,
 ) -> Result<Json<Link>, AppError> {
     let user_id = auth.user_id;
 
@@ -387,7 +1034,9 @@ async fn update_link_handler(
         "Updating link"
     );
 
-    let link = Link::update(&pool, id, user_id, request).await?;
+    let link = Link::update(&pool, &config, id, user_id, request).await?;
+
+    events.publish(user_id, LinkEvent::LinkUpdated { id });
 
     Ok(Json(link))
 }
@@ -400,8 +1049,20 @@ async fn update_link_handler(
 /// - 204 No Content: Link deleted successfully
 /// - 401 Unauthorized: No valid session
 /// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    delete,
+    path = "/api/links/{id}",
+    params(("id" = Uuid, Path, description = "Link id")),
+    responses(
+        (status = 204, description = "Link deleted"),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
 async fn delete_link_handler(
     State(pool): State<PgPool>,
+    State(events): State<EventBus>,
     auth: AuthenticatedUser,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
@@ -415,6 +1076,8 @@ async fn delete_link_handler(
 
     Link::delete(&pool, id, user_id).await?;
 
+    events.publish(user_id, LinkEvent::LinkDeleted { id });
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -500,67 +1163,617 @@ async fn get_tags_handler(
     Ok(Json(tags))
 }
 
+/// Default and maximum number of links returned by the similar endpoint
+const DEFAULT_SIMILAR_LIMIT: i64 = 5;
+const MAX_SIMILAR_LIMIT: i64 = 50;
+
 #[derive(Debug, serde::Deserialize)]
-struct AddLanguageRequest {
-    language_id: uuid::Uuid,
+struct SimilarLinksQuery {
+    limit: Option<i64>,
 }
 
-/// POST /api/links/:id/languages
-async fn add_language_handler(
+/// GET /api/links/:id/similar
+async fn similar_links_handler(
     State(pool): State<PgPool>,
     auth: AuthenticatedUser,
     Path(id): Path<uuid::Uuid>,
-    Json(request): Json<AddLanguageRequest>,
-) -> Result<Json<Vec<Language>>, AppError> {
-    let user_id = auth.user_id;
-    Link::add_language(&pool, id, request.language_id, user_id).await?;
-    let languages = Link::get_languages(&pool, id, user_id).await?;
-    Ok(Json(languages))
+    Query(query): Query<SimilarLinksQuery>,
+) -> Result<Json<Vec<LinkWithCategories>>, AppError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SIMILAR_LIMIT)
+        .clamp(1, MAX_SIMILAR_LIMIT);
+    let links = Link::find_similar(&pool, auth.user_id, id, limit).await?;
+    Ok(Json(links))
 }
 
-/// DELETE /api/links/:id/languages/:language_id
-async fn remove_language_handler(
-    State(pool): State<PgPool>,
-    auth: AuthenticatedUser,
-    Path((id, language_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-) -> Result<Json<Vec<Language>>, AppError> {
-    let user_id = auth.user_id;
-    Link::remove_language(&pool, id, language_id, user_id).await?;
-    let languages = Link::get_languages(&pool, id, user_id).await?;
-    Ok(Json(languages))
+/// Request body for duplicating a link
+#[derive(Debug, Deserialize, ToSchema)]
+struct DuplicateLinkRequest {
+    /// URL of the copy; must differ from the original's
+    url: String,
 }
 
-/// GET /api/links/:id/languages
-async fn get_languages_handler(
+/// POST /api/links/:id/duplicate
+///
+/// Creates a copy of a link under a new URL, keeping its title, description,
+/// notes and all categories, tags, languages and licenses. The URL must differ
+/// from the original since links are deduplicated by domain and path.
+///
+/// # Request Body
+/// ```json
+/// { "url": "https://example.com/docs/v2" }
+/// ```
+///
+/// # Response
+/// - 201 Created: The new link
+/// - 400 Bad Request: Invalid URL, or the same URL as the original
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Link not found or doesn't belong to user
+/// - 409 Conflict: A link with this URL already exists
+#[utoipa::path(
+    post,
+    path = "/api/links/{id}/duplicate",
+    params(("id" = Uuid, Path, description = "Link to copy")),
+    request_body = DuplicateLinkRequest,
+    responses(
+        (status = 201, description = "The new link", body = Link),
+        (status = 400, description = "Invalid URL or same URL as the original", body = ApiErrorResponse),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+        (status = 409, description = "A link with this URL already exists", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn duplicate_link_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
-    Path(id): Path<uuid::Uuid>,
-) -> Result<Json<Vec<Language>>, AppError> {
+    Path(id): Path<Uuid>,
+    Json(request): Json<DuplicateLinkRequest>,
+) -> Result<impl IntoResponse, AppError> {
     let user_id = auth.user_id;
-    let languages = Link::get_languages(&pool, id, user_id).await?;
-    Ok(Json(languages))
-}
+    let new_url = request.url.trim();
 
-#[derive(Debug, serde::Deserialize)]
-struct AddLicenseRequest {
-    license_id: uuid::Uuid,
+    crate::security::validate_url_for_ssrf(new_url)?;
+
+    let original = Link::get_by_id(&pool, id, user_id).await?;
+    if original.url == new_url {
+        return Err(AppError::validation(
+            "url",
+            "The copy needs a different URL from the original",
+        ));
+    }
+
+    let link = Link::duplicate(&pool, &config, id, user_id, new_url).await?;
+
+    Ok((StatusCode::CREATED, Json(link)))
 }
 
-/// POST /api/links/:id/licenses
-async fn add_license_handler(
+/// POST /api/links/:id/pin
+///
+/// Pins a link so it sorts ahead of unpinned links. Pinning doesn't change the
+/// link's status.
+///
+/// # Response
+/// - 200 OK: The updated link
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    post,
+    path = "/api/links/{id}/pin",
+    params(("id" = Uuid, Path, description = "Link to pin")),
+    responses(
+        (status = 200, description = "The pinned link", body = Link),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn pin_link_handler(
     State(pool): State<PgPool>,
     auth: AuthenticatedUser,
-    Path(id): Path<uuid::Uuid>,
-    Json(request): Json<AddLicenseRequest>,
-) -> Result<Json<Vec<License>>, AppError> {
-    let user_id = auth.user_id;
-    Link::add_license(&pool, id, request.license_id, user_id).await?;
-    let licenses = Link::get_licenses(&pool, id, user_id).await?;
-    Ok(Json(licenses))
+    Path(id): Path<Uuid>,
+) -> Result<Json<Link>, AppError> {
+    let link = Link::set_pinned(&pool, id, auth.user_id, true).await?;
+    Ok(Json(link))
 }
 
-/// DELETE /api/links/:id/licenses/:license_id
-async fn remove_license_handler(
+/// POST /api/links/:id/unpin
+///
+/// Unpins a link, returning it to its normal place in the sort order.
+///
+/// # Response
+/// - 200 OK: The updated link
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    post,
+    path = "/api/links/{id}/unpin",
+    params(("id" = Uuid, Path, description = "Link to unpin")),
+    responses(
+        (status = 200, description = "The unpinned link", body = Link),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn unpin_link_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Link>, AppError> {
+    let link = Link::set_pinned(&pool, id, auth.user_id, false).await?;
+    Ok(Json(link))
+}
+
+/// POST /api/links/:id/visit
+///
+/// Records that the user opened the link, keeping it from being
+/// auto-archived as unvisited (`AUTO_ARCHIVE_UNVISITED_DAYS`).
+///
+/// # Response
+/// - 204 No Content: Visit recorded
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Link not found or doesn't belong to user
+async fn record_visit_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    Link::record_visit(&pool, id, auth.user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SnippetQuery {
+    /// `markdown` (default) or `html`
+    format: Option<String>,
+}
+
+/// GET /api/links/:id/markdown
+///
+/// Returns a one-line `text/plain` snippet for pasting the link into a
+/// document: `[Title](url) — description — ★ stars` by default, or an
+/// `<a>` element with `?format=html`. The description and star count are
+/// omitted when absent.
+///
+/// # Response
+/// - 200 OK: The formatted snippet
+/// - 400 Bad Request: Unknown format
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    get,
+    path = "/api/links/{id}/markdown",
+    params(
+        ("id" = Uuid, Path, description = "Link id"),
+        ("format" = Option<String>, Query, description = "`markdown` (default) or `html`"),
+    ),
+    responses(
+        (status = 200, description = "One-line snippet", body = String, content_type = "text/plain"),
+        (status = 400, description = "Unknown format", body = ApiErrorResponse),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn link_markdown_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path(id): Path<uuid::Uuid>,
+    Query(query): Query<SnippetQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let link = Link::get_by_id(&pool, id, auth.user_id).await?;
+
+    let snippet = match query.format.as_deref() {
+        None | Some("markdown") => link_markdown(&link),
+        Some("html") => link_html(&link),
+        Some(_) => {
+            return Err(AppError::validation(
+                "format",
+                "Must be 'markdown' or 'html'",
+            ))
+        }
+    };
+
+    Ok((
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; charset=utf-8",
+        )],
+        snippet,
+    ))
+}
+
+/// GET /api/links/:id/notes.html
+///
+/// Renders the link's Markdown notes to HTML for display. The output is
+/// sanitized, so raw HTML, scripts and `javascript:` links in notes are
+/// dropped.
+///
+/// # Response
+/// - 200 OK: The rendered notes as `text/html`
+/// - 204 No Content: The link has no notes
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    get,
+    path = "/api/links/{id}/notes.html",
+    params(("id" = Uuid, Path, description = "Link id")),
+    responses(
+        (status = 200, description = "Rendered notes", body = String, content_type = "text/html"),
+        (status = 204, description = "Link has no notes"),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn link_notes_html_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<axum::response::Response, AppError> {
+    let link = Link::get_by_id(&pool, id, auth.user_id).await?;
+
+    let Some(html) = link.notes.as_deref().and_then(render_notes_html) else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "text/html; charset=utf-8")],
+        html,
+    )
+        .into_response())
+}
+
+/// Render Markdown notes to sanitized HTML, or `None` for blank notes
+fn render_notes_html(notes: &str) -> Option<String> {
+    if notes.trim().is_empty() {
+        return None;
+    }
+
+    let options = pulldown_cmark::Options::ENABLE_TABLES
+        | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+        | pulldown_cmark::Options::ENABLE_TASKLISTS;
+    let parser = pulldown_cmark::Parser::new_ext(notes, options);
+    let mut html = String::with_capacity(notes.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, parser);
+
+    Some(
+        ammonia::Builder::default()
+            .link_rel(Some("noopener noreferrer nofollow"))
+            .clean(&html)
+            .to_string(),
+    )
+}
+
+/// Optional trailing parts of a link snippet: description and GitHub stars
+fn snippet_suffix(link: &Link) -> Vec<String> {
+    let mut parts = Vec::new();
+    if let Some(description) = link.description.as_deref() {
+        let description = description.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !description.is_empty() {
+            parts.push(description);
+        }
+    }
+    if link.is_github_repo {
+        if let Some(stars) = link.github_stars {
+            parts.push(format!("★ {}", stars));
+        }
+    }
+    parts
+}
+
+/// Render a link as a Markdown list-style line
+fn link_markdown(link: &Link) -> String {
+    let title = link.title.as_deref().unwrap_or(&link.url);
+    let mut escaped_title = String::with_capacity(title.len());
+    for c in title.chars() {
+        if matches!(c, '\\' | '[' | ']') {
+            escaped_title.push('\\');
+        }
+        escaped_title.push(c);
+    }
+    // Parentheses and spaces would end the link destination early
+    let url = link
+        .url
+        .replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29");
+
+    let mut parts = vec![format!("[{}]({})", escaped_title, url)];
+    parts.extend(snippet_suffix(link));
+    parts.join(" — ")
+}
+
+/// Render a link as an HTML `<a>` snippet
+fn link_html(link: &Link) -> String {
+    let title = link.title.as_deref().unwrap_or(&link.url);
+    let mut parts = vec![format!(
+        "<a href=\"{}\">{}</a>",
+        escape_html(&link.url),
+        escape_html(title)
+    )];
+    parts.extend(snippet_suffix(link).iter().map(|part| escape_html(part)));
+    parts.join(" — ")
+}
+
+/// Escape text for use in HTML or SVG content and quoted attributes
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Rendered preview cards, keyed by link id and `updated_at` so an edit
+/// renders a fresh card while stale entries age out
+pub type PreviewCardCache = moka::future::Cache<(Uuid, DateTime<Utc>), std::sync::Arc<str>>;
+
+/// Preview cards kept in memory
+const PREVIEW_CARD_CACHE_CAPACITY: u64 = 1_000;
+
+/// Build the empty preview card cache held in the API state
+pub fn new_preview_card_cache() -> PreviewCardCache {
+    moka::future::Cache::builder()
+        .max_capacity(PREVIEW_CARD_CACHE_CAPACITY)
+        .time_to_idle(std::time::Duration::from_secs(24 * 3600))
+        .build()
+}
+
+/// GET /api/links/:id/preview-image
+///
+/// Returns a 1200x630 SVG card (title, domain and favicon) for sharing the
+/// link. The favicon is downloaded and inlined, so the card loads no external
+/// resources. Cards are cached per `updated_at` and carry a matching `ETag`;
+/// a request with that tag in `If-None-Match` gets a 304.
+///
+/// # Response
+/// - 200 OK: The card as `image/svg+xml`
+/// - 304 Not Modified: The link hasn't changed since the tagged card
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Link not found or doesn't belong to user
+#[utoipa::path(
+    get,
+    path = "/api/links/{id}/preview-image",
+    params(("id" = Uuid, Path, description = "Link id")),
+    responses(
+        (status = 200, description = "Preview card", body = String, content_type = "image/svg+xml"),
+        (status = 304, description = "Card unchanged since the `If-None-Match` tag"),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+        (status = 404, description = "Link not found", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn preview_image_handler(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    State(preview_cards): State<PreviewCardCache>,
+    auth: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<axum::response::Response, AppError> {
+    use axum::http::header;
+
+    let link = Link::get_by_id(&pool, id, auth.user_id).await?;
+    let etag = format!("\"{}-{}\"", link.id, link.updated_at.timestamp_micros());
+
+    let unchanged = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|tag| tag.trim() == etag));
+    if unchanged {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+
+    let svg = preview_cards
+        .get_with((link.id, link.updated_at), async {
+            let favicon = match link.logo.as_deref() {
+                Some(logo) => scraper::fetch_image_data_uri(logo, &config).await,
+                None => None,
+            };
+            preview_card_svg(&link, favicon.as_deref()).into()
+        })
+        .await;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml".to_string()),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+            // Opened directly, the SVG is a document; keep it inert
+            (
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; img-src data:; style-src 'unsafe-inline'".to_string(),
+            ),
+        ],
+        svg.to_string(),
+    )
+        .into_response())
+}
+
+/// Characters per title line on a preview card, at the title font size
+const PREVIEW_TITLE_LINE_CHARS: usize = 32;
+
+/// Title lines shown on a preview card before it is cut with an ellipsis
+const PREVIEW_TITLE_MAX_LINES: usize = 3;
+
+/// Render a link's preview card as SVG
+///
+/// The title falls back to the URL. `favicon` is a `data:` URI drawn next to
+/// the domain; without one the domain starts at the left margin.
+fn preview_card_svg(link: &Link, favicon: Option<&str>) -> String {
+    let title = link
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or(&link.url);
+    let domain = link.domain_display.as_deref().unwrap_or(&link.domain);
+
+    let mut svg = String::from(concat!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="1200" height="630" viewBox="0 0 1200 630">"##,
+        r##"<rect width="1200" height="630" fill="#1c1917"/>"##,
+        r##"<rect x="40" y="40" width="1120" height="550" rx="24" fill="#292524"/>"##,
+        r##"<g font-family="system-ui, -apple-system, 'Segoe UI', sans-serif">"##,
+    ));
+
+    let domain_x = match favicon {
+        Some(favicon) => {
+            svg.push_str(&format!(
+                r#"<image href="{}" x="96" y="96" width="56" height="56" preserveAspectRatio="xMidYMid meet"/>"#,
+                escape_html(favicon)
+            ));
+            176
+        }
+        None => 96,
+    };
+    svg.push_str(&format!(
+        r##"<text x="{}" y="136" font-size="36" fill="#a8a29e">{}</text>"##,
+        domain_x,
+        escape_html(domain)
+    ));
+
+    for (i, line) in wrap_title(title, PREVIEW_TITLE_LINE_CHARS, PREVIEW_TITLE_MAX_LINES)
+        .iter()
+        .enumerate()
+    {
+        svg.push_str(&format!(
+            r##"<text x="96" y="{}" font-size="64" font-weight="700" fill="#fafaf9">{}</text>"##,
+            270 + i * 80,
+            escape_html(line)
+        ));
+    }
+
+    svg.push_str(concat!(
+        r##"<text x="96" y="540" font-size="28" font-weight="600" fill="#ea580c">Rusty Links</text>"##,
+        "</g></svg>",
+    ));
+    svg
+}
+
+/// Break a title into at most `max_lines` lines of about `line_chars`
+/// characters, at word boundaries
+///
+/// Words longer than a line are split. A title that doesn't fit ends in an
+/// ellipsis on the last line.
+fn wrap_title(title: &str, line_chars: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut truncated = false;
+
+    'words: for word in title.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while !word.is_empty() {
+            let used = current.chars().count();
+            let needed = word.len() + usize::from(used > 0);
+            if used + needed <= line_chars {
+                if used > 0 {
+                    current.push(' ');
+                }
+                current.extend(word.drain(..));
+            } else if used == 0 {
+                // A word longer than a whole line
+                current.extend(word.drain(..line_chars));
+            } else {
+                if lines.len() + 1 == max_lines {
+                    truncated = true;
+                    break 'words;
+                }
+                lines.push(std::mem::take(&mut current));
+                continue;
+            }
+            if !word.is_empty() {
+                if lines.len() + 1 == max_lines {
+                    truncated = true;
+                    break 'words;
+                }
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    if truncated {
+        if let Some(last) = lines.last_mut() {
+            let keep = last.chars().count().min(line_chars - 1);
+            *last = last
+                .chars()
+                .take(keep)
+                .collect::<String>()
+                .trim_end()
+                .to_string();
+            last.push('…');
+        }
+    }
+    lines
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct AddLanguageRequest {
+    language_id: uuid::Uuid,
+}
+
+/// POST /api/links/:id/languages
+async fn add_language_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path(id): Path<uuid::Uuid>,
+    Json(request): Json<AddLanguageRequest>,
+) -> Result<Json<Vec<Language>>, AppError> {
+    let user_id = auth.user_id;
+    Link::add_language(&pool, id, request.language_id, user_id).await?;
+    let languages = Link::get_languages(&pool, id, user_id).await?;
+    Ok(Json(languages))
+}
+
+/// DELETE /api/links/:id/languages/:language_id
+async fn remove_language_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path((id, language_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+) -> Result<Json<Vec<Language>>, AppError> {
+    let user_id = auth.user_id;
+    Link::remove_language(&pool, id, language_id, user_id).await?;
+    let languages = Link::get_languages(&pool, id, user_id).await?;
+    Ok(Json(languages))
+}
+
+/// GET /api/links/:id/languages
+async fn get_languages_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Vec<Language>>, AppError> {
+    let user_id = auth.user_id;
+    let languages = Link::get_languages(&pool, id, user_id).await?;
+    Ok(Json(languages))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct AddLicenseRequest {
+    license_id: uuid::Uuid,
+}
+
+/// POST /api/links/:id/licenses
+async fn add_license_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Path(id): Path<uuid::Uuid>,
+    Json(request): Json<AddLicenseRequest>,
+) -> Result<Json<Vec<License>>, AppError> {
+    let user_id = auth.user_id;
+    Link::add_license(&pool, id, request.license_id, user_id).await?;
+    let licenses = Link::get_licenses(&pool, id, user_id).await?;
+    Ok(Json(licenses))
+}
+
+/// DELETE /api/links/:id/licenses/:license_id
+async fn remove_license_handler(
     State(pool): State<PgPool>,
     auth: AuthenticatedUser,
     Path((id, license_id)): Path<(uuid::Uuid, uuid::Uuid)>,
@@ -586,111 +1799,24 @@ async fn get_licenses_handler(
 ///
 /// Refresh all metadata for a link (web scraping + GitHub if applicable)
 ///
+/// An optional `{ "fields": [...] }` body limits which fields are
+/// overwritten (see `services::RefreshOptions`).
+///
 /// # Response
 /// - 200 OK: Returns the updated link with fresh metadata
 /// - 401 Unauthorized: No valid session
 /// - 404 Not Found: Link not found or doesn't belong to user
 async fn refresh_link_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
     Path(id): Path<uuid::Uuid>,
+    options: Option<Json<services::RefreshOptions>>,
 ) -> Result<Json<Link>, AppError> {
     let user_id = auth.user_id;
+    let options = options.map(|Json(options)| options).unwrap_or_default();
 
-    tracing::info!(
-        user_id = %user_id,
-        link_id = %id,
-        "Refreshing metadata for link"
-    );
-
-    // Verify link exists and belongs to user
-    let link = Link::get_by_id(&pool, id, user_id).await?;
-
-    // Determine the GitHub URL to use (main URL or source_code_url)
-    // Only accept URLs that start with https://github.com/
-    let github_url = if link.is_github_repo && link.url.starts_with("https://github.com/") {
-        Some(link.url.clone())
-    } else {
-        link.source_code_url
-            .as_ref()
-            .filter(|url| url.starts_with("https://github.com/"))
-            .cloned()
-    };
-
-    // If we have a GitHub URL, fetch GitHub metadata
-    if let Some(ref gh_url) = github_url {
-        if let Some((owner, repo)) = crate::github::parse_repo_from_url(gh_url) {
-            match crate::github::fetch_repo_metadata(&owner, &repo).await {
-                Ok(metadata) => {
-                    tracing::info!(
-                        link_id = %id,
-                        owner = %owner,
-                        repo = %repo,
-                        stars = metadata.stars,
-                        "Successfully fetched GitHub metadata"
-                    );
-
-                    if let Err(e) = Link::update_github_metadata(&pool, id, user_id, metadata).await
-                    {
-                        tracing::warn!(
-                            link_id = %id,
-                            error = %e,
-                            "Failed to update GitHub metadata during refresh"
-                        );
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        link_id = %id,
-                        owner = %owner,
-                        repo = %repo,
-                        error = %e,
-                        "Failed to fetch GitHub metadata during refresh"
-                    );
-                }
-            }
-        }
-    }
-
-    // Always scrape the main URL for metadata (title, description, logo)
-    if !link.is_github_repo {
-        match scraper::scrape_url(&link.url).await {
-            Ok(metadata) => {
-                tracing::info!(
-                    link_id = %id,
-                    has_title = metadata.title.is_some(),
-                    has_description = metadata.description.is_some(),
-                    "Successfully scraped metadata"
-                );
-
-                if let Err(e) = Link::update_scraped_metadata(&pool, id, user_id, metadata).await {
-                    tracing::warn!(
-                        link_id = %id,
-                        error = %e,
-                        "Failed to update scraped metadata during refresh"
-                    );
-                }
-            }
-            Err(e) => {
-                tracing::warn!(
-                    link_id = %id,
-                    error = %e,
-                    "Failed to scrape URL during refresh"
-                );
-            }
-        }
-    }
-
-    // Mark the link as refreshed
-    Link::mark_refreshed(&pool, id, user_id).await?;
-
-    // Fetch and return updated link
-    let updated_link = Link::get_by_id(&pool, id, user_id).await?;
-
-    tracing::info!(
-        link_id = %id,
-        "Link metadata refreshed successfully"
-    );
+    let updated_link = services::refresh_link(&pool, &config, id, user_id, &options).await?;
 
     Ok(Json(updated_link))
 }
@@ -707,6 +1833,7 @@ async fn refresh_link_handler(
 /// - 502 Bad Gateway: GitHub API request failed
 async fn refresh_github_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
     Path(id): Path<uuid::Uuid>,
 ) -> Result<Json<Link>, AppError> {
@@ -730,12 +1857,15 @@ async fn refresh_github_handler(
     }
 
     // Parse owner and repo from URL
-    let (owner, repo) = crate::github::parse_repo_from_url(&link.url).ok_or_else(|| {
-        AppError::validation(
-            "url",
-            "Could not parse GitHub owner and repository from URL",
-        )
-    })?;
+    let (owner, repo) = config
+        .github
+        .parse_repo_from_url(&link.url)
+        .ok_or_else(|| {
+            AppError::validation(
+                "url",
+                "Could not parse GitHub owner and repository from URL",
+            )
+        })?;
 
     tracing::info!(
         link_id = %id,
@@ -745,10 +1875,13 @@ async fn refresh_github_handler(
     );
 
     // Fetch latest GitHub metadata
-    let metadata = crate::github::fetch_repo_metadata(&owner, &repo).await?;
+    let user_token = UserSettings::github_token(&pool, &config, user_id).await?;
+    let metadata =
+        crate::github::fetch_repo_metadata_as(&config, &owner, &repo, user_token.as_deref())
+            .await?;
 
     // Update the link with fresh metadata
-    Link::update_github_metadata(&pool, id, user_id, metadata).await?;
+    Link::update_github_metadata(&pool, &config, id, user_id, metadata).await?;
 
     // Fetch and return updated link
     let updated_link = Link::get_by_id(&pool, id, user_id).await?;
@@ -768,6 +1901,67 @@ struct BulkDeleteRequest {
     link_ids: Vec<Uuid>,
 }
 
+/// Request body for fetching several links at once
+#[derive(Debug, Deserialize, ToSchema)]
+struct BatchGetRequest {
+    link_ids: Vec<Uuid>,
+}
+
+/// POST /api/links/batch-get
+///
+/// Returns the user's links among `link_ids` with their categories, tags,
+/// languages and licenses, in the requested order. Ids that don't exist or
+/// belong to another user are skipped, so the result may be shorter than the
+/// request.
+///
+/// # Request Body
+/// ```json
+/// { "link_ids": ["uuid1", "uuid2"] }
+/// ```
+///
+/// # Response
+/// - 200 OK: The found links
+/// - 401 Unauthorized: No valid session
+#[utoipa::path(
+    post,
+    path = "/api/links/batch-get",
+    request_body = BatchGetRequest,
+    responses(
+        (status = 200, description = "The user's links among the requested ids, in request order", body = [LinkWithCategories]),
+        (status = 401, description = "No valid session", body = ApiErrorResponse),
+    ),
+    tag = "links"
+)]
+async fn batch_get_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Json(req): Json<BatchGetRequest>,
+) -> Result<Json<Vec<LinkWithCategories>>, AppError> {
+    let links = Link::get_many_with_metadata(&pool, auth.user_id, &req.link_ids).await?;
+    Ok(Json(links))
+}
+
+#[derive(Debug, Deserialize)]
+struct BulkRefreshRequest {
+    link_ids: Vec<Uuid>,
+}
+
+/// Outcome of refreshing one link in a bulk refresh
+#[derive(Debug, Serialize)]
+struct BulkRefreshItem {
+    link_id: Uuid,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BulkRefreshResponse {
+    refreshed: usize,
+    failed: usize,
+    results: Vec<BulkRefreshItem>,
+}
+
 #[derive(Debug, Deserialize)]
 struct BulkCategoryRequest {
     link_ids: Vec<Uuid>,
@@ -782,6 +1976,33 @@ struct BulkTagRequest {
     action: String, // "add" or "remove"
 }
 
+#[derive(Debug, Deserialize)]
+struct BulkLanguageRequest {
+    link_ids: Vec<Uuid>,
+    language_id: Uuid,
+    action: String, // "add" or "remove"
+}
+
+#[derive(Debug, Deserialize)]
+struct BulkLicenseRequest {
+    link_ids: Vec<Uuid>,
+    license_id: Uuid,
+    action: String, // "add" or "remove"
+}
+
+#[derive(Debug, Deserialize)]
+struct BulkUntagRequest {
+    link_ids: Vec<Uuid>,
+    tag_id: Uuid,
+}
+
+/// Result of a bulk category/tag/language/license operation
+#[derive(Debug, Serialize)]
+struct BulkOperationResult {
+    /// Number of links that were actually changed
+    affected: u64,
+}
+
 /// POST /api/links/bulk/delete
 ///
 /// Delete multiple links at once
@@ -799,6 +2020,7 @@ struct BulkTagRequest {
 /// - 404 Not Found: One or more links not found or don't belong to user
 async fn bulk_delete_handler(
     State(pool): State<PgPool>,
+    State(events): State<EventBus>,
     auth: AuthenticatedUser,
     Json(req): Json<BulkDeleteRequest>,
 ) -> Result<StatusCode, AppError> {
@@ -807,122 +2029,339 @@ async fn bulk_delete_handler(
     tracing::info!(
         user_id = %user_id,
         count = req.link_ids.len(),
-        "Bulk deleting links"
+        "Bulk deleting links"
+    );
+
+    // Verify all links belong to user and delete
+    for link_id in req.link_ids {
+        Link::delete(&pool, link_id, user_id).await?;
+        events.publish(user_id, LinkEvent::LinkDeleted { id: link_id });
+    }
+
+    tracing::info!(
+        user_id = %user_id,
+        "Bulk delete completed"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/links/bulk/refresh
+///
+/// Refresh metadata for multiple links. Unlike the other bulk operations,
+/// a failing link doesn't abort the batch; each link's outcome is reported.
+///
+/// # Request Body
+/// ```json
+/// {
+///     "link_ids": ["uuid1", "uuid2"]
+/// }
+/// ```
+///
+/// # Response
+/// - 200 OK: Per-link results in request order
+/// - 401 Unauthorized: No valid session
+async fn bulk_refresh_handler(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    auth: AuthenticatedUser,
+    Json(req): Json<BulkRefreshRequest>,
+) -> Result<Json<BulkRefreshResponse>, AppError> {
+    let user_id = auth.user_id;
+
+    tracing::info!(
+        user_id = %user_id,
+        count = req.link_ids.len(),
+        "Bulk refreshing links"
+    );
+
+    let results: Vec<BulkRefreshItem> =
+        services::refresh_links(&pool, &config, &req.link_ids, user_id)
+            .await?
+            .into_iter()
+            .map(|(link_id, result)| BulkRefreshItem {
+                link_id,
+                success: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            })
+            .collect();
+
+    let refreshed = results.iter().filter(|r| r.success).count();
+    let failed = results.len() - refreshed;
+
+    tracing::info!(
+        user_id = %user_id,
+        refreshed,
+        failed,
+        "Bulk refresh completed"
+    );
+
+    Ok(Json(BulkRefreshResponse {
+        refreshed,
+        failed,
+        results,
+    }))
+}
+
+/// POST /api/links/bulk/categories
+///
+/// Add, remove or set a category on multiple links. `set` replaces each
+/// link's categories with just this one. The batch runs in a single
+/// transaction: if any link is missing or not owned by the user, nothing changes.
+///
+/// # Request Body
+/// ```json
+/// {
+///     "link_ids": ["uuid1", "uuid2"],
+///     "category_id": "category-uuid",
+///     "action": "add"
+/// }
+/// ```
+///
+/// # Response
+/// - 200 OK: Returns `{"affected": n}`, the number of links changed
+/// - 400 Bad Request: Invalid action (must be 'add', 'remove' or 'set')
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Category or one or more links not found
+async fn bulk_category_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Json(req): Json<BulkCategoryRequest>,
+) -> Result<Json<BulkOperationResult>, AppError> {
+    let user_id = auth.user_id;
+
+    tracing::info!(
+        user_id = %user_id,
+        count = req.link_ids.len(),
+        category_id = %req.category_id,
+        action = %req.action,
+        "Bulk category operation"
+    );
+
+    let affected = match req.action.as_str() {
+        "add" => Link::bulk_add_category(&pool, &req.link_ids, req.category_id, user_id).await?,
+        "remove" => {
+            Link::bulk_remove_category(&pool, &req.link_ids, req.category_id, user_id).await?
+        }
+        "set" => Link::bulk_set_category(&pool, &req.link_ids, req.category_id, user_id).await?,
+        _ => {
+            return Err(AppError::validation(
+                "action",
+                "Must be 'add', 'remove' or 'set'",
+            ))
+        }
+    };
+
+    tracing::info!(
+        user_id = %user_id,
+        affected,
+        "Bulk category operation completed"
+    );
+
+    Ok(Json(BulkOperationResult { affected }))
+}
+
+/// POST /api/links/bulk/tags
+///
+/// Add or remove a tag from multiple links. The batch runs in a single
+/// transaction: if any link is missing or not owned by the user, nothing changes.
+///
+/// # Request Body
+/// ```json
+/// {
+///     "link_ids": ["uuid1", "uuid2"],
+///     "tag_id": "tag-uuid",
+///     "action": "add"
+/// }
+/// ```
+///
+/// # Response
+/// - 200 OK: Returns `{"affected": n}`, the number of links changed
+/// - 400 Bad Request: Invalid action (must be 'add' or 'remove')
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Tag or one or more links not found
+async fn bulk_tag_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Json(req): Json<BulkTagRequest>,
+) -> Result<Json<BulkOperationResult>, AppError> {
+    let user_id = auth.user_id;
+
+    tracing::info!(
+        user_id = %user_id,
+        count = req.link_ids.len(),
+        tag_id = %req.tag_id,
+        action = %req.action,
+        "Bulk tag operation"
+    );
+
+    let affected = match req.action.as_str() {
+        "add" => Link::bulk_add_tag(&pool, &req.link_ids, req.tag_id, user_id).await?,
+        "remove" => Link::bulk_remove_tag(&pool, &req.link_ids, req.tag_id, user_id).await?,
+        _ => return Err(AppError::validation("action", "Must be 'add' or 'remove'")),
+    };
+
+    tracing::info!(
+        user_id = %user_id,
+        affected,
+        "Bulk tag operation completed"
+    );
+
+    Ok(Json(BulkOperationResult { affected }))
+}
+
+/// POST /api/links/bulk/languages
+///
+/// Add or remove a language from multiple links. The batch runs in a single
+/// transaction: if any link is missing or not owned by the user, nothing changes.
+///
+/// # Request Body
+/// ```json
+/// {
+///     "link_ids": ["uuid1", "uuid2"],
+///     "language_id": "language-uuid",
+///     "action": "add"
+/// }
+/// ```
+///
+/// # Response
+/// - 200 OK: Returns `{"affected": n}`, the number of links changed
+/// - 400 Bad Request: Invalid action (must be 'add' or 'remove')
+/// - 401 Unauthorized: No valid session
+/// - 404 Not Found: Language or one or more links not found
+async fn bulk_language_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Json(req): Json<BulkLanguageRequest>,
+) -> Result<Json<BulkOperationResult>, AppError> {
+    let user_id = auth.user_id;
+
+    tracing::info!(
+        user_id = %user_id,
+        count = req.link_ids.len(),
+        language_id = %req.language_id,
+        action = %req.action,
+        "Bulk language operation"
     );
 
-    // Verify all links belong to user and delete
-    for link_id in req.link_ids {
-        Link::delete(&pool, link_id, user_id).await?;
-    }
+    let affected = match req.action.as_str() {
+        "add" => Link::bulk_add_language(&pool, &req.link_ids, req.language_id, user_id).await?,
+        "remove" => {
+            Link::bulk_remove_language(&pool, &req.link_ids, req.language_id, user_id).await?
+        }
+        _ => return Err(AppError::validation("action", "Must be 'add' or 'remove'")),
+    };
 
     tracing::info!(
         user_id = %user_id,
-        "Bulk delete completed"
+        affected,
+        "Bulk language operation completed"
     );
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(Json(BulkOperationResult { affected }))
 }
 
-/// POST /api/links/bulk/categories
+/// POST /api/links/bulk/licenses
 ///
-/// Add or remove a category from multiple links
+/// Add or remove a license from multiple links. The batch runs in a single
+/// transaction: if any link is missing or not owned by the user, nothing changes.
 ///
 /// # Request Body
 /// ```json
 /// {
 ///     "link_ids": ["uuid1", "uuid2"],
-///     "category_id": "category-uuid",
+///     "license_id": "license-uuid",
 ///     "action": "add"
 /// }
 /// ```
 ///
 /// # Response
-/// - 200 OK: Category operation completed
+/// - 200 OK: Returns `{"affected": n}`, the number of links changed
 /// - 400 Bad Request: Invalid action (must be 'add' or 'remove')
 /// - 401 Unauthorized: No valid session
-async fn bulk_category_handler(
+/// - 404 Not Found: License or one or more links not found
+async fn bulk_license_handler(
     State(pool): State<PgPool>,
     auth: AuthenticatedUser,
-    Json(req): Json<BulkCategoryRequest>,
-) -> Result<StatusCode, AppError> {
+    Json(req): Json<BulkLicenseRequest>,
+) -> Result<Json<BulkOperationResult>, AppError> {
     let user_id = auth.user_id;
 
     tracing::info!(
         user_id = %user_id,
         count = req.link_ids.len(),
-        category_id = %req.category_id,
+        license_id = %req.license_id,
         action = %req.action,
-        "Bulk category operation"
+        "Bulk license operation"
     );
 
-    for link_id in &req.link_ids {
-        match req.action.as_str() {
-            "add" => Link::add_category(&pool, *link_id, req.category_id, user_id).await?,
-            "remove" => Link::remove_category(&pool, *link_id, req.category_id, user_id).await?,
-            _ => return Err(AppError::validation("action", "Must be 'add' or 'remove'")),
+    let affected = match req.action.as_str() {
+        "add" => Link::bulk_add_license(&pool, &req.link_ids, req.license_id, user_id).await?,
+        "remove" => {
+            Link::bulk_remove_license(&pool, &req.link_ids, req.license_id, user_id).await?
         }
-    }
+        _ => return Err(AppError::validation("action", "Must be 'add' or 'remove'")),
+    };
 
     tracing::info!(
         user_id = %user_id,
-        "Bulk category operation completed"
+        affected,
+        "Bulk license operation completed"
     );
 
-    Ok(StatusCode::OK)
+    Ok(Json(BulkOperationResult { affected }))
 }
 
-/// POST /api/links/bulk/tags
+/// POST /api/links/bulk/untag
 ///
-/// Add or remove a tag from multiple links
+/// Remove a tag from multiple links in a single transaction.
+/// Shorthand for `POST /api/links/bulk/tags` with `"action": "remove"`.
 ///
 /// # Request Body
 /// ```json
 /// {
 ///     "link_ids": ["uuid1", "uuid2"],
-///     "tag_id": "tag-uuid",
-///     "action": "add"
+///     "tag_id": "tag-uuid"
 /// }
 /// ```
 ///
 /// # Response
-/// - 200 OK: Tag operation completed
-/// - 400 Bad Request: Invalid action (must be 'add' or 'remove')
+/// - 200 OK: Returns `{"affected": n}`, the number of links the tag was removed from
 /// - 401 Unauthorized: No valid session
-async fn bulk_tag_handler(
+/// - 404 Not Found: One or more links not found
+async fn bulk_untag_handler(
     State(pool): State<PgPool>,
     auth: AuthenticatedUser,
-    Json(req): Json<BulkTagRequest>,
-) -> Result<StatusCode, AppError> {
+    Json(req): Json<BulkUntagRequest>,
+) -> Result<Json<BulkOperationResult>, AppError> {
     let user_id = auth.user_id;
 
     tracing::info!(
         user_id = %user_id,
         count = req.link_ids.len(),
         tag_id = %req.tag_id,
-        action = %req.action,
-        "Bulk tag operation"
+        "Bulk untag operation"
     );
 
-    for link_id in &req.link_ids {
-        match req.action.as_str() {
-            "add" => Link::add_tag(&pool, *link_id, req.tag_id, user_id).await?,
-            "remove" => Link::remove_tag(&pool, *link_id, req.tag_id, user_id).await?,
-            _ => return Err(AppError::validation("action", "Must be 'add' or 'remove'")),
-        }
-    }
+    let affected = Link::bulk_remove_tag(&pool, &req.link_ids, req.tag_id, user_id).await?;
 
     tracing::info!(
         user_id = %user_id,
-        "Bulk tag operation completed"
+        affected,
+        "Bulk untag operation completed"
     );
 
-    Ok(StatusCode::OK)
+    Ok(Json(BulkOperationResult { affected }))
 }
 
 /// Export data structures
 #[derive(Debug, Serialize)]
 struct ExportData {
+    /// When the export started; pass it as `since` for the next incremental export
     exported_at: DateTime<Utc>,
+    /// The `since` this export was limited to, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<DateTime<Utc>>,
     version: String,
     links: Vec<ExportLink>,
     categories: Vec<Category>,
@@ -940,27 +2379,48 @@ struct ExportLink {
     languages: Vec<String>,
     licenses: Vec<String>,
     created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
     is_github_repo: bool,
     github_stars: Option<i32>,
 }
 
+/// Query parameters for export
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    /// Only export links updated after this time (RFC 3339)
+    since: Option<DateTime<Utc>>,
+}
+
 /// GET /api/export
 ///
 /// Export all user data as JSON
 ///
+/// With `?since=<timestamp>` only links updated after that time are included,
+/// for incremental backups; categories and tags are always exported in full.
+/// Deleted links are not reported, as deletes are permanent.
+///
 /// # Response
 /// - 200 OK: Returns export data with all links, categories, and tags
+/// - 400 Bad Request: `since` is not an RFC 3339 timestamp
 /// - 401 Unauthorized: No valid session
 async fn export_links_handler(
     State(pool): State<PgPool>,
     auth: AuthenticatedUser,
+    Query(query): Query<ExportQuery>,
 ) -> Result<Json<ExportData>, AppError> {
     let user_id = auth.user_id;
 
-    tracing::info!(user_id = %user_id, "Exporting links");
+    // Taken before reading, so changes made during the export are picked up
+    // by the next incremental one
+    let exported_at = Utc::now();
+
+    tracing::info!(user_id = %user_id, since = ?query.since, "Exporting links");
 
     // Fetch all user data
-    let links = Link::get_all_by_user(&pool, user_id).await?;
+    let links = match query.since {
+        Some(since) => Link::get_updated_since(&pool, user_id, since).await?,
+        None => Link::get_all_by_user(&pool, user_id).await?,
+    };
     let categories = Category::get_all_by_user(&pool, user_id).await?;
     let tags = Tag::get_all_by_user(&pool, user_id).await?;
 
@@ -983,6 +2443,7 @@ async fn export_links_handler(
             languages: link_languages.into_iter().map(|l| l.name).collect(),
             licenses: link_licenses.into_iter().map(|l| l.name).collect(),
             created_at: link.created_at,
+            updated_at: link.updated_at,
             is_github_repo: link.is_github_repo,
             github_stars: link.github_stars,
         });
@@ -995,7 +2456,8 @@ async fn export_links_handler(
     );
 
     Ok(Json(ExportData {
-        exported_at: Utc::now(),
+        exported_at,
+        since: query.since,
         version: "1.0".to_string(),
         links: export_links,
         categories,
@@ -1027,6 +2489,221 @@ struct ImportResult {
     imported: u32,
     skipped: u32,
     errors: Vec<String>,
+    /// Imported GitHub repositories whose stars, license and language were
+    /// fetched during the import
+    github_enriched: u32,
+}
+
+/// Outcome of an import without writing anything
+#[derive(Debug, Serialize)]
+struct ImportPreview {
+    /// Links the import would create
+    will_import: u32,
+    /// URLs the import would skip as already saved (or repeated in the file)
+    will_skip: Vec<String>,
+    /// Entries that couldn't be read or have an unusable URL
+    parse_errors: Vec<String>,
+}
+
+/// Reject imports with more links than `MAX_IMPORT_LINKS` before any work
+fn check_import_size(count: usize, max: usize) -> Result<(), AppError> {
+    if count > max {
+        return Err(AppError::validation(
+            "links",
+            &format!(
+                "Too many links in one import ({}, maximum {}). Split the file and import the parts separately.",
+                count, max
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Key of an imported category name in the resolved categories: the
+/// lowercased name, or the lowercased path for `Parent/Child` names
+fn category_key(name: &str) -> String {
+    parse_category_path(name).join("/").to_lowercase()
+}
+
+/// Parse import entries one by one, so a bad entry is reported instead of
+/// rejecting the whole file
+///
+/// GitHub repository URLs are canonicalized as in the real import. Returns the
+/// usable entries and an error per rejected one, labelled by its index.
+fn parse_import_entries(
+    entries: Vec<serde_json::Value>,
+    config: &Config,
+) -> (Vec<ImportLink>, Vec<String>) {
+    let mut links = Vec::with_capacity(entries.len());
+    let mut errors = Vec::new();
+
+    for (i, entry) in entries.into_iter().enumerate() {
+        let mut link = match serde_json::from_value::<ImportLink>(entry) {
+            Ok(link) => link,
+            Err(e) => {
+                errors.push(format!("links[{}]: {}", i, e));
+                continue;
+            }
+        };
+        if let Some(canonical) = config.github.canonical_repo_url(&link.url) {
+            link.url = canonical;
+        }
+        match Link::validate_url(&link.url, config) {
+            Ok(()) => links.push(link),
+            Err(e) => errors.push(format!("links[{}]: {}: {}", i, link.url, e)),
+        }
+    }
+
+    (links, errors)
+}
+
+/// Parse a newline-separated URL list, one URL per line
+///
+/// Blank lines are ignored. GitHub repository URLs are canonicalized as in the
+/// JSON import. Returns the usable URLs and an error per rejected line,
+/// labelled by its 1-based line number.
+fn parse_url_list(text: &str, config: &Config) -> (Vec<String>, Vec<String>) {
+    let mut urls = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let url = config
+            .github
+            .canonical_repo_url(line)
+            .unwrap_or_else(|| line.to_string());
+        match Link::validate_url(&url, config) {
+            Ok(()) => urls.push(url),
+            Err(e) => errors.push(format!("line {}: {}: {}", i + 1, line, e)),
+        }
+    }
+
+    (urls, errors)
+}
+
+/// POST /api/links/import/preview
+///
+/// Dry run of `POST /api/links/import`: takes the same body and reports what
+/// the import would do, without creating links, categories or tags.
+///
+/// # Response
+/// - 200 OK: `{"will_import": n, "will_skip": [urls], "parse_errors": [...]}`
+/// - 400 Bad Request: The body is neither a `links` array nor an object with
+///   one, or it has more than `MAX_IMPORT_LINKS` links
+/// - 401 Unauthorized: No valid session
+async fn import_preview_handler(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    auth: AuthenticatedUser,
+    JsonBody(data): JsonBody<serde_json::Value>,
+) -> Result<Json<ImportPreview>, AppError> {
+    let user_id = auth.user_id;
+
+    let entries = match data {
+        serde_json::Value::Object(mut object) => match object.remove("links") {
+            Some(serde_json::Value::Array(entries)) => entries,
+            _ => return Err(AppError::validation("links", "Expected an array of links")),
+        },
+        serde_json::Value::Array(entries) => entries,
+        _ => return Err(AppError::validation("links", "Expected an array of links")),
+    };
+
+    check_import_size(entries.len(), config.max_import_links)?;
+
+    let (links, parse_errors) = parse_import_entries(entries, &config);
+
+    let mut will_import = 0;
+    let mut will_skip = Vec::new();
+    let mut seen = HashSet::new();
+
+    for link in links {
+        if !seen.insert(link.url.clone())
+            || retry_transient(|| Link::exists_by_url(&pool, &config, user_id, &link.url)).await?
+        {
+            will_skip.push(link.url);
+        } else {
+            will_import += 1;
+        }
+    }
+
+    tracing::info!(
+        user_id = %user_id,
+        will_import,
+        will_skip = will_skip.len(),
+        parse_errors = parse_errors.len(),
+        "Import preview completed"
+    );
+
+    Ok(Json(ImportPreview {
+        will_import,
+        will_skip,
+        parse_errors,
+    }))
+}
+
+/// Attempts made for an import row's database calls before recording an error
+const IMPORT_DB_ATTEMPTS: u32 = 3;
+
+/// Base delay between retries of a transient database error during import
+const IMPORT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Whether a database error may succeed on retry (dropped connection, pool
+/// exhaustion) rather than being a problem with the row itself
+fn is_transient_db_error(error: &AppError) -> bool {
+    matches!(
+        error,
+        AppError::Database(
+            sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed
+        )
+    )
+}
+
+/// Run a database call, retrying transient failures with a linear backoff
+async fn retry_transient<T, F, Fut>(mut op: F) -> Result<T, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, AppError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if attempt < IMPORT_DB_ATTEMPTS && is_transient_db_error(&e) => {
+                tracing::debug!(attempt, error = %e, "Retrying transient database error");
+                tokio::time::sleep(IMPORT_RETRY_DELAY * attempt).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Create an imported link, retrying transient database errors
+///
+/// A dropped connection can hide an insert that committed, so a retry may hit
+/// the row it already saved. A duplicate after a retried error is that row and
+/// is returned as created; a duplicate on the first attempt is a real conflict.
+async fn create_import_link(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    create_link: CreateLink,
+) -> Result<Link, AppError> {
+    let mut attempts = 0;
+    let result = retry_transient(|| {
+        attempts += 1;
+        Link::create(pool, config, user_id, create_link.clone())
+    })
+    .await;
+
+    match result {
+        Err(AppError::Duplicate { .. }) if attempts > 1 => {
+            Link::get_by_normalized_url(pool, config, user_id, &create_link.url).await
+        }
+        result => result,
+    }
 }
 
 /// POST /api/import
@@ -1048,16 +2725,27 @@ struct ImportResult {
 /// }
 /// ```
 ///
+/// GitHub repository URLs are stored in canonical form
+/// (`https://github.com/owner/repo`); other URLs are kept as given.
+///
+/// Transient database errors are retried per row; a row that still fails is
+/// reported in `errors` and the import carries on with the next one. A row
+/// whose insert committed behind a dropped connection is imported once.
+///
 /// # Response
 /// - 200 OK: Returns import results with counts and errors
+/// - 400 Bad Request: More than `MAX_IMPORT_LINKS` links
 /// - 401 Unauthorized: No valid session
 async fn import_links_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
-    Json(data): Json<ImportData>,
+    JsonBody(data): JsonBody<ImportData>,
 ) -> Result<Json<ImportResult>, AppError> {
     let user_id = auth.user_id;
 
+    check_import_size(data.links.len(), config.max_import_links)?;
+
     tracing::info!(
         user_id = %user_id,
         link_count = data.links.len(),
@@ -1067,10 +2755,70 @@ async fn import_links_handler(
     let mut imported = 0;
     let mut skipped = 0;
     let mut errors = Vec::new();
+    let mut github_links = Vec::new();
+
+    // Resolve every category and tag name up front, one batch per kind.
+    // Category names with a slash are paths (`Parent/Child`), resolved one by
+    // one since each level depends on the one above.
+    let mut category_names = Vec::new();
+    let mut category_paths = Vec::new();
+    for name in data
+        .links
+        .iter()
+        .flat_map(|l| l.categories.iter().flatten())
+    {
+        let mut segments = parse_category_path(name);
+        if segments.len() > 1 {
+            category_paths.push(segments);
+        } else if let Some(name) = segments.pop() {
+            category_names.push(name);
+        }
+    }
+    let mut categories = Category::get_or_create_many(&pool, user_id, &category_names)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to resolve categories for import");
+            HashMap::new()
+        });
+    let mut seen_paths = HashSet::new();
+    for segments in category_paths {
+        let key = category_key(&segments.join("/"));
+        if !seen_paths.insert(key.clone()) {
+            continue;
+        }
+        match retry_transient(|| {
+            Category::create_path(&pool, user_id, &segments, config.max_category_depth)
+        })
+        .await
+        {
+            Ok(category) => {
+                categories.insert(key, category);
+            }
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    let tag_names: Vec<String> = data
+        .links
+        .iter()
+        .flat_map(|l| l.tags.iter().flatten().cloned())
+        .collect();
+    let tags = Tag::get_or_create_many(&pool, &config, user_id, &tag_names)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to resolve tags for import");
+            HashMap::new()
+        });
+
+    for mut link_data in data.links {
+        // Store GitHub repository URLs in one form so they dedupe and are
+        // detected as repositories
+        if let Some(canonical) = config.github.canonical_repo_url(&link_data.url) {
+            link_data.url = canonical;
+        }
 
-    for link_data in data.links {
         // Check if URL already exists
-        match Link::exists_by_url(&pool, user_id, &link_data.url).await {
+        match retry_transient(|| Link::exists_by_url(&pool, &config, user_id, &link_data.url)).await
+        {
             Ok(true) => {
                 skipped += 1;
                 continue;
@@ -1091,22 +2839,22 @@ async fn import_links_handler(
             title: link_data.title,
             description: link_data.description,
             logo: None,
+            final_url: None,
         };
 
-        match Link::create(&pool, user_id, create_link).await {
+        match create_import_link(&pool, &config, user_id, create_link).await {
             Ok(link) => {
                 // Add categories by name
                 if let Some(cats) = link_data.categories {
                     for cat_name in cats {
-                        match Category::get_or_create_by_name(&pool, user_id, &cat_name).await {
-                            Ok(cat) => {
+                        match categories.get(&category_key(&cat_name)) {
+                            Some(cat) => {
                                 let _ = Link::add_category(&pool, link.id, cat.id, user_id).await;
                             }
-                            Err(e) => {
+                            None => {
                                 tracing::warn!(
                                     link_id = %link.id,
                                     category = %cat_name,
-                                    error = %e,
                                     "Failed to add category"
                                 );
                             }
@@ -1117,15 +2865,17 @@ async fn import_links_handler(
                 // Add tags by name
                 if let Some(tag_names) = link_data.tags {
                     for tag_name in tag_names {
-                        match Tag::get_or_create_by_name(&pool, user_id, &tag_name).await {
-                            Ok(tag) => {
+                        match tags.get(
+                            &normalize_tag_name(&tag_name, config.normalize_tag_case)
+                                .to_lowercase(),
+                        ) {
+                            Some(tag) => {
                                 let _ = Link::add_tag(&pool, link.id, tag.id, user_id).await;
                             }
-                            Err(e) => {
+                            None => {
                                 tracing::warn!(
                                     link_id = %link.id,
                                     tag = %tag_name,
-                                    error = %e,
                                     "Failed to add tag"
                                 );
                             }
@@ -1136,6 +2886,10 @@ async fn import_links_handler(
                 // Note: languages and licenses would require similar get_or_create functions
                 // For now, we'll skip them in import
 
+                if link.is_github_repo {
+                    github_links.push((link.id, link.url));
+                }
+
                 imported += 1;
             }
             Err(e) => {
@@ -1144,11 +2898,15 @@ async fn import_links_handler(
         }
     }
 
+    let github_enriched =
+        services::enrich_github_links(&pool, &config, user_id, &github_links).await;
+
     tracing::info!(
         user_id = %user_id,
         imported = imported,
         skipped = skipped,
         errors = errors.len(),
+        github_enriched,
         "Import completed"
     );
 
@@ -1156,10 +2914,99 @@ async fn import_links_handler(
         imported,
         skipped,
         errors,
+        github_enriched,
+    }))
+}
+
+/// POST /api/links/import/urls
+///
+/// Import a plain `text/plain` list of URLs, one per line
+///
+/// Each new URL is created like a quick add: the user's default category is
+/// attached and metadata is fetched in the background. URLs already saved, or
+/// repeated earlier in the list, are skipped. Lines that aren't valid URLs are
+/// reported in `errors` by line number; blank lines are ignored.
+///
+/// # Response
+/// - 200 OK: Returns import results with counts and errors
+/// - 400 Bad Request: More than `MAX_IMPORT_LINKS` lines with URLs
+/// - 401 Unauthorized: No valid session
+async fn import_urls_handler(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    State(jobs): State<JobQueue>,
+    State(events): State<EventBus>,
+    auth: AuthenticatedUser,
+    body: String,
+) -> Result<Json<ImportResult>, AppError> {
+    let user_id = auth.user_id;
+
+    let (urls, mut errors) = parse_url_list(&body, &config);
+    check_import_size(urls.len() + errors.len(), config.max_import_links)?;
+
+    tracing::info!(
+        user_id = %user_id,
+        link_count = urls.len(),
+        invalid_lines = errors.len(),
+        "Starting URL list import"
+    );
+
+    let mut imported = 0;
+    let mut skipped = 0;
+    let mut seen = HashSet::new();
+
+    for url in urls {
+        if !seen.insert(url.clone()) {
+            skipped += 1;
+            continue;
+        }
+
+        match retry_transient(|| Link::exists_by_url(&pool, &config, user_id, &url)).await {
+            Ok(true) => {
+                skipped += 1;
+                continue;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                errors.push(format!("{}: failed to check existence: {}", url, e));
+                continue;
+            }
+        }
+
+        let request = CreateLinkWithCategories {
+            url: url.clone(),
+            title: None,
+            description: None,
+            logo: None,
+            category_ids: Vec::new(),
+            tag_ids: Vec::new(),
+            language_ids: Vec::new(),
+            license_ids: Vec::new(),
+        };
+        match create_link_for_user(&pool, &config, &jobs, &events, user_id, request, true).await {
+            Ok(_) => imported += 1,
+            Err(e) => errors.push(format!("{}: {}", url, e)),
+        }
+    }
+
+    tracing::info!(
+        user_id = %user_id,
+        imported = imported,
+        skipped = skipped,
+        errors = errors.len(),
+        "URL list import completed"
+    );
+
+    // Metadata, GitHub's included, is fetched by the background jobs
+    Ok(Json(ImportResult {
+        imported,
+        skipped,
+        errors,
+        github_enriched: 0,
     }))
 }
 
-/// Query parameters for check-duplicate endpoint
+/// Query parameters for the check-duplicate and by-url endpoints
 #[derive(Debug, Deserialize)]
 struct CheckDuplicateQuery {
     url: String,
@@ -1184,6 +3031,13 @@ struct PreviewResponse {
     github_description: Option<String>,
     github_languages: Vec<String>,
     github_license: Option<String>,
+    final_url: Option<String>,
+    /// Whether the server could reach the URL
+    reachable: bool,
+    /// Why the URL is unreachable, if it isn't
+    reachable_detail: Option<String>,
+    /// Tag names suggested by the page's meta keywords; not applied to the link
+    keyword_suggestions: Vec<String>,
 }
 
 /// GET /api/links/check-duplicate?url=...
@@ -1198,6 +3052,7 @@ struct PreviewResponse {
 /// - 401 Unauthorized: No valid session
 async fn check_duplicate_handler(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
     Query(query): Query<CheckDuplicateQuery>,
 ) -> Result<Json<Option<Link>>, AppError> {
@@ -1209,7 +3064,7 @@ async fn check_duplicate_handler(
         "Checking for duplicate URL"
     );
 
-    let existing = Link::find_by_url(&pool, user_id, &query.url).await?;
+    let existing = Link::find_by_url(&pool, &config, user_id, &query.url).await?;
 
     Ok(Json(existing))
 }
@@ -1226,16 +3081,20 @@ async fn check_duplicate_handler(
 /// }
 /// ```
 ///
+/// The URL's reachability is checked from the server (`reachable`,
+/// `reachable_detail`); an unreachable URL still yields a preview.
+///
 /// # Response
 /// - 200 OK: Returns preview data with extracted metadata
 /// - 401 Unauthorized: No valid session
 /// - 400 Bad Request: Invalid URL format
 async fn preview_link_handler(
-    State(_pool): State<PgPool>,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
     Json(request): Json<PreviewRequest>,
 ) -> Result<Json<PreviewResponse>, AppError> {
-    let _user_id = auth.user_id;
+    let user_id = auth.user_id;
 
     tracing::info!(url = %request.url, "Previewing link metadata");
 
@@ -1252,7 +3111,7 @@ async fn preview_link_handler(
         .to_string();
 
     // Check if GitHub repo
-    let is_github = crate::github::is_github_repo(&request.url);
+    let is_github = config.github.is_github_repo(&request.url);
 
     let mut response = PreviewResponse {
         url: request.url.clone(),
@@ -1265,12 +3124,32 @@ async fn preview_link_handler(
         github_description: None,
         github_languages: vec![],
         github_license: None,
+        final_url: None,
+        reachable: true,
+        reachable_detail: None,
+        keyword_suggestions: vec![],
     };
 
+    match scraper::check_url_health_detailed(&request.url, &config).await {
+        Ok(health) => {
+            response.reachable = health.reachable;
+            response.reachable_detail = health.detail;
+        }
+        Err(e) => {
+            tracing::warn!(url = %request.url, error = %e, "Failed to check URL reachability");
+            response.reachable = false;
+            response.reachable_detail = Some("Reachability could not be checked".to_string());
+        }
+    }
+
     if is_github {
         // Fetch GitHub metadata
-        if let Some((owner, repo)) = crate::github::parse_repo_from_url(&request.url) {
-            if let Ok(metadata) = crate::github::fetch_repo_metadata(&owner, &repo).await {
+        if let Some((owner, repo)) = config.github.parse_repo_from_url(&request.url) {
+            let user_token = UserSettings::github_token(&pool, &config, user_id).await?;
+            if let Ok(metadata) =
+                crate::github::fetch_repo_metadata_as(&config, &owner, &repo, user_token.as_deref())
+                    .await
+            {
                 response.title = Some(format!("{}/{}", owner, repo));
                 response.description = metadata.description.clone();
                 response.github_stars = Some(metadata.stars);
@@ -1284,10 +3163,12 @@ async fn preview_link_handler(
         }
     } else {
         // Regular web scraping
-        if let Ok(metadata) = scraper::scrape_url(&request.url).await {
+        if let Ok(metadata) = scraper::scrape_url(&request.url, &config).await {
             response.title = metadata.title;
             response.description = metadata.description;
             response.favicon = metadata.favicon;
+            response.final_url = metadata.final_url;
+            response.keyword_suggestions = metadata.keyword_suggestions;
         }
     }
 
@@ -1295,26 +3176,74 @@ async fn preview_link_handler(
         url = %request.url,
         title = ?response.title,
         is_github = is_github,
+        reachable = response.reachable,
         "Link preview generated"
     );
 
     Ok(Json(response))
 }
 
+/// OpenAPI description of the links endpoints
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        list_links_handler,
+        create_link_handler,
+        quick_add_handler,
+        status_counts_handler,
+        timeline_handler,
+        json_feed_handler,
+        failed_since_handler,
+        batch_get_handler,
+        get_link_handler,
+        get_link_by_url_handler,
+        update_link_handler,
+        delete_link_handler,
+        duplicate_link_handler,
+        pin_link_handler,
+        unpin_link_handler,
+        link_markdown_handler,
+        link_notes_html_handler,
+        preview_image_handler,
+    ),
+    components(schemas(
+        CreateLinkWithCategories,
+        DuplicateLinkRequest,
+        BatchGetRequest,
+        PaginatedResponse,
+        PaginationLinks,
+        ApiErrorResponse,
+    ))
+)]
+pub struct LinksApi;
+
 /// Create the links router
 pub fn create_router() -> Router<super::AppState> {
     Router::new()
         .route("/", post(create_link_handler).get(list_links_handler))
+        .route("/quick", post(quick_add_handler))
+        .route("/by-url", axum::routing::get(get_link_by_url_handler))
         .route(
             "/check-duplicate",
             axum::routing::get(check_duplicate_handler),
         )
         .route("/preview", post(preview_link_handler))
+        .route("/status-counts", axum::routing::get(status_counts_handler))
+        .route("/timeline", axum::routing::get(timeline_handler))
+        .route("/feed.json", axum::routing::get(json_feed_handler))
+        .route("/failed-since", axum::routing::get(failed_since_handler))
+        .route("/batch-get", post(batch_get_handler))
         .route("/export", axum::routing::get(export_links_handler))
         .route("/import", post(import_links_handler))
+        .route("/import/preview", post(import_preview_handler))
+        .route("/import/urls", post(import_urls_handler))
         .route("/bulk/delete", post(bulk_delete_handler))
+        .route("/bulk/refresh", post(bulk_refresh_handler))
         .route("/bulk/categories", post(bulk_category_handler))
         .route("/bulk/tags", post(bulk_tag_handler))
+        .route("/bulk/languages", post(bulk_language_handler))
+        .route("/bulk/licenses", post(bulk_license_handler))
+        .route("/bulk/untag", post(bulk_untag_handler))
         .route(
             "/{id}",
             axum::routing::get(get_link_handler)
@@ -1323,6 +3252,20 @@ pub fn create_router() -> Router<super::AppState> {
         )
         .route("/{id}/refresh", post(refresh_link_handler))
         .route("/{id}/refresh-github", post(refresh_github_handler))
+        .route("/{id}/similar", axum::routing::get(similar_links_handler))
+        .route("/{id}/markdown", axum::routing::get(link_markdown_handler))
+        .route(
+            "/{id}/preview-image",
+            axum::routing::get(preview_image_handler),
+        )
+        .route(
+            "/{id}/notes.html",
+            axum::routing::get(link_notes_html_handler),
+        )
+        .route("/{id}/duplicate", post(duplicate_link_handler))
+        .route("/{id}/pin", post(pin_link_handler))
+        .route("/{id}/unpin", post(unpin_link_handler))
+        .route("/{id}/visit", post(record_visit_handler))
         .route(
             "/{id}/categories",
             post(add_category_handler).get(get_categories_handler),
@@ -1353,3 +3296,407 @@ pub fn create_router() -> Router<super::AppState> {
             axum::routing::delete(remove_license_handler),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagination_links_keep_filters_and_sort() {
+        let params = LinkSearchParams {
+            query: Some("rust lang".to_string()),
+            status: Some("active".to_string()),
+            sort_by: Some("title".to_string()),
+            sort_order: Some("asc".to_string()),
+            page: Some(2),
+            ..Default::default()
+        };
+
+        let links = PaginationLinks::new(&params, 2, 20, 3);
+
+        assert_eq!(
+            links.first,
+            "/api/links?query=rust+lang&status=active&sort_by=title&sort_order=asc&page=1&per_page=20"
+        );
+        assert_eq!(
+            links.prev.as_deref(),
+            Some("/api/links?query=rust+lang&status=active&sort_by=title&sort_order=asc&page=1&per_page=20")
+        );
+        assert_eq!(
+            links.next.as_deref(),
+            Some("/api/links?query=rust+lang&status=active&sort_by=title&sort_order=asc&page=3&per_page=20")
+        );
+        assert_eq!(
+            links.last,
+            "/api/links?query=rust+lang&status=active&sort_by=title&sort_order=asc&page=3&per_page=20"
+        );
+    }
+
+    #[test]
+    fn test_pagination_links_omit_prev_and_next_at_edges() {
+        let params = LinkSearchParams::default();
+
+        let only_page = PaginationLinks::new(&params, 1, 20, 1);
+        assert_eq!(only_page.prev, None);
+        assert_eq!(only_page.next, None);
+        assert_eq!(only_page.first, only_page.last);
+
+        let last_page = PaginationLinks::new(&params, 5, 10, 5);
+        assert_eq!(
+            last_page.prev.as_deref(),
+            Some("/api/links?page=4&per_page=10")
+        );
+        assert_eq!(last_page.next, None);
+    }
+
+    #[test]
+    fn test_pagination_links_keep_highlight() {
+        let params = LinkSearchParams {
+            query: Some("rust".to_string()),
+            highlight: Some(true),
+            ..Default::default()
+        };
+
+        let links = PaginationLinks::new(&params, 1, 20, 2);
+
+        assert_eq!(
+            links.next.as_deref(),
+            Some("/api/links?query=rust&highlight=true&page=2&per_page=20")
+        );
+    }
+
+    #[test]
+    fn test_pagination_links_keep_pinned_filter() {
+        let params = LinkSearchParams {
+            is_pinned: Some(true),
+            ..Default::default()
+        };
+
+        let links = PaginationLinks::new(&params, 1, 20, 2);
+
+        assert_eq!(
+            links.next.as_deref(),
+            Some("/api/links?is_pinned=true&page=2&per_page=20")
+        );
+    }
+
+    #[test]
+    fn test_pagination_links_keep_created_range() {
+        let params = LinkSearchParams {
+            created_after: Some("2026-03-01T00:00:00Z".parse().unwrap()),
+            created_before: Some("2026-04-01T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let links = PaginationLinks::new(&params, 1, 20, 2);
+
+        assert_eq!(
+            links.next.as_deref(),
+            Some(
+                "/api/links?created_after=2026-03-01T00%3A00%3A00Z\
+                 &created_before=2026-04-01T00%3A00%3A00Z&page=2&per_page=20"
+            )
+        );
+    }
+
+    #[test]
+    fn test_quick_add_url_prefers_query() {
+        assert_eq!(
+            quick_add_url(Some(" https://a.example "), "https://b.example"),
+            Some("https://a.example".to_string())
+        );
+        assert_eq!(
+            quick_add_url(Some(""), "https://b.example\n"),
+            Some("https://b.example".to_string())
+        );
+        assert_eq!(
+            quick_add_url(None, "https://b.example"),
+            Some("https://b.example".to_string())
+        );
+        assert_eq!(quick_add_url(None, "  \n"), None);
+    }
+
+    #[test]
+    fn test_parse_idempotency_key() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_idempotency_key(&headers).unwrap(), None);
+
+        headers.insert("idempotency-key", "  retry-123 ".parse().unwrap());
+        assert_eq!(
+            parse_idempotency_key(&headers).unwrap().as_deref(),
+            Some("retry-123")
+        );
+
+        headers.insert("idempotency-key", "   ".parse().unwrap());
+        assert_eq!(parse_idempotency_key(&headers).unwrap(), None);
+
+        let too_long = "k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1);
+        headers.insert("idempotency-key", too_long.parse().unwrap());
+        assert!(parse_idempotency_key(&headers).is_err());
+    }
+
+    fn sample_link() -> Link {
+        let now = Utc::now();
+        Link {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            url: "https://github.com/rust-lang/rust".to_string(),
+            domain: "github.com".to_string(),
+            domain_display: None,
+            path: Some("/rust-lang/rust".to_string()),
+            title: Some("The [Rust] Language".to_string()),
+            description: Some("Empowering everyone\nto build <reliable> software".to_string()),
+            logo: None,
+            source_code_url: None,
+            documentation_url: None,
+            notes: None,
+            is_github_repo: true,
+            github_stars: Some(98000),
+            github_archived: None,
+            github_last_commit: None,
+            github_owner_type: None,
+            status: "active".to_string(),
+            consecutive_failures: 0,
+            refreshed_at: None,
+            last_checked: None,
+            created_at: now,
+            updated_at: now,
+            final_url: None,
+            renamed_from: None,
+            github_refreshed_at: None,
+            is_pinned: false,
+            last_visited_at: None,
+            title_manually_edited: false,
+            http_etag: None,
+            http_last_modified: None,
+            status_changed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_json_feed() {
+        let link = sample_link();
+        let created_at = link.created_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let untitled = Link {
+            title: None,
+            description: None,
+            url: "https://example.com/".to_string(),
+            ..sample_link()
+        };
+
+        let feed = serde_json::to_value(json_feed(vec![link, untitled], "https://links.example/"))
+            .unwrap();
+
+        assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(feed["home_page_url"], "https://links.example/");
+        assert_eq!(
+            feed["feed_url"],
+            "https://links.example/api/links/feed.json"
+        );
+        assert_eq!(
+            feed["items"][0],
+            serde_json::json!({
+                "id": "https://github.com/rust-lang/rust",
+                "url": "https://github.com/rust-lang/rust",
+                "title": "The [Rust] Language",
+                "content_text": "Empowering everyone\nto build <reliable> software",
+                "date_published": created_at,
+            })
+        );
+        assert!(feed["items"][1].get("title").is_none());
+        assert_eq!(feed["items"][1]["content_text"], "");
+    }
+
+    #[test]
+    fn test_link_markdown_escapes_title() {
+        let link = sample_link();
+        assert_eq!(
+            link_markdown(&link),
+            "[The \\[Rust\\] Language](https://github.com/rust-lang/rust) — Empowering everyone to build <reliable> software — ★ 98000"
+        );
+
+        let link = Link {
+            title: None,
+            description: None,
+            is_github_repo: false,
+            url: "https://en.wikipedia.org/wiki/Rust_(programming_language)".to_string(),
+            ..sample_link()
+        };
+        assert_eq!(
+            link_markdown(&link),
+            "[https://en.wikipedia.org/wiki/Rust_(programming_language)](https://en.wikipedia.org/wiki/Rust_%28programming_language%29)"
+        );
+    }
+
+    #[test]
+    fn test_link_html_escapes_text() {
+        let link = sample_link();
+        assert_eq!(
+            link_html(&link),
+            "<a href=\"https://github.com/rust-lang/rust\">The [Rust] Language</a> — Empowering everyone to build &lt;reliable&gt; software — ★ 98000"
+        );
+    }
+
+    #[test]
+    fn test_render_notes_html() {
+        assert_eq!(render_notes_html(""), None);
+        assert_eq!(render_notes_html("  \n\t"), None);
+
+        let html =
+            render_notes_html("# Setup\n\nUse **cargo** and `rustup`.\n\n- one\n- two").unwrap();
+        assert!(html.contains("<h1>Setup</h1>"));
+        assert!(html.contains("<strong>cargo</strong>"));
+        assert!(html.contains("<code>rustup</code>"));
+        assert!(html.contains("<li>one</li>"));
+    }
+
+    #[test]
+    fn test_render_notes_html_sanitizes() {
+        let html = render_notes_html(
+            "<script>alert(1)</script>\n\n[click](javascript:alert(1)) <img src=x onerror=alert(1)>\n\n[docs](https://docs.rs)",
+        )
+        .unwrap();
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("javascript:"));
+        assert!(!html.contains("onerror"));
+        assert!(html.contains("href=\"https://docs.rs\""));
+        assert!(html.contains("rel=\"noopener noreferrer nofollow\""));
+    }
+
+    #[test]
+    fn test_wrap_title() {
+        assert_eq!(wrap_title("Hello World", 32, 3), vec!["Hello World"]);
+        assert_eq!(
+            wrap_title("one two three four", 9, 2),
+            vec!["one two", "three…"]
+        );
+        assert_eq!(
+            wrap_title("abcdefghijkl mn", 5, 3),
+            vec!["abcde", "fghij", "kl mn"]
+        );
+        assert!(wrap_title("   ", 32, 3).is_empty());
+    }
+
+    #[test]
+    fn test_preview_card_svg() {
+        let link = sample_link();
+        let svg = preview_card_svg(&link, Some("data:image/png;base64,iVBORw0KGgo="));
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains(">The [Rust] Language</text>"));
+        assert!(svg.contains(">github.com</text>"));
+        assert!(svg.contains(r#"<image href="data:image/png;base64,iVBORw0KGgo=""#));
+
+        // Untitled links show the URL; markup in titles is escaped
+        let untitled = Link {
+            title: None,
+            ..sample_link()
+        };
+        let svg = preview_card_svg(&untitled, None);
+        assert!(svg.contains(">https://github.com/rust-lang/rust</text>"));
+        assert!(!svg.contains("<image"));
+
+        let hostile = Link {
+            title: Some("<script>alert('x')</script> & co".to_string()),
+            ..sample_link()
+        };
+        let svg = preview_card_svg(&hostile, None);
+        assert!(!svg.contains("<script>"));
+        assert!(svg.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; co"));
+    }
+
+    #[test]
+    fn test_check_import_size() {
+        assert!(check_import_size(0, 5000).is_ok());
+        assert!(check_import_size(5000, 5000).is_ok());
+
+        let err = check_import_size(5001, 5000).unwrap_err();
+        assert_eq!(err.status_code(), 400);
+        assert!(err.to_string().contains("maximum 5000"));
+    }
+
+    #[test]
+    fn test_parse_import_entries_reports_bad_entries() {
+        let entries = vec![
+            serde_json::json!({"url": "https://example.com/a", "title": "A"}),
+            serde_json::json!({"title": "No URL"}),
+            serde_json::json!({"url": "not a url"}),
+            serde_json::json!({"url": "javascript:alert(1)"}),
+            serde_json::json!({"url": "https://github.com/rust-lang/rust.git"}),
+        ];
+
+        let (links, errors) = parse_import_entries(entries, &crate::config::test_config());
+
+        let urls: Vec<_> = links.iter().map(|l| l.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://example.com/a", "https://github.com/rust-lang/rust"]
+        );
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("links[1]: missing field `url`"));
+        assert!(errors[1].starts_with("links[2]: not a url:"));
+        assert!(errors[2].starts_with("links[3]: javascript:alert(1):"));
+    }
+
+    #[test]
+    fn test_parse_url_list() {
+        let text = "https://example.com/a\n\n  https://example.com/b  \r\nnot a url\nhttps://github.com/rust-lang/rust.git\n";
+
+        let (urls, errors) = parse_url_list(text, &crate::config::test_config());
+
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/a",
+                "https://example.com/b",
+                "https://github.com/rust-lang/rust"
+            ]
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("line 4: not a url:"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_recovers_from_dropped_connection() {
+        let calls = std::sync::atomic::AtomicU32::new(0);
+
+        let result = retry_transient(|| {
+            let call = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move {
+                if call == 0 {
+                    Err(AppError::Database(sqlx::Error::PoolTimedOut))
+                } else {
+                    Ok(call)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_gives_up_and_skips_permanent_errors() {
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result: Result<(), AppError> = retry_transient(|| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async { Err(AppError::Database(sqlx::Error::PoolTimedOut)) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(
+            calls.load(std::sync::atomic::Ordering::SeqCst),
+            IMPORT_DB_ATTEMPTS
+        );
+
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result: Result<(), AppError> = retry_transient(|| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async { Err(AppError::Database(sqlx::Error::RowNotFound)) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
@@ -3,23 +3,33 @@
 pub mod admin;
 pub mod auth;
 pub mod categories;
+pub mod extract;
 pub mod health;
 pub mod languages;
 pub mod licenses;
 pub mod links;
+pub mod openapi;
 pub mod scrape;
+pub mod settings;
+pub mod stats;
 pub mod tags;
 pub mod webhook;
+pub mod ws;
 
 use axum::{
+    extract::DefaultBodyLimit,
+    http::StatusCode,
+    response::{IntoResponse, Response},
     routing::{delete, get, post},
     Router,
 };
 use sqlx::PgPool;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::Arc;
+use tower_http::limit::RequestBodyLimitLayer;
 
 use crate::config::Config;
+use crate::error::AppError;
 
 /// Shared application state
 #[derive(Clone)]
@@ -27,11 +37,18 @@ pub struct AppState {
     pub pool: PgPool,
     pub config: Config,
     pub scheduler_shutdown: Arc<AtomicBool>,
+    /// Batch size used by the scheduler's most recent cycle
+    pub scheduler_batch_size: Arc<AtomicUsize>,
     /// Hosted-mode maintenance flag. Always present; only flipped by the
     /// maintenance webhook (hosted mode), stays `false` in standalone mode.
     pub maintenance_mode: Arc<AtomicBool>,
     pub maintenance_message: Arc<std::sync::RwLock<Option<String>>>,
     pub oidc_verifier: Arc<crate::auth::oidc_rs::OidcVerifier>,
+    pub jobs: crate::jobs::JobQueue,
+    /// Link events streamed to WebSocket clients
+    pub events: crate::events::EventBus,
+    /// Rendered link preview cards
+    pub preview_cards: links::PreviewCardCache,
 }
 
 impl axum::extract::FromRef<AppState> for PgPool {
@@ -46,22 +63,48 @@ impl axum::extract::FromRef<AppState> for Config {
     }
 }
 
+impl axum::extract::FromRef<AppState> for crate::jobs::JobQueue {
+    fn from_ref(state: &AppState) -> crate::jobs::JobQueue {
+        state.jobs.clone()
+    }
+}
+
+impl axum::extract::FromRef<AppState> for crate::events::EventBus {
+    fn from_ref(state: &AppState) -> crate::events::EventBus {
+        state.events.clone()
+    }
+}
+
+impl axum::extract::FromRef<AppState> for links::PreviewCardCache {
+    fn from_ref(state: &AppState) -> links::PreviewCardCache {
+        state.preview_cards.clone()
+    }
+}
+
 /// Create the main API router with all endpoints.
+#[allow(clippy::too_many_arguments)]
 pub fn create_router(
     pool: PgPool,
     config: Config,
     scheduler_shutdown: Arc<AtomicBool>,
+    scheduler_batch_size: Arc<AtomicUsize>,
     maintenance_mode: Arc<AtomicBool>,
     maintenance_message: Arc<std::sync::RwLock<Option<String>>>,
     oidc_verifier: Arc<crate::auth::oidc_rs::OidcVerifier>,
+    jobs: crate::jobs::JobQueue,
+    events: crate::events::EventBus,
 ) -> Router {
     let state = AppState {
         pool: pool.clone(),
         config: config.clone(),
         scheduler_shutdown,
+        scheduler_batch_size,
         maintenance_mode,
         maintenance_message,
         oidc_verifier: oidc_verifier.clone(),
+        jobs,
+        events,
+        preview_cards: links::new_preview_card_cache(),
     };
 
     let health_router = Router::new()
@@ -95,7 +138,10 @@ pub fn create_router(
         .nest("/tags", tags::create_router())
         .nest("/languages", languages::create_router())
         .nest("/licenses", licenses::create_router())
-        .nest("/scrape", scrape::create_router());
+        .nest("/scrape", scrape::create_router())
+        .nest("/settings", settings::create_router())
+        .nest("/stats", stats::create_router())
+        .route("/ws", get(ws::ws_handler));
 
     if config.hosted() {
         // Hosted-only: maintenance webhook + bearer at+jwt verification.
@@ -110,9 +156,33 @@ pub fn create_router(
         let admin_router = Router::new()
             .route("/users", get(admin::list_users))
             .route("/users/{user_id}", delete(admin::delete_user))
-            .route("/users/{user_id}/promote", post(admin::promote_user));
+            .route("/users/{user_id}/promote", post(admin::promote_user))
+            .route(
+                "/maintenance/reclassify-github",
+                post(admin::reclassify_github),
+            )
+            .route(
+                "/maintenance/backfill-url-parts",
+                post(admin::backfill_url_parts),
+            );
         router = router.nest("/admin", admin_router);
     }
 
-    router.with_state(state)
+    // Cap request bodies. Axum's extractors enforce their own 2 MB default, so
+    // raise that to the configured limit too, and answer oversized requests
+    // with the usual JSON error instead of a plain-text 413.
+    let body_limit = config.max_request_body_bytes;
+    router
+        .layer(DefaultBodyLimit::max(body_limit))
+        .layer(RequestBodyLimitLayer::new(body_limit))
+        .layer(axum::middleware::map_response(
+            move |response: Response| async move {
+                if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                    AppError::PayloadTooLarge { limit: body_limit }.into_response()
+                } else {
+                    response
+                }
+            },
+        ))
+        .with_state(state)
 }
@@ -0,0 +1,84 @@
+//! OpenAPI description of the REST API
+//!
+//! The spec is assembled from the `#[utoipa::path]` annotations on the
+//! handlers and served at `GET /api/openapi.json`, with Swagger UI at
+//! `/api/docs`. Only the links endpoints are described so far.
+
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+/// API-wide metadata; the endpoint groups are merged in by [`spec`]
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Rusty Links API",
+        description = "REST API for managing bookmarked links"
+    ),
+    modifiers(&BearerAuth),
+    security(("bearer_auth" = [])),
+    tags((name = "links", description = "Bookmarked links"))
+)]
+struct ApiDoc;
+
+/// Registers the bearer token scheme accepted by every authenticated endpoint
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Build the full OpenAPI document
+pub fn spec() -> utoipa::openapi::OpenApi {
+    let mut spec = ApiDoc::openapi();
+    spec.merge(super::links::LinksApi::openapi());
+    spec
+}
+
+/// Swagger UI at `/api/docs`, serving the spec at `/api/openapi.json`
+///
+/// Merged into the root router rather than the `/api` one because Swagger UI
+/// redirects and links its assets by absolute path.
+pub fn swagger_ui() -> SwaggerUi {
+    SwaggerUi::new("/api/docs").url("/api/openapi.json", spec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_describes_link_endpoints() {
+        let spec = spec();
+
+        for path in [
+            "/api/links",
+            "/api/links/status-counts",
+            "/api/links/timeline",
+            "/api/links/feed.json",
+            "/api/links/failed-since",
+            "/api/links/batch-get",
+            "/api/links/{id}",
+            "/api/links/{id}/duplicate",
+            "/api/links/{id}/markdown",
+            "/api/links/{id}/preview-image",
+        ] {
+            assert!(spec.paths.paths.contains_key(path), "missing {}", path);
+        }
+
+        let schemas = &spec.components.as_ref().unwrap().schemas;
+        assert!(schemas.contains_key("Link"));
+        assert!(schemas.contains_key("ApiErrorResponse"));
+    }
+}
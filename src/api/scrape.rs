@@ -1,9 +1,16 @@
 //! Web scraping API endpoint
 
 use crate::auth::middleware::AuthenticatedUser;
+use crate::config::Config;
 use crate::error::AppError;
 use crate::scraper;
-use axum::{http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::post,
+    Json, Router,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
@@ -11,23 +18,37 @@ struct ScrapeRequest {
     url: String,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct ScrapeQuery {
+    #[serde(default)]
+    debug: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct ScrapeResponse {
     title: Option<String>,
     description: Option<String>,
     favicon: Option<String>,
+    final_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    debug: Option<scraper::ScrapeDebugInfo>,
 }
 
 /// POST /api/scrape
+///
+/// With `?debug=true`, the response also includes the fetched page's
+/// content type and body length, to diagnose pages that yield no metadata.
 async fn scrape_handler(
+    State(config): State<Config>,
     _auth: AuthenticatedUser,
+    Query(query): Query<ScrapeQuery>,
     Json(request): Json<ScrapeRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     tracing::info!(url = %request.url, "Scraping URL for metadata");
 
     crate::security::validate_url_for_ssrf(&request.url)?;
 
-    let metadata = scraper::scrape_url(&request.url).await?;
+    let metadata = scraper::scrape_url(&request.url, &config).await?;
 
     tracing::info!(
         url = %request.url,
@@ -41,6 +62,8 @@ async fn scrape_handler(
         title: metadata.title,
         description: metadata.description,
         favicon: metadata.favicon,
+        final_url: metadata.final_url,
+        debug: query.debug.then_some(metadata.debug),
     };
 
     Ok((StatusCode::OK, Json(response)))
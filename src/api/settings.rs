@@ -0,0 +1,72 @@
+//! User settings API endpoints
+
+use crate::auth::middleware::AuthenticatedUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::models::{UpdateUserSettings, UserSettings};
+use axum::{
+    extract::State,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+/// GET /api/settings
+async fn get_settings(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<UserSettings>, AppError> {
+    let settings = UserSettings::get(&pool, auth.user_id).await?;
+    Ok(Json(settings))
+}
+
+/// PUT /api/settings
+async fn update_settings(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+    Json(request): Json<UpdateUserSettings>,
+) -> Result<Json<UserSettings>, AppError> {
+    let settings = UserSettings::update(&pool, auth.user_id, request).await?;
+    Ok(Json(settings))
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubTokenRequest {
+    token: String,
+}
+
+/// PUT /api/settings/github-token
+///
+/// Store a personal GitHub token, used instead of the server-wide token for
+/// GitHub requests this user triggers. Returns the settings, which only
+/// report whether a token is set.
+async fn set_github_token(
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    auth: AuthenticatedUser,
+    Json(request): Json<GitHubTokenRequest>,
+) -> Result<Json<UserSettings>, AppError> {
+    let settings =
+        UserSettings::set_github_token(&pool, &config, auth.user_id, &request.token).await?;
+    Ok(Json(settings))
+}
+
+/// DELETE /api/settings/github-token
+async fn clear_github_token(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<UserSettings>, AppError> {
+    let settings = UserSettings::clear_github_token(&pool, auth.user_id).await?;
+    Ok(Json(settings))
+}
+
+/// Create the settings router
+pub fn create_router() -> Router<super::AppState> {
+    Router::new()
+        .route("/", get(get_settings).put(update_settings))
+        .route(
+            "/github-token",
+            put(set_github_token).delete(clear_github_token),
+        )
+}
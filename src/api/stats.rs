@@ -0,0 +1,27 @@
+//! Statistics API endpoints for the dashboard
+
+use crate::auth::middleware::AuthenticatedUser;
+use crate::error::AppError;
+use crate::models::stats::{LanguageCount, Stats};
+use axum::{extract::State, routing::get, Json, Router};
+use sqlx::PgPool;
+
+/// GET /api/stats/languages
+///
+/// Returns how many of the user's links use each programming language, most
+/// used first, e.g. `[{"language": "Rust", "count": 12}]`.
+///
+/// # Response
+/// - 200 OK: Link counts per language
+/// - 401 Unauthorized: No valid session
+async fn languages_handler(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Vec<LanguageCount>>, AppError> {
+    let counts = Stats::by_language(&pool, auth.user_id).await?;
+    Ok(Json(counts))
+}
+
+pub fn create_router() -> Router<super::AppState> {
+    Router::new().route("/languages", get(languages_handler))
+}
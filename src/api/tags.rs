@@ -1,13 +1,14 @@
 //! Tag management API endpoints
 
 use crate::auth::middleware::AuthenticatedUser;
+use crate::config::Config;
 use crate::error::AppError;
 use crate::models::Tag;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::post,
+    routing::{get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,17 @@ struct TagResponse {
     link_count: i64,
 }
 
+#[derive(Debug, Serialize)]
+struct OrphanedTagsResponse {
+    count: usize,
+    tags: Vec<Tag>,
+}
+
+#[derive(Debug, Serialize)]
+struct DeleteOrphanedResponse {
+    deleted: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct CreateTagRequest {
     name: String,
@@ -29,10 +41,11 @@ struct CreateTagRequest {
 /// POST /api/tags
 async fn create_tag(
     State(pool): State<PgPool>,
+    State(config): State<Config>,
     auth: AuthenticatedUser,
     Json(request): Json<CreateTagRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let tag = Tag::create(&pool, auth.user_id, &request.name).await?;
+    let tag = Tag::create(&pool, &config, auth.user_id, &request.name).await?;
     let response = TagResponse {
         id: tag.id,
         name: tag.name,
@@ -73,6 +86,31 @@ async fn list_tags(
     Ok(Json(response))
 }
 
+/// GET /api/tags/orphaned
+///
+/// Lists the user's tags that aren't attached to any link.
+async fn list_orphaned_tags(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<OrphanedTagsResponse>, AppError> {
+    let tags = Tag::get_orphaned(&pool, auth.user_id).await?;
+    Ok(Json(OrphanedTagsResponse {
+        count: tags.len(),
+        tags,
+    }))
+}
+
+/// DELETE /api/tags/orphaned
+///
+/// Deletes the user's tags that aren't attached to any link.
+async fn delete_orphaned_tags(
+    State(pool): State<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<Json<DeleteOrphanedResponse>, AppError> {
+    let deleted = Tag::delete_orphaned(&pool, auth.user_id).await?;
+    Ok(Json(DeleteOrphanedResponse { deleted }))
+}
+
 /// DELETE /api/tags/:id
 async fn delete_tag(
     State(pool): State<PgPool>,
@@ -87,5 +125,9 @@ async fn delete_tag(
 pub fn create_router() -> Router<super::AppState> {
     Router::new()
         .route("/", post(create_tag).get(list_tags))
+        .route(
+            "/orphaned",
+            get(list_orphaned_tags).delete(delete_orphaned_tags),
+        )
         .route("/{id}", axum::routing::delete(delete_tag))
 }
@@ -0,0 +1,68 @@
+//! WebSocket endpoint streaming live link events to the UI
+
+use crate::auth::middleware::AuthenticatedUser;
+use crate::events::{EventBus, UserEvent};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// GET /api/ws
+///
+/// Upgrades to a WebSocket that receives the authenticated user's link
+/// events as JSON text messages, e.g.
+/// `{"type": "link_status_changed", "id": "...", "status": "inaccessible"}`.
+/// Messages sent by the client are ignored.
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(events): State<EventBus>,
+    auth: AuthenticatedUser,
+) -> Response {
+    let receiver = events.subscribe();
+    ws.on_upgrade(move |socket| stream_events(socket, receiver, auth.user_id))
+}
+
+/// Forward the user's events until either side closes
+async fn stream_events(
+    mut socket: WebSocket,
+    mut receiver: broadcast::Receiver<UserEvent>,
+    user_id: Uuid,
+) {
+    tracing::debug!(user_id = %user_id, "WebSocket client connected");
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Ok(UserEvent { user_id: owner, event }) if owner == user_id => {
+                    let json = match serde_json::to_string(&event) {
+                        Ok(json) => json,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Failed to serialize link event");
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    // The client refetches on its own; dropping stale events is fine
+                    tracing::warn!(user_id = %user_id, skipped, "WebSocket client lagged, skipped events");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    tracing::debug!(user_id = %user_id, "WebSocket client disconnected");
+}
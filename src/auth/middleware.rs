@@ -4,39 +4,75 @@ use uuid::Uuid;
 
 use crate::error::AppError;
 
-/// Extract the client IP address from request parts.
-/// Checks X-Forwarded-For and X-Real-Ip headers first (for reverse proxy setups),
-/// then falls back to the connection info.
-fn client_ip(parts: &Parts) -> String {
-    if let Some(forwarded) = parts
-        .headers
-        .get("X-Forwarded-For")
-        .and_then(|v| v.to_str().ok())
-    {
-        if let Some(ip) = forwarded.split(',').next().map(|s| s.trim()) {
-            if !ip.is_empty() {
-                return ip.to_string();
+/// Resolve the client IP address of a request.
+///
+/// `X-Forwarded-For` and `X-Real-Ip` are only honoured when `trust_proxy` is
+/// set, since any client can send them; otherwise the peer address of the
+/// connection is used. Proxies append to `X-Forwarded-For`, so only its last
+/// entry (added by the trusted proxy) is used; earlier entries come from the
+/// client and may be spoofed.
+pub fn resolve_client_ip(
+    headers: &axum::http::HeaderMap,
+    connect_info: Option<&axum::extract::ConnectInfo<std::net::SocketAddr>>,
+    trust_proxy: bool,
+) -> String {
+    if trust_proxy {
+        if let Some(forwarded) = headers.get("X-Forwarded-For").and_then(|v| v.to_str().ok()) {
+            if let Some(ip) = forwarded.rsplit(',').next().map(|s| s.trim()) {
+                if !ip.is_empty() {
+                    return ip.to_string();
+                }
             }
         }
-    }
 
-    if let Some(real_ip) = parts.headers.get("X-Real-Ip").and_then(|v| v.to_str().ok()) {
-        let trimmed = real_ip.trim();
-        if !trimmed.is_empty() {
-            return trimmed.to_string();
+        if let Some(real_ip) = headers.get("X-Real-Ip").and_then(|v| v.to_str().ok()) {
+            let trimmed = real_ip.trim();
+            if !trimmed.is_empty() {
+                return trimmed.to_string();
+            }
         }
     }
 
-    if let Some(connect_info) = parts
-        .extensions
-        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
-    {
+    if let Some(connect_info) = connect_info {
         return connect_info.0.ip().to_string();
     }
 
     "unknown".to_string()
 }
 
+/// Extract the client IP address from request parts, honouring `TRUST_PROXY`.
+fn client_ip(parts: &Parts, config: &crate::config::Config) -> String {
+    resolve_client_ip(
+        &parts.headers,
+        parts
+            .extensions
+            .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>(),
+        config.trust_proxy,
+    )
+}
+
+/// Client IP address extractor.
+///
+/// Resolves to the proxy-supplied address when `TRUST_PROXY` is enabled and
+/// to the connection's peer address otherwise ("unknown" if neither is known).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIp(pub String);
+
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+    crate::config::Config: axum::extract::FromRef<S>,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        use axum::extract::FromRef;
+
+        let config = crate::config::Config::from_ref(state);
+        Ok(ClientIp(client_ip(parts, &config)))
+    }
+}
+
 /// JWT claims extracted from Authorization header.
 ///
 /// Use as an Axum extractor to require authentication on a route.
@@ -78,7 +114,7 @@ where
         use axum::extract::FromRef;
 
         let config = crate::config::Config::from_ref(state);
-        let ip = client_ip(parts);
+        let ip = client_ip(parts, &config);
         let path = parts.uri.path().to_string();
 
         // Extract Authorization header
@@ -161,7 +197,7 @@ where
         use axum_extra::extract::CookieJar;
 
         let pool = sqlx::PgPool::from_ref(state);
-        let ip = client_ip(parts);
+        let ip = client_ip(parts, &config);
         let path = parts.uri.path().to_string();
 
         // --- Path 1: rl_session cookie ---
@@ -289,6 +325,48 @@ mod tests {
         assert!(!auth.auth_via_oidc);
     }
 
+    #[test]
+    fn test_resolve_client_ip_ignores_proxy_headers_by_default() {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("X-Forwarded-For", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        headers.insert("X-Real-Ip", "198.51.100.2".parse().unwrap());
+        let peer = axum::extract::ConnectInfo(std::net::SocketAddr::from(([10, 0, 0, 1], 4000)));
+
+        assert_eq!(resolve_client_ip(&headers, Some(&peer), false), "10.0.0.1");
+        assert_eq!(resolve_client_ip(&headers, None, false), "unknown");
+    }
+
+    #[test]
+    fn test_resolve_client_ip_trusted_proxy() {
+        let mut headers = axum::http::HeaderMap::new();
+        let peer = axum::extract::ConnectInfo(std::net::SocketAddr::from(([10, 0, 0, 1], 4000)));
+
+        headers.insert("X-Real-Ip", "198.51.100.2".parse().unwrap());
+        assert_eq!(
+            resolve_client_ip(&headers, Some(&peer), true),
+            "198.51.100.2"
+        );
+
+        headers.insert("X-Forwarded-For", "203.0.113.7".parse().unwrap());
+        assert_eq!(
+            resolve_client_ip(&headers, Some(&peer), true),
+            "203.0.113.7"
+        );
+    }
+
+    #[test]
+    fn test_resolve_client_ip_ignores_spoofed_forwarded_prefix() {
+        let mut headers = axum::http::HeaderMap::new();
+        let peer = axum::extract::ConnectInfo(std::net::SocketAddr::from(([10, 0, 0, 1], 4000)));
+
+        // The client sent "1.2.3.4"; the proxy appended the address it saw
+        headers.insert("X-Forwarded-For", "1.2.3.4, 203.0.113.7".parse().unwrap());
+        assert_eq!(
+            resolve_client_ip(&headers, Some(&peer), true),
+            "203.0.113.7"
+        );
+    }
+
     #[test]
     fn test_admin_claims_wraps_claims() {
         let claims = Claims {
@@ -286,7 +286,7 @@ pub async fn callback(
         ])
         .send()
         .await
-        .map_err(|e| AppError::ExternalService(format!("Token endpoint request failed: {e}")))?;
+        .map_err(|e| AppError::request_failed("Token endpoint request failed", e))?;
 
     if !resp.status().is_success() {
         let err: TokenErrorResponse = resp.json().await.unwrap_or(TokenErrorResponse {
@@ -13,7 +13,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use crate::config::OidcConfig;
+use crate::config::{Config, OidcConfig};
 use crate::error::AppError;
 
 // ── JWKS types ────────────────────────────────────────────────────────────────
@@ -126,10 +126,13 @@ pub struct OidcVerifier {
 }
 
 impl OidcVerifier {
-    pub fn new(config: OidcConfig) -> Self {
+    /// Build a verifier for the OIDC settings in `config`
+    ///
+    /// JWKS requests go through the configured outbound proxy and TLS floor.
+    pub fn new(config: &Config) -> Self {
         Self {
-            config,
-            http: reqwest::Client::builder()
+            config: config.oidc.clone(),
+            http: crate::security::outbound_client_builder(config)
                 .timeout(std::time::Duration::from_secs(10))
                 .build()
                 .expect("failed to build HTTP client"),
@@ -391,7 +394,7 @@ impl OidcVerifier {
             .get(jwks_url)
             .send()
             .await
-            .map_err(|e| AppError::ExternalService(format!("JWKS fetch failed: {e}")))?
+            .map_err(|e| AppError::request_failed("JWKS fetch failed", e))?
             .json()
             .await
             .map_err(|e| AppError::ExternalService(format!("JWKS parse failed: {e}")))?;
@@ -114,12 +114,38 @@ pub enum AppError {
         field: String,
     },
 
+    /// The request conflicts with one still in progress
+    ///
+    /// The String explains what the client should do, e.g. retry later.
+    Conflict(String),
+
     /// External service error (GitHub API, web scraping, etc.)
     ///
     /// Wraps errors from external HTTP APIs and web scraping operations.
     /// The String contains details about which service failed and why.
     ExternalService(String),
 
+    /// An external service's rate limit is exhausted (e.g. the GitHub API)
+    ///
+    /// Reported to clients like `ExternalService`; a separate variant so
+    /// callers can back off without comparing messages. The String says what
+    /// to do about it.
+    RateLimited(String),
+
+    /// An external resource was removed for legal reasons (HTTP 451), e.g. a
+    /// GitHub repository taken down by a DMCA notice
+    ///
+    /// Reported to clients like `ExternalService`. The String describes the
+    /// resource.
+    UnavailableForLegalReasons(String),
+
+    /// A request to an external service, or a database query, timed out
+    ///
+    /// The String names what was being contacted (usually a host, or "the
+    /// database" for a query cancelled by `QUERY_TIMEOUT_MS`), so clients can
+    /// tell a slow site apart from other upstream failures.
+    Timeout(String),
+
     /// I/O error (file operations, network, etc.)
     ///
     /// Wraps std::io::Error for file system and network operations.
@@ -133,6 +159,11 @@ pub enum AppError {
     /// Account is locked due to too many failed login attempts
     AccountLocked,
 
+    /// Request body exceeds the configured size limit
+    ///
+    /// `limit` is the maximum accepted size in bytes.
+    PayloadTooLarge { limit: usize },
+
     /// Membership required (SaaS mode)
     ///
     /// The user is authenticated but does not have an active membership.
@@ -159,7 +190,7 @@ pub enum AppError {
 ///     "status": 409
 /// }
 /// ```
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ApiErrorResponse {
     /// User-friendly error message
     pub error: String,
@@ -224,6 +255,28 @@ impl AppError {
         AppError::Forbidden(message.to_string())
     }
 
+    /// Create an error for a failed outbound HTTP request
+    ///
+    /// Timeouts become `Timeout` naming the host; anything else becomes
+    /// `ExternalService` with `context` prefixed to the error.
+    ///
+    /// # Example
+    /// ```
+    /// let error = AppError::request_failed("Failed to fetch URL", err);
+    /// ```
+    pub fn request_failed(context: &str, err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            let target = err
+                .url()
+                .and_then(|url| url.host_str())
+                .unwrap_or("external service")
+                .to_string();
+            AppError::Timeout(target)
+        } else {
+            AppError::ExternalService(format!("{}: {}", context, err))
+        }
+    }
+
     /// Get the HTTP status code for this error
     ///
     /// Maps each error variant to an appropriate HTTP status code
@@ -234,10 +287,12 @@ impl AppError {
     /// - 401 Unauthorized: Invalid credentials, session expired
     /// - 403 Forbidden: Unauthorized access
     /// - 404 Not Found: Resource not found
-    /// - 409 Conflict: Duplicate resources
+    /// - 409 Conflict: Duplicate resources, requests conflicting with one in progress
     /// - 500 Internal Server Error: Database, I/O, JSON, Internal errors
-    /// - 502 Bad Gateway: External service errors
+    /// - 502 Bad Gateway: External service errors, including upstream rate
+    ///   limits and legal takedowns
     /// - 503 Service Unavailable: Configuration errors
+    /// - 504 Gateway Timeout: External service and database query timeouts
     pub fn status_code(&self) -> u16 {
         match self {
             AppError::Validation { .. } => 400,
@@ -247,14 +302,19 @@ impl AppError {
             AppError::Forbidden(_) => 403,
             AppError::NotFound { .. } => 404,
             AppError::Duplicate { .. } => 409,
+            AppError::Conflict(_) => 409,
             AppError::MembershipRequired(_) => 403,
             AppError::AccountLocked => 429,
+            AppError::PayloadTooLarge { .. } => 413,
             AppError::Database(_) => 500,
             AppError::Io(_) => 500,
             AppError::Json(_) => 500,
             AppError::Internal(_) => 500,
             AppError::ExternalService(_) => 502,
+            AppError::RateLimited(_) => 502,
+            AppError::UnavailableForLegalReasons(_) => 502,
             AppError::Configuration(_) => 503,
+            AppError::Timeout(_) => 504,
         }
     }
 
@@ -274,8 +334,13 @@ impl AppError {
             AppError::MembershipRequired(_) => "MEMBERSHIP_REQUIRED",
             AppError::NotFound { .. } => "NOT_FOUND",
             AppError::Duplicate { .. } => "DUPLICATE_FIELD",
+            AppError::Conflict(_) => "CONFLICT",
             AppError::AccountLocked => "ACCOUNT_LOCKED",
-            AppError::ExternalService(_) => "EXTERNAL_SERVICE_ERROR",
+            AppError::PayloadTooLarge { .. } => "PAYLOAD_TOO_LARGE",
+            AppError::ExternalService(_)
+            | AppError::RateLimited(_)
+            | AppError::UnavailableForLegalReasons(_) => "EXTERNAL_SERVICE_ERROR",
+            AppError::Timeout(_) => "TIMEOUT",
             AppError::Io(_) => "IO_ERROR",
             AppError::Json(_) => "JSON_ERROR",
             AppError::Internal(_) => "INTERNAL_ERROR",
@@ -320,12 +385,19 @@ impl AppError {
             AppError::Duplicate { field } => {
                 format!("{} already exists.", capitalize_first(field))
             }
+            AppError::Conflict(msg) => msg.clone(),
             AppError::AccountLocked => {
                 "Account is temporarily locked due to too many failed login attempts. Please try again later.".to_string()
             }
-            AppError::ExternalService(msg) => {
+            AppError::PayloadTooLarge { limit } => {
+                format!("Request body is too large (maximum {} bytes).", limit)
+            }
+            AppError::ExternalService(msg)
+            | AppError::RateLimited(msg)
+            | AppError::UnavailableForLegalReasons(msg) => {
                 format!("External service error: {}", msg)
             }
+            AppError::Timeout(target) => format!("The request to {} timed out.", target),
             AppError::Io(_) => "An I/O error occurred. Please try again.".to_string(),
             AppError::Json(_) => "Invalid JSON data.".to_string(),
             AppError::Internal(_) => {
@@ -366,6 +438,7 @@ impl std::error::Error for AppError {
 /// Handles special cases:
 /// - RowNotFound becomes NotFound error
 /// - Unique constraint violations become Duplicate errors
+/// - Statements cancelled by `statement_timeout` become Timeout errors
 /// - Other errors wrapped as Database errors
 impl From<sqlx::Error> for AppError {
     fn from(err: sqlx::Error) -> Self {
@@ -382,6 +455,10 @@ impl From<sqlx::Error> for AppError {
                 let field = extract_field_from_constraint(db_err.message());
                 AppError::Duplicate { field }
             }
+            // Cancelled by `statement_timeout` (query_canceled)
+            sqlx::Error::Database(ref db_err) if db_err.code().as_deref() == Some("57014") => {
+                AppError::Timeout("the database".to_string())
+            }
             // All other database errors
             _ => AppError::Database(err),
         }
@@ -412,7 +489,7 @@ impl From<sqlx::migrate::MigrateError> for AppError {
 /// Convert reqwest::Error to AppError
 impl From<reqwest::Error> for AppError {
     fn from(err: reqwest::Error) -> Self {
-        AppError::ExternalService(format!("HTTP request failed: {}", err))
+        AppError::request_failed("HTTP request failed", err)
     }
 }
 
@@ -498,6 +575,9 @@ impl axum::response::IntoResponse for AppError {
             AppError::Duplicate { field } => {
                 tracing::info!(field = %field, "Duplicate field error");
             }
+            AppError::Conflict(msg) => {
+                tracing::info!(message = %msg, "Conflicting request");
+            }
             AppError::NotFound { resource, id } => {
                 tracing::debug!(resource = %resource, id = %id, "Resource not found");
             }
@@ -507,6 +587,9 @@ impl axum::response::IntoResponse for AppError {
             AppError::AccountLocked => {
                 tracing::warn!("Account locked due to too many failed attempts");
             }
+            AppError::PayloadTooLarge { limit } => {
+                tracing::info!(limit = limit, "Request body too large");
+            }
             AppError::Database(e) => {
                 tracing::error!(error = %e, "Database error");
             }
@@ -519,6 +602,15 @@ impl axum::response::IntoResponse for AppError {
             AppError::ExternalService(msg) => {
                 tracing::error!(message = %msg, "External service error");
             }
+            AppError::RateLimited(msg) => {
+                tracing::warn!(message = %msg, "External service rate limit exceeded");
+            }
+            AppError::UnavailableForLegalReasons(msg) => {
+                tracing::warn!(message = %msg, "External resource unavailable for legal reasons");
+            }
+            AppError::Timeout(target) => {
+                tracing::warn!(target = %target, "Request timed out");
+            }
             AppError::Io(e) => {
                 tracing::error!(error = %e, "I/O error");
             }
@@ -571,6 +663,14 @@ mod tests {
         assert!(error.to_string().contains("User not found"));
     }
 
+    #[test]
+    fn test_timeout_error() {
+        let error = AppError::Timeout("example.com".to_string());
+        assert_eq!(error.status_code(), 504);
+        assert_eq!(error.error_code(), "TIMEOUT");
+        assert_eq!(error.to_string(), "The request to example.com timed out.");
+    }
+
     #[test]
     fn test_duplicate_error() {
         let error = AppError::duplicate("email");
@@ -635,6 +735,22 @@ mod tests {
         assert!(error.to_string().contains("temporarily locked"));
     }
 
+    #[test]
+    fn test_conflict_error() {
+        let error = AppError::Conflict("Try again shortly".to_string());
+        assert_eq!(error.status_code(), 409);
+        assert_eq!(error.error_code(), "CONFLICT");
+        assert_eq!(error.to_string(), "Try again shortly");
+    }
+
+    #[test]
+    fn test_payload_too_large_error() {
+        let error = AppError::PayloadTooLarge { limit: 1024 };
+        assert_eq!(error.status_code(), 413);
+        assert_eq!(error.error_code(), "PAYLOAD_TOO_LARGE");
+        assert!(error.to_string().contains("1024 bytes"));
+    }
+
     #[test]
     fn test_membership_required_error() {
         let error = AppError::MembershipRequired("https://example.com/membership".to_string());
@@ -659,6 +775,20 @@ mod tests {
         assert!(error.to_string().contains("GitHub API timeout"));
     }
 
+    #[test]
+    fn test_rate_limited_and_takedown_errors_report_as_external_service() {
+        for error in [
+            AppError::RateLimited("GitHub API rate limit exceeded".to_string()),
+            AppError::UnavailableForLegalReasons("GitHub repository o/r".to_string()),
+        ] {
+            assert_eq!(error.status_code(), 502);
+            assert_eq!(error.error_code(), "EXTERNAL_SERVICE_ERROR");
+            assert!(error
+                .to_string()
+                .starts_with("External service error: GitHub"));
+        }
+    }
+
     #[test]
     fn test_internal_error() {
         let error = AppError::Internal("unexpected state".to_string());
@@ -734,10 +864,14 @@ mod tests {
             AppError::forbidden("msg"),
             AppError::not_found("r", "id"),
             AppError::duplicate("f"),
+            AppError::Conflict("c".to_string()),
             AppError::AccountLocked,
+            AppError::PayloadTooLarge { limit: 1 },
             AppError::MembershipRequired("url".to_string()),
             AppError::Configuration("c".to_string()),
             AppError::ExternalService("e".to_string()),
+            AppError::RateLimited("r".to_string()),
+            AppError::UnavailableForLegalReasons("u".to_string()),
             AppError::Internal("i".to_string()),
         ];
 
@@ -0,0 +1,122 @@
+//! In-process event bus for live UI updates
+//!
+//! Producers (the scheduler, API handlers) publish `LinkEvent`s for a user on
+//! a `tokio::sync::broadcast` channel; each WebSocket connection subscribes
+//! and forwards the events that belong to its user.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Events a receiver can miss before it lags and skips ahead
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Change to a link, as sent to WebSocket clients
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LinkEvent {
+    /// A link moved to a new status (e.g. `active` -> `inaccessible`)
+    LinkStatusChanged { id: Uuid, status: String },
+    /// A link was added; clients fetch it if they show it
+    LinkCreated { id: Uuid },
+    /// A link's fields were edited
+    LinkUpdated { id: Uuid },
+    /// A link was deleted
+    LinkDeleted { id: Uuid },
+}
+
+/// An event addressed to a single user
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserEvent {
+    pub user_id: Uuid,
+    pub event: LinkEvent,
+}
+
+/// Handle for publishing and subscribing to link events
+///
+/// Cheap to clone; all clones share the same channel.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<UserEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+
+    /// Publish an event for a user
+    ///
+    /// Never blocks: events published while nobody is subscribed are dropped,
+    /// and subscribers that fall more than the channel capacity behind skip
+    /// the oldest events.
+    pub fn publish(&self, user_id: Uuid, event: LinkEvent) {
+        // An error only means there are no subscribers right now
+        let _ = self.sender.send(UserEvent { user_id, event });
+    }
+
+    /// Receive events published from now on, for all users
+    pub fn subscribe(&self) -> broadcast::Receiver<UserEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_reaches_subscribers() {
+        let bus = EventBus::new();
+        // Publishing without subscribers is a no-op
+        bus.publish(
+            Uuid::new_v4(),
+            LinkEvent::LinkStatusChanged {
+                id: Uuid::new_v4(),
+                status: "active".to_string(),
+            },
+        );
+
+        let mut receiver = bus.subscribe();
+        let user_id = Uuid::new_v4();
+        let event = LinkEvent::LinkStatusChanged {
+            id: Uuid::new_v4(),
+            status: "inaccessible".to_string(),
+        };
+        bus.publish(user_id, event.clone());
+
+        assert_eq!(receiver.recv().await.unwrap(), UserEvent { user_id, event });
+    }
+
+    #[test]
+    fn test_link_event_json() {
+        let id = Uuid::nil();
+        let json = serde_json::to_value(LinkEvent::LinkStatusChanged {
+            id,
+            status: "archived".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "link_status_changed",
+                "id": id,
+                "status": "archived"
+            })
+        );
+
+        let json = serde_json::to_value(LinkEvent::LinkDeleted { id }).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "link_deleted", "id": id })
+        );
+    }
+}
@@ -3,11 +3,11 @@
 //! This module provides functions to interact with the GitHub API to fetch
 //! repository metadata such as stars, description, language, license, etc.
 
+use crate::config::Config;
 use crate::error::AppError;
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::Deserialize;
-use std::sync::OnceLock;
 
 /// Metadata fetched from a GitHub repository
 #[derive(Debug, Clone, serde::Serialize)]
@@ -18,17 +18,30 @@ pub struct GitHubRepoMetadata {
     pub last_commit: Option<DateTime<Utc>>,
     pub license: Option<String>,
     pub language: Option<String>,
+    /// Account type of the repository owner ("User" or "Organization")
+    pub owner_type: Option<String>,
+    /// Repository topics (e.g. "cli", "async")
+    pub topics: Vec<String>,
+    /// New "owner/repo" when the requested repository was renamed or
+    /// transferred (GitHub answers the old name with a 301 to the new one)
+    pub moved_to: Option<String>,
 }
 
 /// Response from GitHub API for repository information
 #[derive(Debug, Deserialize)]
 struct GitHubApiResponse {
+    #[serde(default)]
+    full_name: Option<String>,
     stargazers_count: i32,
     description: Option<String>,
     archived: bool,
     pushed_at: Option<String>,
     license: Option<GitHubLicense>,
     language: Option<String>,
+    #[serde(default)]
+    owner: Option<GitHubOwner>,
+    #[serde(default)]
+    topics: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,66 +49,133 @@ struct GitHubLicense {
     name: String,
 }
 
-/// Check if a URL is a GitHub repository URL
-///
-/// Handles various GitHub URL formats:
-/// - https://github.com/owner/repo
-/// - https://github.com/owner/repo.git
-/// - https://github.com/owner/repo/tree/branch
-/// - git@github.com:owner/repo.git
-///
-/// # Examples
-/// ```
-/// assert!(is_github_repo("https://github.com/rust-lang/rust"));
-/// assert!(is_github_repo("https://github.com/rust-lang/rust.git"));
-/// assert!(!is_github_repo("https://gitlab.com/user/project"));
-/// ```
-pub fn is_github_repo(url: &str) -> bool {
-    static GITHUB_REGEX: OnceLock<Regex> = OnceLock::new();
-    let re = GITHUB_REGEX.get_or_init(|| {
-        Regex::new(
-            r"^(?:https?://github\.com/|git@github\.com:)([^/]+)/([^/\s]+?)(?:\.git)?(?:/.*)?$",
-        )
-        .unwrap()
-    });
-
-    re.is_match(url)
+#[derive(Debug, Deserialize)]
+struct GitHubOwner {
+    #[serde(rename = "type")]
+    owner_type: Option<String>,
 }
 
-/// Parse owner and repository name from a GitHub URL
-///
-/// # Returns
-/// Returns `Some((owner, repo))` if the URL is a valid GitHub repository URL,
-/// otherwise returns `None`.
+/// GitHub instance that repository links point at
 ///
-/// # Examples
-/// ```
-/// assert_eq!(
-///     parse_repo_from_url("https://github.com/rust-lang/rust"),
-///     Some(("rust-lang".to_string(), "rust".to_string()))
-/// );
-/// assert_eq!(
-///     parse_repo_from_url("https://github.com/owner/repo.git"),
-///     Some(("owner".to_string(), "repo".to_string()))
-/// );
-/// ```
-pub fn parse_repo_from_url(url: &str) -> Option<(String, String)> {
-    static GITHUB_REGEX: OnceLock<Regex> = OnceLock::new();
-    let re = GITHUB_REGEX.get_or_init(|| {
-        Regex::new(
-            r"^(?:https?://github\.com/|git@github\.com:)([^/]+)/([^/\s]+?)(?:\.git)?(?:/.*)?$",
-        )
-        .unwrap()
-    });
-
-    re.captures(url).map(|caps| {
-        let owner = caps.get(1).unwrap().as_str().to_string();
-        let repo = caps.get(2).unwrap().as_str().to_string();
-        (owner, repo)
-    })
+/// Defaults to public github.com. GitHub Enterprise Server deployments set
+/// their own web host and API base URL (usually `https://<host>/api/v3`).
+#[derive(Debug, Clone)]
+pub struct GitHubHost {
+    /// Web host of repository URLs, e.g. `github.com`
+    pub host: String,
+    /// REST API base URL without a trailing slash, e.g. `https://api.github.com`
+    pub api_base_url: String,
+    repo_regex: Regex,
+}
+
+impl GitHubHost {
+    pub fn new(host: &str, api_base_url: &str) -> Self {
+        let host = host.trim().to_ascii_lowercase();
+        let escaped = regex::escape(&host);
+        let repo_regex = Regex::new(&format!(
+            r"^(?:https?://{0}/|git@{0}:)([^/]+)/([^/\s]+?)(?:\.git)?(?:/.*)?$",
+            escaped
+        ))
+        .expect("escaped host forms a valid regex");
+
+        Self {
+            host,
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
+            repo_regex,
+        }
+    }
+
+    /// Public github.com
+    pub fn public() -> Self {
+        Self::new("github.com", "https://api.github.com")
+    }
+
+    /// Check if a URL is a repository URL on this host
+    ///
+    /// Handles various GitHub URL formats:
+    /// - https://github.com/owner/repo
+    /// - https://github.com/owner/repo.git
+    /// - https://github.com/owner/repo/tree/branch
+    /// - git@github.com:owner/repo.git
+    ///
+    /// # Examples
+    /// ```
+    /// let github = GitHubHost::public();
+    /// assert!(github.is_github_repo("https://github.com/rust-lang/rust"));
+    /// assert!(github.is_github_repo("https://github.com/rust-lang/rust.git"));
+    /// assert!(!github.is_github_repo("https://gitlab.com/user/project"));
+    /// ```
+    pub fn is_github_repo(&self, url: &str) -> bool {
+        self.repo_regex.is_match(url)
+    }
+
+    /// Parse owner and repository name from a repository URL on this host
+    ///
+    /// # Returns
+    /// Returns `Some((owner, repo))` if the URL is a valid repository URL on
+    /// this host, otherwise returns `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// let github = GitHubHost::public();
+    /// assert_eq!(
+    ///     github.parse_repo_from_url("https://github.com/rust-lang/rust"),
+    ///     Some(("rust-lang".to_string(), "rust".to_string()))
+    /// );
+    /// assert_eq!(
+    ///     github.parse_repo_from_url("https://github.com/owner/repo.git"),
+    ///     Some(("owner".to_string(), "repo".to_string()))
+    /// );
+    /// ```
+    pub fn parse_repo_from_url(&self, url: &str) -> Option<(String, String)> {
+        self.repo_regex.captures(url).map(|caps| {
+            let owner = caps.get(1).unwrap().as_str().to_string();
+            let repo = caps.get(2).unwrap().as_str().to_string();
+            (owner, repo)
+        })
+    }
+
+    /// Rewrite a repository URL on this host to its canonical form
+    ///
+    /// Drops `.git`, trailing paths such as `/tree/main`, and converts the SSH
+    /// form, so `git@github.com:owner/repo.git` becomes
+    /// `https://github.com/owner/repo`. Returns `None` for other URLs.
+    pub fn canonical_repo_url(&self, url: &str) -> Option<String> {
+        self.parse_repo_from_url(url)
+            .map(|(owner, repo)| format!("https://{}/{}/{}", self.host, owner, repo))
+    }
+
+    /// Whether `url` is an https URL on this host
+    pub fn is_host_url(&self, url: &str) -> bool {
+        url.strip_prefix("https://")
+            .and_then(|rest| rest.strip_prefix(self.host.as_str()))
+            .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Point a repository URL on this host at a moved repository
+    ///
+    /// Replaces the owner/repo segments with `new_full_name` ("owner/repo"),
+    /// keeping any trailing path (e.g. `/tree/main`) and a `.git` suffix.
+    /// Returns `None` if `url` is not an https repository URL on this host.
+    pub fn replace_repo_in_url(&self, url: &str, new_full_name: &str) -> Option<String> {
+        let prefix = format!("https://{}/", self.host);
+        let rest = url.strip_prefix(&prefix)?;
+        let mut segments = rest.splitn(3, '/');
+        let _owner = segments.next().filter(|s| !s.is_empty())?;
+        let repo = segments.next().filter(|s| !s.is_empty())?;
+        let tail = segments.next();
+
+        let suffix = if repo.ends_with(".git") { ".git" } else { "" };
+        let mut new_url = format!("{}{}{}", prefix, new_full_name, suffix);
+        if let Some(tail) = tail {
+            new_url.push('/');
+            new_url.push_str(tail);
+        }
+        Some(new_url)
+    }
 }
 
-/// Fetch repository metadata from GitHub API
+/// Fetch repository metadata from the configured GitHub API
 ///
 /// # Arguments
 /// * `owner` - Repository owner (username or organization)
@@ -103,58 +183,136 @@ pub fn parse_repo_from_url(url: &str) -> Option<(String, String)> {
 ///
 /// # Returns
 /// Returns `GitHubRepoMetadata` on success, or an error if:
-/// - The repository doesn't exist (404)
-/// - Rate limit exceeded (403)
+/// - The repository doesn't exist (404, `AppError::NotFound`)
+/// - Rate limit exceeded (429, or a 403 with no remaining quota, a `retry-after`
+///   header or a rate limit message; `AppError::RateLimited`)
+/// - The repository is private or access is denied (other 403, `AppError::Forbidden`)
+/// - The repository was taken down, e.g. by a DMCA notice (451,
+///   `AppError::UnavailableForLegalReasons`)
 /// - Network error
 ///
 /// # Rate Limiting
 /// - Unauthenticated requests: 60 requests per hour
 /// - Authenticated requests (with GITHUB_TOKEN): 5000 requests per hour
 ///
+/// Uses the server-wide `GITHUB_TOKEN`; see `fetch_repo_metadata_as` for
+/// requests made on behalf of a user.
+///
 /// # Example
 /// ```
-/// let metadata = fetch_repo_metadata("rust-lang", "rust").await?;
+/// let metadata = fetch_repo_metadata(&config, "rust-lang", "rust").await?;
 /// println!("Stars: {}", metadata.stars);
 /// ```
-pub async fn fetch_repo_metadata(owner: &str, repo: &str) -> Result<GitHubRepoMetadata, AppError> {
-    let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+pub async fn fetch_repo_metadata(
+    config: &Config,
+    owner: &str,
+    repo: &str,
+) -> Result<GitHubRepoMetadata, AppError> {
+    fetch_repo_metadata_as(config, owner, repo, None).await
+}
+
+/// Fetch repository metadata with a user's personal token
+///
+/// `user_token` (from the user's settings) takes precedence over the
+/// server-wide `GITHUB_TOKEN`, so requests a user triggers count against
+/// their own rate limit and can see repositories their token can access.
+pub async fn fetch_repo_metadata_as(
+    config: &Config,
+    owner: &str,
+    repo: &str,
+    user_token: Option<&str>,
+) -> Result<GitHubRepoMetadata, AppError> {
+    fetch_repo_metadata_from(config, &config.github.api_base_url, owner, repo, user_token).await
+}
+
+/// Fetch repository metadata from a GitHub-compatible API at `api_base`
+async fn fetch_repo_metadata_from(
+    config: &Config,
+    api_base: &str,
+    owner: &str,
+    repo: &str,
+    user_token: Option<&str>,
+) -> Result<GitHubRepoMetadata, AppError> {
+    let url = format!("{}/repos/{}/{}", api_base, owner, repo);
 
     tracing::info!(
         owner = %owner,
         repo = %repo,
+        url = %crate::security::mask_sensitive_query_params(&url, &config.log_masked_query_params),
         "Fetching GitHub repository metadata"
     );
 
     // Build HTTP client with required headers
-    let mut request_builder = reqwest::Client::new()
+    let mut request_builder = crate::security::outbound_client_builder(config)
+        .build()?
         .get(&url)
         .header("User-Agent", "RustyLinks/1.0")
         .header("Accept", "application/vnd.github+json");
 
     // Add GitHub token if available for higher rate limits
-    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+    let server_token = std::env::var("GITHUB_TOKEN").ok();
+    if let Some(token) = user_token.or(server_token.as_deref()) {
         if !token.is_empty() {
-            tracing::debug!("Using GitHub token for authentication");
+            tracing::debug!(
+                personal = user_token.is_some(),
+                "Using GitHub token for authentication"
+            );
             request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
         }
     }
 
+    // Held until the response body has been read below
+    let _permit = config.outbound_limiter.acquire().await;
     let response = request_builder.send().await?;
 
-    // Check for rate limiting
-    if response.status() == 403 {
-        if let Some(remaining) = response.headers().get("x-ratelimit-remaining") {
-            if remaining == "0" {
-                tracing::warn!(
-                    owner = %owner,
-                    repo = %repo,
-                    "GitHub API rate limit exceeded"
-                );
-                return Err(AppError::ExternalService(
-                    "GitHub API rate limit exceeded. Please try again later or set GITHUB_TOKEN environment variable.".to_string()
-                ));
-            }
+    // Distinguish rate limiting from a genuine permission error
+    if response.status() == 403 || response.status() == 429 {
+        // Secondary rate limits leave quota remaining but send `retry-after`
+        // or say so in the message
+        let headers = response.headers();
+        let rate_limited = response.status() == 429
+            || headers
+                .get("x-ratelimit-remaining")
+                .is_some_and(|remaining| remaining == "0")
+            || headers.contains_key(reqwest::header::RETRY_AFTER)
+            || response
+                .text()
+                .await
+                .is_ok_and(|body| body.to_lowercase().contains("rate limit"));
+
+        if rate_limited {
+            tracing::warn!(
+                owner = %owner,
+                repo = %repo,
+                "GitHub API rate limit exceeded"
+            );
+            return Err(AppError::RateLimited(
+                "GitHub API rate limit exceeded. Please try again later or set GITHUB_TOKEN environment variable.".to_string(),
+            ));
         }
+
+        tracing::warn!(
+            owner = %owner,
+            repo = %repo,
+            "GitHub repository is private or access is forbidden"
+        );
+        return Err(AppError::Forbidden(format!(
+            "GitHub repository {}/{} is private or access is forbidden",
+            owner, repo
+        )));
+    }
+
+    // Taken down repositories stay unavailable, so report them distinctly
+    if response.status() == 451 {
+        tracing::warn!(
+            owner = %owner,
+            repo = %repo,
+            "GitHub repository is unavailable for legal reasons"
+        );
+        return Err(AppError::UnavailableForLegalReasons(format!(
+            "GitHub repository {}/{} is unavailable for legal reasons (HTTP 451), e.g. a DMCA takedown",
+            owner, repo
+        )));
     }
 
     // Check for not found
@@ -192,6 +350,18 @@ pub async fn fetch_repo_metadata(owner: &str, repo: &str) -> Result<GitHubRepoMe
         .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
         .map(|dt| dt.with_timezone(&Utc));
 
+    // Redirects are followed, so a moved repo reports its new full_name
+    let moved_to = api_response
+        .full_name
+        .filter(|full_name| !full_name.eq_ignore_ascii_case(&format!("{}/{}", owner, repo)));
+    if let Some(ref new_name) = moved_to {
+        tracing::info!(
+            from = %format!("{}/{}", owner, repo),
+            to = %new_name,
+            "GitHub repository has moved"
+        );
+    }
+
     let metadata = GitHubRepoMetadata {
         stars: api_response.stargazers_count,
         description: api_response.description,
@@ -199,6 +369,9 @@ pub async fn fetch_repo_metadata(owner: &str, repo: &str) -> Result<GitHubRepoMe
         last_commit,
         license: api_response.license.map(|l| l.name),
         language: api_response.language,
+        owner_type: api_response.owner.and_then(|o| o.owner_type),
+        topics: api_response.topics,
+        moved_to,
     };
 
     tracing::info!(
@@ -219,78 +392,113 @@ mod tests {
     #[test]
     fn test_is_github_repo() {
         // Valid GitHub URLs
-        assert!(is_github_repo("https://github.com/rust-lang/rust"));
-        assert!(is_github_repo("https://github.com/rust-lang/rust.git"));
-        assert!(is_github_repo("https://github.com/owner/repo/tree/main"));
-        assert!(is_github_repo(
-            "https://github.com/owner/repo/blob/main/README.md"
-        ));
-        assert!(is_github_repo("git@github.com:owner/repo.git"));
+        assert!(GitHubHost::public().is_github_repo("https://github.com/rust-lang/rust"));
+        assert!(GitHubHost::public().is_github_repo("https://github.com/rust-lang/rust.git"));
+        assert!(GitHubHost::public().is_github_repo("https://github.com/owner/repo/tree/main"));
+        assert!(GitHubHost::public()
+            .is_github_repo("https://github.com/owner/repo/blob/main/README.md"));
+        assert!(GitHubHost::public().is_github_repo("git@github.com:owner/repo.git"));
 
         // Invalid URLs
-        assert!(!is_github_repo("https://gitlab.com/user/project"));
-        assert!(!is_github_repo("https://bitbucket.org/user/repo"));
-        assert!(!is_github_repo("https://github.com/"));
-        assert!(!is_github_repo("https://github.com/user"));
-        assert!(!is_github_repo("not a url"));
+        assert!(!GitHubHost::public().is_github_repo("https://gitlab.com/user/project"));
+        assert!(!GitHubHost::public().is_github_repo("https://bitbucket.org/user/repo"));
+        assert!(!GitHubHost::public().is_github_repo("https://github.com/"));
+        assert!(!GitHubHost::public().is_github_repo("https://github.com/user"));
+        assert!(!GitHubHost::public().is_github_repo("not a url"));
     }
 
     #[test]
     fn test_is_github_repo_http() {
-        assert!(is_github_repo("http://github.com/owner/repo"));
+        assert!(GitHubHost::public().is_github_repo("http://github.com/owner/repo"));
     }
 
     #[test]
     fn test_is_github_repo_with_trailing_slash() {
-        assert!(is_github_repo("https://github.com/owner/repo/"));
+        assert!(GitHubHost::public().is_github_repo("https://github.com/owner/repo/"));
     }
 
     #[test]
     fn test_is_github_repo_empty_string() {
-        assert!(!is_github_repo(""));
+        assert!(!GitHubHost::public().is_github_repo(""));
     }
 
     #[test]
     fn test_parse_repo_from_url() {
         // Valid GitHub URLs
         assert_eq!(
-            parse_repo_from_url("https://github.com/rust-lang/rust"),
+            GitHubHost::public().parse_repo_from_url("https://github.com/rust-lang/rust"),
             Some(("rust-lang".to_string(), "rust".to_string()))
         );
         assert_eq!(
-            parse_repo_from_url("https://github.com/owner/repo.git"),
+            GitHubHost::public().parse_repo_from_url("https://github.com/owner/repo.git"),
             Some(("owner".to_string(), "repo".to_string()))
         );
         assert_eq!(
-            parse_repo_from_url("https://github.com/user/project/tree/main"),
+            GitHubHost::public().parse_repo_from_url("https://github.com/user/project/tree/main"),
             Some(("user".to_string(), "project".to_string()))
         );
         assert_eq!(
-            parse_repo_from_url("git@github.com:owner/repo.git"),
+            GitHubHost::public().parse_repo_from_url("git@github.com:owner/repo.git"),
             Some(("owner".to_string(), "repo".to_string()))
         );
 
         // Invalid URLs
-        assert_eq!(parse_repo_from_url("https://gitlab.com/user/project"), None);
-        assert_eq!(parse_repo_from_url("https://github.com/"), None);
-        assert_eq!(parse_repo_from_url("not a url"), None);
+        assert_eq!(
+            GitHubHost::public().parse_repo_from_url("https://gitlab.com/user/project"),
+            None
+        );
+        assert_eq!(
+            GitHubHost::public().parse_repo_from_url("https://github.com/"),
+            None
+        );
+        assert_eq!(GitHubHost::public().parse_repo_from_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_canonical_repo_url() {
+        for url in [
+            "https://github.com/owner/repo",
+            "https://github.com/owner/repo.git",
+            "https://github.com/owner/repo/tree/main",
+            "http://github.com/owner/repo/blob/main/src/lib.rs",
+            "git@github.com:owner/repo.git",
+        ] {
+            assert_eq!(
+                GitHubHost::public().canonical_repo_url(url).as_deref(),
+                Some("https://github.com/owner/repo"),
+                "{}",
+                url
+            );
+        }
+
+        assert_eq!(
+            GitHubHost::public().canonical_repo_url("https://gitlab.com/owner/repo"),
+            None
+        );
+        assert_eq!(
+            GitHubHost::public().canonical_repo_url("https://example.com/owner/repo"),
+            None
+        );
     }
 
     #[test]
     fn test_parse_repo_strips_git_suffix() {
-        let result = parse_repo_from_url("https://github.com/owner/my-repo.git");
+        let result =
+            GitHubHost::public().parse_repo_from_url("https://github.com/owner/my-repo.git");
         assert_eq!(result, Some(("owner".to_string(), "my-repo".to_string())));
     }
 
     #[test]
     fn test_parse_repo_with_deep_path() {
-        let result = parse_repo_from_url("https://github.com/owner/repo/blob/main/src/lib.rs");
+        let result = GitHubHost::public()
+            .parse_repo_from_url("https://github.com/owner/repo/blob/main/src/lib.rs");
         assert_eq!(result, Some(("owner".to_string(), "repo".to_string())));
     }
 
     #[test]
     fn test_parse_repo_ssh_format() {
-        let result = parse_repo_from_url("git@github.com:my-org/my-project.git");
+        let result =
+            GitHubHost::public().parse_repo_from_url("git@github.com:my-org/my-project.git");
         assert_eq!(
             result,
             Some(("my-org".to_string(), "my-project".to_string()))
@@ -307,7 +515,8 @@ mod tests {
         ];
         for url in urls {
             assert!(
-                is_github_repo(url) == parse_repo_from_url(url).is_some(),
+                GitHubHost::public().is_github_repo(url)
+                    == GitHubHost::public().parse_repo_from_url(url).is_some(),
                 "Inconsistency for URL: {}",
                 url
             );
@@ -323,9 +532,263 @@ mod tests {
             last_commit: None,
             license: Some("MIT".to_string()),
             language: Some("Rust".to_string()),
+            owner_type: Some("Organization".to_string()),
+            topics: vec![],
+            moved_to: None,
         };
         assert_eq!(meta.stars, 1000);
         assert!(!meta.archived);
         assert_eq!(meta.license, Some("MIT".to_string()));
     }
+
+    #[test]
+    fn test_api_response_owner_type() {
+        let with_owner: GitHubApiResponse = serde_json::from_str(
+            r#"{"stargazers_count": 1, "description": null, "archived": false,
+                "pushed_at": null, "license": null, "language": null,
+                "owner": {"login": "rust-lang", "type": "Organization"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            with_owner.owner.and_then(|o| o.owner_type),
+            Some("Organization".to_string())
+        );
+
+        let without_owner: GitHubApiResponse = serde_json::from_str(
+            r#"{"stargazers_count": 1, "description": null, "archived": false,
+                "pushed_at": null, "license": null, "language": null}"#,
+        )
+        .unwrap();
+        assert!(without_owner.owner.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_repo_metadata_follows_repo_move() {
+        use axum::{http::header, response::IntoResponse, routing::get, Router};
+
+        let app = Router::new()
+            .route(
+                "/repos/old-owner/old-name",
+                get(|| async {
+                    (
+                        axum::http::StatusCode::MOVED_PERMANENTLY,
+                        [(header::LOCATION, "/repositories/42")],
+                    )
+                        .into_response()
+                }),
+            )
+            .route(
+                "/repositories/42",
+                get(|| async {
+                    axum::Json(serde_json::json!({
+                        "full_name": "new-owner/new-name",
+                        "stargazers_count": 7,
+                        "description": null,
+                        "archived": false,
+                        "pushed_at": null,
+                        "license": null,
+                        "language": null
+                    }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = crate::config::test_config();
+        let meta = fetch_repo_metadata_from(&config, &base, "old-owner", "old-name", None)
+            .await
+            .unwrap();
+        assert_eq!(meta.stars, 7);
+        assert_eq!(meta.moved_to, Some("new-owner/new-name".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_repo_metadata_sends_user_token() {
+        use axum::{http::HeaderMap, routing::get, Router};
+
+        let app = Router::new().route(
+            "/repos/owner/name",
+            get(|headers: HeaderMap| async move {
+                assert_eq!(headers.get("authorization").unwrap(), "Bearer ghp_personal");
+                axum::Json(serde_json::json!({
+                    "full_name": "owner/name",
+                    "stargazers_count": 3,
+                    "description": null,
+                    "archived": false,
+                    "pushed_at": null,
+                    "license": null,
+                    "language": null
+                }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = crate::config::test_config();
+        let meta = fetch_repo_metadata_from(&config, &base, "owner", "name", Some("ghp_personal"))
+            .await
+            .unwrap();
+        assert_eq!(meta.stars, 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_repo_metadata_reports_takedown() {
+        use axum::{http::StatusCode, routing::get, Router};
+
+        let app = Router::new().route(
+            "/repos/owner/name",
+            get(|| async { StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = crate::config::test_config();
+        let err = fetch_repo_metadata_from(&config, &base, "owner", "name", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::UnavailableForLegalReasons(_)));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_repo_metadata_secondary_rate_limit_is_not_forbidden() {
+        use axum::{http::StatusCode, routing::get, Router};
+
+        let app = Router::new()
+            .route(
+                "/repos/owner/retry-after",
+                get(|| async { (StatusCode::FORBIDDEN, [("retry-after", "60")], "") }),
+            )
+            .route(
+                "/repos/owner/message",
+                get(|| async {
+                    (
+                        StatusCode::FORBIDDEN,
+                        [("x-ratelimit-remaining", "4999")],
+                        r#"{"message": "You have exceeded a secondary rate limit."}"#,
+                    )
+                }),
+            )
+            .route(
+                "/repos/owner/private",
+                get(|| async {
+                    (
+                        StatusCode::FORBIDDEN,
+                        [("x-ratelimit-remaining", "4999")],
+                        r#"{"message": "Resource not accessible by integration"}"#,
+                    )
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let config = crate::config::test_config();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        for repo in ["retry-after", "message"] {
+            let err = fetch_repo_metadata_from(&config, &base, "owner", repo, None)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, AppError::RateLimited(_)),
+                "{} should be rate limited",
+                repo
+            );
+        }
+
+        let err = fetch_repo_metadata_from(&config, &base, "owner", "private", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Forbidden(_)));
+    }
+
+    #[test]
+    fn test_replace_repo_in_url() {
+        assert_eq!(
+            GitHubHost::public()
+                .replace_repo_in_url("https://github.com/old/name/tree/main", "new/renamed"),
+            Some("https://github.com/new/renamed/tree/main".to_string())
+        );
+        assert_eq!(
+            GitHubHost::public().replace_repo_in_url("https://github.com/old/name", "new/renamed"),
+            Some("https://github.com/new/renamed".to_string())
+        );
+        assert_eq!(
+            GitHubHost::public()
+                .replace_repo_in_url("https://github.com/old/name.git", "new/renamed"),
+            Some("https://github.com/new/renamed.git".to_string())
+        );
+        assert_eq!(
+            GitHubHost::public().replace_repo_in_url("https://github.com/old", "new/renamed"),
+            None
+        );
+        assert_eq!(
+            GitHubHost::public().replace_repo_in_url("https://gitlab.com/a/b", "new/renamed"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_api_response_with_topics() {
+        let response: GitHubApiResponse = serde_json::from_str(
+            r#"{"stargazers_count": 1, "description": null, "archived": false,
+                "pushed_at": null, "license": null, "language": "Rust",
+                "topics": ["cli", "async"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.topics,
+            vec!["cli".to_string(), "async".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_api_response_without_topics() {
+        let response: GitHubApiResponse = serde_json::from_str(
+            r#"{"stargazers_count": 1, "description": null, "archived": false,
+                "pushed_at": null, "license": null, "language": "Rust"}"#,
+        )
+        .unwrap();
+        assert!(response.topics.is_empty());
+    }
+
+    #[test]
+    fn test_enterprise_host_repo_urls() {
+        let host = GitHubHost::new("GitHub.Company.com", "https://github.company.com/api/v3/");
+        assert_eq!(host.host, "github.company.com");
+        assert_eq!(host.api_base_url, "https://github.company.com/api/v3");
+
+        assert!(host.is_github_repo("https://github.company.com/team/service"));
+        assert!(host.is_github_repo("git@github.company.com:team/service.git"));
+        assert_eq!(
+            host.parse_repo_from_url("https://github.company.com/team/service/tree/main"),
+            Some(("team".to_string(), "service".to_string()))
+        );
+
+        // Public GitHub and look-alike hosts don't match an enterprise host
+        assert!(!host.is_github_repo("https://github.com/rust-lang/rust"));
+        assert!(!host.is_github_repo("https://github.companyXcom/team/service"));
+        assert!(!host.is_host_url("https://github.company.com.evil.test/team/service"));
+        assert!(host.is_host_url("https://github.company.com/team/service"));
+    }
+
+    #[test]
+    fn test_enterprise_host_replace_repo_in_url() {
+        let host = GitHubHost::new("github.company.com", "https://github.company.com/api/v3");
+        assert_eq!(
+            host.replace_repo_in_url("https://github.company.com/old/name/tree/main", "new/name"),
+            Some("https://github.company.com/new/name/tree/main".to_string())
+        );
+        assert_eq!(
+            host.replace_repo_in_url("https://github.com/old/name", "new/name"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_public_host() {
+        assert_eq!(GitHubHost::public().host, "github.com");
+        assert_eq!(GitHubHost::public().api_base_url, "https://api.github.com");
+    }
 }
@@ -0,0 +1,130 @@
+//! In-process background job queue
+//!
+//! A single worker task drains a bounded `tokio::sync::mpsc` channel so API
+//! handlers can hand off slow external fetches and respond immediately.
+
+use crate::config::Config;
+use crate::services;
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Work item processed by the background worker
+#[derive(Debug, Clone, PartialEq)]
+pub enum Job {
+    /// Scrape/fetch metadata for a link, as `POST /api/links/:id/refresh` does
+    RefreshLink {
+        link_id: Uuid,
+        user_id: Uuid,
+        options: services::RefreshOptions,
+    },
+}
+
+/// Handle for submitting jobs to the background worker
+///
+/// Cheap to clone; all clones feed the same bounded channel.
+#[derive(Clone)]
+pub struct JobQueue {
+    sender: mpsc::Sender<Job>,
+}
+
+impl JobQueue {
+    /// Create the queue and spawn its worker task
+    ///
+    /// The channel holds at most `config.job_queue_capacity` pending jobs.
+    pub fn start(pool: PgPool, config: Config) -> Self {
+        let (queue, receiver) = Self::bounded(config.job_queue_capacity);
+
+        tokio::spawn(run_worker(pool, config, receiver));
+
+        queue
+    }
+
+    fn bounded(capacity: usize) -> (Self, mpsc::Receiver<Job>) {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        (Self { sender }, receiver)
+    }
+
+    /// Submit a job without waiting
+    ///
+    /// Returns `false` if the queue is full or the worker has stopped. Callers
+    /// should treat that as backpressure and skip the work rather than block;
+    /// links that miss their refresh are picked up by the scheduler later.
+    pub fn enqueue(&self, job: Job) -> bool {
+        match self.sender.try_send(job) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(job)) => {
+                tracing::warn!(job = ?job, "Background job queue is full, dropping job");
+                false
+            }
+            Err(mpsc::error::TrySendError::Closed(job)) => {
+                tracing::error!(job = ?job, "Background job worker has stopped, dropping job");
+                false
+            }
+        }
+    }
+}
+
+/// Process jobs one at a time until every sender is dropped
+async fn run_worker(pool: PgPool, config: Config, mut receiver: mpsc::Receiver<Job>) {
+    tracing::info!(
+        capacity = config.job_queue_capacity,
+        "Background job worker started"
+    );
+
+    while let Some(job) = receiver.recv().await {
+        match job {
+            Job::RefreshLink {
+                link_id,
+                user_id,
+                options,
+            } => {
+                if let Err(e) =
+                    services::refresh_link(&pool, &config, link_id, user_id, &options).await
+                {
+                    tracing::warn!(
+                        link_id = %link_id,
+                        error = %e,
+                        "Background link refresh failed"
+                    );
+                }
+            }
+        }
+    }
+
+    tracing::info!("Background job worker stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_enqueue_applies_backpressure_when_full() {
+        let (queue, mut receiver) = JobQueue::bounded(1);
+        let job = Job::RefreshLink {
+            link_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            options: services::RefreshOptions::default(),
+        };
+
+        assert!(queue.enqueue(job.clone()));
+        assert!(!queue.enqueue(job.clone()));
+
+        // Draining frees capacity again
+        assert_eq!(receiver.recv().await, Some(job.clone()));
+        assert!(queue.enqueue(job));
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_fails_when_worker_stopped() {
+        let (queue, receiver) = JobQueue::bounded(4);
+        drop(receiver);
+
+        assert!(!queue.enqueue(Job::RefreshLink {
+            link_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            options: services::RefreshOptions::default(),
+        }));
+    }
+}
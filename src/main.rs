@@ -16,14 +16,20 @@ use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[cfg(feature = "server")]
-async fn initialize_database(database_url: &str) -> Result<PgPool, AppError> {
-    tracing::info!("Initializing database connection pool...");
+async fn initialize_database(config: &config::Config) -> Result<PgPool, AppError> {
+    tracing::info!(
+        test_before_acquire = config.db_test_before_acquire,
+        "Initializing database connection pool..."
+    );
 
+    // Pinging on acquire lets the pool recover from a database restart: dead
+    // connections are dropped and replaced instead of failing the request.
     let pool = PgPoolOptions::new()
         .max_connections(5)
         .acquire_timeout(Duration::from_secs(30))
         .idle_timeout(Duration::from_secs(600))
-        .connect(database_url)
+        .test_before_acquire(config.db_test_before_acquire)
+        .connect(&config.database_url)
         .await?;
 
     tracing::info!("Running database migrations...");
@@ -36,12 +42,17 @@ async fn initialize_database(database_url: &str) -> Result<PgPool, AppError> {
 #[cfg(feature = "server")]
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    // An invalid LOG_FORMAT falls back to pretty here and is reported by
+    // Config::from_env below
+    let registry = tracing_subscriber::registry().with(
+        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+    );
+    match config::LogFormat::from_env().unwrap_or_default() {
+        config::LogFormat::Pretty => registry.with(tracing_subscriber::fmt::layer()).init(),
+        config::LogFormat::Json => registry
+            .with(tracing_subscriber::fmt::layer().json())
+            .init(),
+    }
 
     tracing::info!(
         version = rusty_links::build_info::VERSION,
@@ -51,6 +62,10 @@ async fn main() {
     );
 
     let config = config::Config::from_env().expect("Failed to load configuration");
+    if let Err(e) = config.validate() {
+        tracing::error!("Invalid configuration: {}", e);
+        std::process::exit(1);
+    }
 
     tracing::info!(
         database_url = %config.masked_database_url(),
@@ -58,7 +73,31 @@ async fn main() {
         "Configuration loaded"
     );
 
-    let pool = match initialize_database(&config.database_url).await {
+    if config.allow_invalid_certs {
+        tracing::warn!(
+            "ALLOW_INVALID_CERTS is enabled: TLS certificates are NOT verified for any scraped or health-checked link"
+        );
+    } else if !config.insecure_tls_domains.is_empty() {
+        tracing::warn!(
+            domains = ?config.insecure_tls_domains,
+            "TLS certificate verification is disabled for these domains"
+        );
+    }
+
+    tracing::info!(
+        min_tls_version = %config.min_tls_version,
+        max_outbound_concurrency = config.max_outbound_concurrency,
+        "Outbound request limits"
+    );
+    if config.github.host != "github.com" {
+        tracing::info!(
+            github_host = %config.github.host,
+            github_api_base_url = %config.github.api_base_url,
+            "Using GitHub Enterprise host"
+        );
+    }
+
+    let pool = match initialize_database(&config).await {
         Ok(pool) => pool,
         Err(e) => {
             tracing::error!("Failed to connect to database");
@@ -126,12 +165,18 @@ async fn main() {
         }
     }
 
-    let scheduler_instance = scheduler::Scheduler::new(pool.clone(), config.clone());
+    let events = rusty_links::events::EventBus::new();
+
+    let scheduler_instance =
+        scheduler::Scheduler::new(pool.clone(), config.clone(), events.clone());
     let scheduler_shutdown = scheduler_instance.shutdown_handle();
+    let scheduler_batch_size = scheduler_instance.batch_size_handle();
     let _scheduler_handle = scheduler_instance.start();
 
     tracing::info!("Background scheduler started");
 
+    let jobs = rusty_links::jobs::JobQueue::start(pool.clone(), config.clone());
+
     // Maintenance state and OIDC verifier are cheap to construct and carried in
     // AppState in both modes. They are only exercised in hosted mode (the
     // maintenance webhook flips the flag; the verifier validates at+jwt bearer
@@ -140,17 +185,18 @@ async fn main() {
     let maintenance_message: std::sync::Arc<std::sync::RwLock<Option<String>>> =
         std::sync::Arc::new(std::sync::RwLock::new(None));
 
-    let oidc_verifier = std::sync::Arc::new(rusty_links::auth::oidc_rs::OidcVerifier::new(
-        config.oidc.clone(),
-    ));
+    let oidc_verifier = std::sync::Arc::new(rusty_links::auth::oidc_rs::OidcVerifier::new(&config));
 
     let api_router = api::create_router(
         pool.clone(),
         config.clone(),
         scheduler_shutdown,
+        scheduler_batch_size,
         maintenance_mode.clone(),
         maintenance_message.clone(),
         oidc_verifier.clone(),
+        jobs,
+        events,
     );
 
     let ip = dioxus::cli_config::server_ip()
@@ -164,7 +210,10 @@ async fn main() {
         address
     );
 
-    let mut dioxus_router = axum::Router::new().serve_dioxus_application(ServeConfig::new(), App);
+    // Server functions read the configuration from the request extensions
+    let mut dioxus_router = axum::Router::new()
+        .serve_dioxus_application(ServeConfig::new(), App)
+        .layer(axum::Extension(config.clone()));
 
     // In hosted mode, protect page routes: check `rl_session` cookie and redirect
     // unauthenticated users to the OIDC login handler.
@@ -223,7 +272,9 @@ async fn main() {
         ));
     }
 
-    let mut router = axum::Router::new().nest("/api", api_router);
+    let mut router = axum::Router::new()
+        .nest("/api", api_router)
+        .merge(api::openapi::swagger_ui());
 
     // In hosted mode, merge the OIDC RP routes at root level (before the
     // dioxus router) so the `/oauth2/*` BFF endpoints are reachable.
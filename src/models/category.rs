@@ -2,14 +2,19 @@
 //!
 //! This module handles category management with hierarchical support (3 levels max).
 
+use super::{normalize_name, unique_names};
 use crate::error::AppError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+/// Levels of nesting the schema allows (depth 0 to 2)
+pub const MAX_CATEGORY_LEVELS: usize = 3;
+
 /// Category entity
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct Category {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -42,6 +47,7 @@ impl Category {
         user_id: Uuid,
         create: CreateCategory,
     ) -> Result<Category, AppError> {
+        let name = validated_name(&create.name)?;
         let (depth, parent_id) = if let Some(pid) = create.parent_id {
             // Validate parent exists and belongs to user
             let parent = sqlx::query_as::<_, Category>(
@@ -74,7 +80,7 @@ impl Category {
             "#,
         )
         .bind(user_id)
-        .bind(&create.name)
+        .bind(&name)
         .bind(parent_id)
         .bind(depth)
         .fetch_one(pool)
@@ -96,9 +102,12 @@ impl Category {
     }
 
     /// Get all categories for a user (flat list)
+    ///
+    /// Ordered by depth, then manual `sort_order` (unordered categories last),
+    /// then name.
     pub async fn get_all_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Category>, AppError> {
         let categories = sqlx::query_as::<_, Category>(
-            "SELECT * FROM categories WHERE user_id = $1 ORDER BY depth, name",
+            "SELECT * FROM categories WHERE user_id = $1 ORDER BY depth, sort_order NULLS LAST, name",
         )
         .bind(user_id)
         .fetch_all(pool)
@@ -107,41 +116,224 @@ impl Category {
         Ok(categories)
     }
 
-    /// Get category by name, or create it if it doesn't exist
-    pub async fn get_or_create_by_name(
+    /// Get a user's categories with no links and no subcategories
+    ///
+    /// Sorted like `get_all_by_user`.
+    pub async fn get_orphaned(pool: &PgPool, user_id: Uuid) -> Result<Vec<Category>, AppError> {
+        let categories = sqlx::query_as::<_, Category>(
+            r#"
+            SELECT * FROM categories c
+            WHERE c.user_id = $1
+              AND NOT EXISTS (SELECT 1 FROM link_categories lc WHERE lc.category_id = c.id)
+              AND NOT EXISTS (SELECT 1 FROM categories child WHERE child.parent_id = c.id)
+            ORDER BY c.depth, c.sort_order NULLS LAST, c.name
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(categories)
+    }
+
+    /// Find a user's category by name, ignoring case
+    pub async fn find_by_name(
         pool: &PgPool,
         user_id: Uuid,
         name: &str,
-    ) -> Result<Category, AppError> {
-        // Try to find existing category with this name
-        let existing = sqlx::query_as::<_, Category>(
-            "SELECT * FROM categories WHERE user_id = $1 AND name = $2",
+    ) -> Result<Option<Category>, AppError> {
+        let category = sqlx::query_as::<_, Category>(
+            "SELECT * FROM categories WHERE user_id = $1 AND LOWER(name) = LOWER($2)",
         )
         .bind(user_id)
         .bind(name)
         .fetch_optional(pool)
         .await?;
 
-        if let Some(category) = existing {
+        Ok(category)
+    }
+
+    /// Get category by name (case-insensitive), or create it if it doesn't exist
+    ///
+    /// The name is trimmed and inner whitespace collapsed first. A category
+    /// created concurrently under another case is returned rather than
+    /// failing on the unique index.
+    pub async fn get_or_create_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Category, AppError> {
+        let name = validated_name(name)?;
+        let name = name.as_str();
+        if let Some(category) = Self::find_by_name(pool, user_id, name).await? {
             return Ok(category);
         }
 
         // Create new category (top-level, no parent)
-        let category = sqlx::query_as::<_, Category>(
+        let created = sqlx::query_as::<_, Category>(
             r#"
             INSERT INTO categories (user_id, name, parent_id, depth)
             VALUES ($1, $2, NULL, 0)
+            ON CONFLICT (user_id, LOWER(name)) DO NOTHING
             RETURNING *
             "#,
         )
         .bind(user_id)
         .bind(name)
-        .fetch_one(pool)
+        .fetch_optional(pool)
         .await?;
 
-        tracing::info!(category_id = %category.id, name = %category.name, "Category created via import");
+        match created {
+            Some(category) => {
+                tracing::info!(category_id = %category.id, name = %category.name, "Category created via import");
+                Ok(category)
+            }
+            None => Self::find_by_name(pool, user_id, name)
+                .await?
+                .ok_or_else(|| AppError::not_found("category", name)),
+        }
+    }
 
-        Ok(category)
+    /// Get or create top-level categories for many names at once
+    ///
+    /// Existing categories are fetched in one query and the missing ones
+    /// created in a single insert. The map is keyed by lowercased name,
+    /// matching the case-insensitive lookup of `get_or_create_by_name`; blank
+    /// names are skipped.
+    pub async fn get_or_create_many(
+        pool: &PgPool,
+        user_id: Uuid,
+        names: &[String],
+    ) -> Result<HashMap<String, Category>, AppError> {
+        let wanted = unique_names(names);
+        let keys: Vec<String> = wanted.keys().cloned().collect();
+
+        let mut categories = HashMap::new();
+        for category in Self::find_many_by_name(pool, user_id, &keys).await? {
+            categories.insert(category.name.to_lowercase(), category);
+        }
+
+        let missing: Vec<String> = wanted
+            .iter()
+            .filter(|(key, _)| !categories.contains_key(*key))
+            .map(|(_, name)| name.clone())
+            .collect();
+        if missing.is_empty() {
+            return Ok(categories);
+        }
+
+        let created = sqlx::query_as::<_, Category>(
+            r#"
+            INSERT INTO categories (user_id, name, parent_id, depth)
+            SELECT $1, UNNEST($2::text[]), NULL, 0
+            ON CONFLICT (user_id, LOWER(name)) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(&missing)
+        .fetch_all(pool)
+        .await?;
+        tracing::info!(count = created.len(), "Categories created via import");
+        for category in created {
+            categories.insert(category.name.to_lowercase(), category);
+        }
+
+        // Names created concurrently under another case were skipped above
+        let raced: Vec<String> = missing
+            .iter()
+            .map(|name| name.to_lowercase())
+            .filter(|key| !categories.contains_key(key))
+            .collect();
+        if !raced.is_empty() {
+            for category in Self::find_many_by_name(pool, user_id, &raced).await? {
+                categories.insert(category.name.to_lowercase(), category);
+            }
+        }
+
+        Ok(categories)
+    }
+
+    /// The user's categories whose lowercased name is in `keys`
+    async fn find_many_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        keys: &[String],
+    ) -> Result<Vec<Category>, AppError> {
+        let categories = sqlx::query_as::<_, Category>(
+            "SELECT * FROM categories WHERE user_id = $1 AND LOWER(name) = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(keys)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(categories)
+    }
+
+    /// Get or create the chain of categories named by `segments`, root first
+    ///
+    /// Each level reuses the user's category of that name when it already sits
+    /// under the previous level (or at the top, for the first), and creates it
+    /// otherwise. Category names are unique per user, so a name that exists
+    /// elsewhere in the tree is rejected rather than duplicated. Paths with
+    /// more than `max_depth` levels are rejected before anything is created.
+    ///
+    /// # Returns
+    /// The innermost category
+    pub async fn create_path(
+        pool: &PgPool,
+        user_id: Uuid,
+        segments: &[String],
+        max_depth: usize,
+    ) -> Result<Category, AppError> {
+        let path = segments.join("/");
+        if segments.is_empty() || segments.iter().any(|s| s.trim().is_empty()) {
+            return Err(AppError::validation(
+                "categories",
+                &format!("Invalid category path '{}'", path),
+            ));
+        }
+        let max_depth = max_depth.min(MAX_CATEGORY_LEVELS);
+        if segments.len() > max_depth {
+            return Err(AppError::validation(
+                "categories",
+                &format!(
+                    "Category path '{}' has {} levels (maximum {})",
+                    path,
+                    segments.len(),
+                    max_depth
+                ),
+            ));
+        }
+
+        let mut parent: Option<Category> = None;
+        for name in segments {
+            let name = name.trim();
+            let parent_id = parent.as_ref().map(|p| p.id);
+            let category = match Self::find_by_name(pool, user_id, name).await? {
+                Some(existing) if existing.parent_id == parent_id => existing,
+                Some(existing) => {
+                    return Err(AppError::validation(
+                        "categories",
+                        &format!(
+                            "Category '{}' already exists elsewhere, so '{}' can't be created",
+                            existing.name, path
+                        ),
+                    ));
+                }
+                None => {
+                    let create = CreateCategory {
+                        name: name.to_string(),
+                        parent_id,
+                    };
+                    Self::create(pool, user_id, create).await?
+                }
+            };
+            parent = Some(category);
+        }
+
+        parent.ok_or_else(|| AppError::validation("categories", "Empty category path"))
     }
 
     /// Get categories as a hierarchical tree
@@ -154,12 +346,15 @@ impl Category {
     }
 
     /// Update category name
+    ///
+    /// The name is trimmed and inner whitespace collapsed, as on create.
     pub async fn update(
         pool: &PgPool,
         id: Uuid,
         user_id: Uuid,
         name: &str,
     ) -> Result<Category, AppError> {
+        let name = validated_name(name)?;
         let category = sqlx::query_as::<_, Category>(
             r#"
             UPDATE categories
@@ -170,7 +365,7 @@ impl Category {
         )
         .bind(id)
         .bind(user_id)
-        .bind(name)
+        .bind(&name)
         .fetch_optional(pool)
         .await?
         .ok_or_else(|| AppError::not_found("category", &id.to_string()))?;
@@ -180,6 +375,48 @@ impl Category {
         Ok(category)
     }
 
+    /// Set the manual sort order of categories
+    ///
+    /// `ids` lists categories in their new order; each gets its position in
+    /// the list as `sort_order`. Categories not listed keep their position.
+    /// All positions are written in one transaction, and nothing changes if
+    /// any id isn't one of the user's categories.
+    pub async fn reorder(pool: &PgPool, user_id: Uuid, ids: &[Uuid]) -> Result<(), AppError> {
+        validate_reorder_ids(ids)?;
+
+        let mut tx = pool.begin().await?;
+
+        let owned: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM categories WHERE user_id = $1 AND id = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(ids)
+        .fetch_one(&mut *tx)
+        .await?;
+        if owned != ids.len() as i64 {
+            return Err(AppError::validation("ids", "Unknown category id"));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE categories c
+            SET sort_order = v.position::int
+            FROM UNNEST($2::uuid[]) WITH ORDINALITY AS v(id, position)
+            WHERE c.id = v.id AND c.user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        tracing::info!(user_id = %user_id, count = ids.len(), "Categories reordered");
+
+        Ok(())
+    }
+
     /// Delete a category and its children (cascade)
     pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<(), AppError> {
         // First verify the category exists and belongs to user
@@ -206,6 +443,45 @@ impl Category {
     }
 }
 
+/// Split an imported category name into path segments
+///
+/// `Programming / Rust` becomes `["Programming", "Rust"]`, each segment
+/// normalized like a category name. Blank segments (from `A//B` or a trailing
+/// slash) are dropped, so a name without a slash yields a single segment.
+pub fn parse_category_path(name: &str) -> Vec<String> {
+    name.split('/')
+        .map(normalize_name)
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Normalize a category name, rejecting names left blank
+fn validated_name(name: &str) -> Result<String, AppError> {
+    let name = normalize_name(name);
+    if name.is_empty() {
+        return Err(AppError::validation(
+            "name",
+            "Category name cannot be empty",
+        ));
+    }
+    Ok(name)
+}
+
+/// Reject empty reorder requests and repeated ids
+fn validate_reorder_ids(ids: &[Uuid]) -> Result<(), AppError> {
+    if ids.is_empty() {
+        return Err(AppError::validation(
+            "ids",
+            "At least one category id is required",
+        ));
+    }
+    let mut seen = HashSet::new();
+    if !ids.iter().all(|id| seen.insert(id)) {
+        return Err(AppError::validation("ids", "Category ids must be unique"));
+    }
+    Ok(())
+}
+
 /// Build a hierarchical tree from a flat list of categories
 fn build_category_tree(categories: Vec<Category>) -> Vec<CategoryWithChildren> {
     let mut root_categories: Vec<CategoryWithChildren> = Vec::new();
@@ -332,6 +608,38 @@ mod tests {
         assert_eq!(tree[0].children.len(), 3);
     }
 
+    #[test]
+    fn test_parse_category_path() {
+        assert_eq!(
+            parse_category_path("Programming / Rust/Web"),
+            vec!["Programming", "Rust", "Web"]
+        );
+        assert_eq!(parse_category_path("Rust"), vec!["Rust"]);
+        assert_eq!(parse_category_path("A//B/"), vec!["A", "B"]);
+        assert!(parse_category_path(" / ").is_empty());
+        assert_eq!(
+            parse_category_path("Web   Dev / Tools"),
+            vec!["Web Dev", "Tools"]
+        );
+    }
+
+    #[test]
+    fn test_validated_name() {
+        assert_eq!(
+            validated_name("  Side  Projects ").unwrap(),
+            "Side Projects"
+        );
+        assert!(validated_name(" \t ").is_err());
+    }
+
+    #[test]
+    fn test_validate_reorder_ids() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(validate_reorder_ids(&[a, b]).is_ok());
+        assert!(validate_reorder_ids(&[]).is_err());
+        assert!(validate_reorder_ids(&[a, b, a]).is_err());
+    }
+
     #[test]
     fn test_build_children_no_matches() {
         let user_id = Uuid::new_v4();
@@ -0,0 +1,127 @@
+//! Idempotency key model and database operations
+
+use crate::error::AppError;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// How long a key replays its original result
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+/// Maximum accepted length of an `Idempotency-Key` header value
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Client-supplied key identifying a link creation request
+/// (`idempotency_keys` table)
+pub struct IdempotencyKey;
+
+/// Outcome of `IdempotencyKey::reserve`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotencyReservation {
+    /// The key is new (or expired) and now belongs to this request
+    Reserved,
+    /// An earlier request with this key created the link
+    Completed(Uuid),
+    /// An earlier request with this key is still creating its link
+    Pending,
+}
+
+impl IdempotencyKey {
+    /// Claim a key before creating the link it guards
+    ///
+    /// Inserts the key with no link, so a concurrent request with the same
+    /// key sees it as `Pending` instead of creating a second link. An expired
+    /// entry is reclaimed. Keys older than `IDEMPOTENCY_KEY_TTL_HOURS` are
+    /// ignored.
+    pub async fn reserve(
+        pool: &PgPool,
+        user_id: Uuid,
+        key: &str,
+    ) -> Result<IdempotencyReservation, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (user_id, key, link_id)
+            VALUES ($1, $2, NULL)
+            ON CONFLICT (user_id, key) DO UPDATE
+            SET link_id = NULL, created_at = NOW()
+            WHERE idempotency_keys.created_at <= NOW() - make_interval(hours => $3)
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(IDEMPOTENCY_KEY_TTL_HOURS as i32)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() > 0 {
+            return Ok(IdempotencyReservation::Reserved);
+        }
+
+        let link_id = sqlx::query_scalar::<_, Option<Uuid>>(
+            "SELECT link_id FROM idempotency_keys WHERE user_id = $1 AND key = $2",
+        )
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+        Ok(match link_id {
+            Some(link_id) => IdempotencyReservation::Completed(link_id),
+            None => IdempotencyReservation::Pending,
+        })
+    }
+
+    /// Record the link created for a reserved key
+    ///
+    /// Only a pending reservation is filled in, so the first link recorded
+    /// for a key wins.
+    pub async fn complete(
+        pool: &PgPool,
+        user_id: Uuid,
+        key: &str,
+        link_id: Uuid,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys SET link_id = $3
+            WHERE user_id = $1 AND key = $2 AND link_id IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(link_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drop a pending reservation whose link could not be created
+    ///
+    /// Lets the client retry with the same key.
+    pub async fn release(pool: &PgPool, user_id: Uuid, key: &str) -> Result<(), AppError> {
+        sqlx::query(
+            "DELETE FROM idempotency_keys WHERE user_id = $1 AND key = $2 AND link_id IS NULL",
+        )
+        .bind(user_id)
+        .bind(key)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete keys past the replay window
+    ///
+    /// Returns the number of keys removed.
+    pub async fn delete_expired(pool: &PgPool) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "DELETE FROM idempotency_keys WHERE created_at <= NOW() - make_interval(hours => $1)",
+        )
+        .bind(IDEMPOTENCY_KEY_TTL_HOURS as i32)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
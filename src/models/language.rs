@@ -7,7 +7,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 /// Language entity
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct Language {
     pub id: Uuid,
     pub name: String,
@@ -66,6 +66,69 @@ impl Language {
         Ok(language)
     }
 
+    /// Find a global or user-owned language by name, ignoring case
+    ///
+    /// Global languages take precedence over the user's custom ones.
+    pub async fn find_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Language>, AppError> {
+        let language = sqlx::query_as::<_, Language>(
+            r#"
+            SELECT * FROM languages
+            WHERE (user_id IS NULL OR user_id = $1) AND LOWER(name) = LOWER($2)
+            ORDER BY user_id NULLS FIRST
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(language)
+    }
+
+    /// Get language by name (case-insensitive), or create it if it doesn't exist
+    ///
+    /// Global languages take precedence over the user's custom ones, so
+    /// "rust" resolves to the seeded "Rust" row rather than a duplicate. A
+    /// language created concurrently under another case is returned rather
+    /// than failing on the unique index.
+    pub async fn get_or_create_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Language, AppError> {
+        if let Some(language) = Self::find_by_name(pool, user_id, name).await? {
+            return Ok(language);
+        }
+
+        let created = sqlx::query_as::<_, Language>(
+            r#"
+            INSERT INTO languages (user_id, name)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id, LOWER(name)) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        match created {
+            Some(language) => {
+                tracing::info!(language_id = %language.id, name = %language.name, "Language created");
+                Ok(language)
+            }
+            None => Self::find_by_name(pool, user_id, name)
+                .await?
+                .ok_or_else(|| AppError::not_found("language", name)),
+        }
+    }
+
     /// Delete a user-created language (cannot delete global languages)
     pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<(), AppError> {
         let result = sqlx::query("DELETE FROM languages WHERE id = $1 AND user_id = $2")
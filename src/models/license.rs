@@ -7,7 +7,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 /// License entity
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct License {
     pub id: Uuid,
     pub user_id: Option<Uuid>,
@@ -70,6 +70,69 @@ impl License {
         Ok(license)
     }
 
+    /// Find a global or user-owned license by name, ignoring case
+    ///
+    /// Global licenses take precedence over the user's custom ones.
+    pub async fn find_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Option<License>, AppError> {
+        let license = sqlx::query_as::<_, License>(
+            r#"
+            SELECT * FROM licenses
+            WHERE (user_id IS NULL OR user_id = $1) AND LOWER(name) = LOWER($2)
+            ORDER BY user_id NULLS FIRST
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(license)
+    }
+
+    /// Get license by name (case-insensitive), or create it if it doesn't exist
+    ///
+    /// "mit" resolves to the seeded "MIT" row rather than a duplicate. New
+    /// licenses use the name as their full name. A license created
+    /// concurrently under another case is returned rather than failing on the
+    /// unique index.
+    pub async fn get_or_create_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<License, AppError> {
+        if let Some(license) = Self::find_by_name(pool, user_id, name).await? {
+            return Ok(license);
+        }
+
+        let created = sqlx::query_as::<_, License>(
+            r#"
+            INSERT INTO licenses (user_id, name, full_name)
+            VALUES ($1, $2, $2)
+            ON CONFLICT (user_id, LOWER(name)) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        match created {
+            Some(license) => {
+                tracing::info!(license_id = %license.id, name = %license.name, "License created");
+                Ok(license)
+            }
+            None => Self::find_by_name(pool, user_id, name)
+                .await?
+                .ok_or_else(|| AppError::not_found("license", name)),
+        }
+    }
+
     /// Delete a user-created license (cannot delete global licenses)
     pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<(), AppError> {
         let result = sqlx::query("DELETE FROM licenses WHERE id = $1 AND user_id = $2")
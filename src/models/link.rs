@@ -7,24 +7,48 @@
 //!
 //! Links are scoped to users - each user can only access their own links.
 
+use crate::config::Config;
 use crate::error::AppError;
 use crate::models::{Category, Language, License, Tag};
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use url::Url;
 use uuid::Uuid;
 
+/// Score per category shared with the link in `Link::find_similar`
+const SIMILAR_CATEGORY_WEIGHT: i64 = 3;
+/// Score per tag shared with the link in `Link::find_similar`
+const SIMILAR_TAG_WEIGHT: i64 = 2;
+/// Score for being on the same domain in `Link::find_similar`
+const SIMILAR_DOMAIN_BONUS: i64 = 1;
+
+/// Every status a link can have (mirrors `links_status_check`)
+pub const LINK_STATUSES: [&str; 6] = [
+    "active",
+    "archived",
+    "inaccessible",
+    "repo_unavailable",
+    "private_repo",
+    "repo_takedown",
+];
+
+/// Statuses of links that stopped working, as reported by `recently_failed`
+pub const FAILED_STATUSES: [&str; 3] = ["inaccessible", "repo_unavailable", "repo_takedown"];
+
 /// Link entity
 ///
 /// Represents a bookmarked link with metadata.
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct Link {
     pub id: Uuid,
     pub user_id: Uuid,
     pub url: String,
+    /// ASCII (punycode) host, used for filtering and grouping
     pub domain: String,
+    /// Unicode host for display, e.g. "münchen.de"
+    pub domain_display: Option<String>,
     pub path: Option<String>,
     pub title: Option<String>,
     pub description: Option<String>,
@@ -36,12 +60,33 @@ pub struct Link {
     pub github_stars: Option<i32>,
     pub github_archived: Option<bool>,
     pub github_last_commit: Option<NaiveDate>,
+    pub github_owner_type: Option<String>,
     pub status: String,
     pub consecutive_failures: i32,
     pub refreshed_at: Option<DateTime<Utc>>,
     pub last_checked: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Where the URL ended up after following redirects, if it redirected
+    pub final_url: Option<String>,
+    /// Previous URL, if the GitHub repository was renamed or transferred
+    pub renamed_from: Option<String>,
+    /// When GitHub metadata was last fetched
+    pub github_refreshed_at: Option<DateTime<Utc>>,
+    /// Pinned links sort ahead of all others
+    pub is_pinned: bool,
+    /// When the user last opened the link
+    pub last_visited_at: Option<DateTime<Utc>>,
+    /// The user changed the title; refreshes keep it unless forced
+    pub title_manually_edited: bool,
+    /// `ETag` of the page as last scraped, for conditional re-fetches
+    #[serde(skip)]
+    pub http_etag: Option<String>,
+    /// `Last-Modified` of the page as last scraped, for conditional re-fetches
+    #[serde(skip)]
+    pub http_last_modified: Option<String>,
+    /// When `status` last changed, if it has since this was tracked
+    pub status_changed_at: Option<DateTime<Utc>>,
 }
 
 /// Data for creating a new link
@@ -51,10 +96,12 @@ pub struct CreateLink {
     pub title: Option<String>,
     pub description: Option<String>,
     pub logo: Option<String>,
+    #[serde(default)]
+    pub final_url: Option<String>,
 }
 
 /// Data for updating a link
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, utoipa::ToSchema)]
 pub struct UpdateLink {
     pub url: Option<String>,
     pub title: Option<String>,
@@ -71,19 +118,52 @@ pub struct UpdateLink {
 }
 
 /// Search parameters for filtering links
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Default, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LinkSearchParams {
     pub query: Option<String>, // Text search in title, description, url, domain
     pub status: Option<String>, // Filter by status
     pub is_github: Option<bool>, // Filter GitHub repos only
+    pub is_pinned: Option<bool>, // Filter by pinned state
     pub category_id: Option<Uuid>, // Filter by category
     pub tag_id: Option<Uuid>,  // Filter by tag
     pub language_id: Option<Uuid>, // Filter by programming language
     pub license_id: Option<Uuid>, // Filter by software license
     pub sort_by: Option<String>, // Sort field: created_at, title, github_stars, status, updated_at
-    pub sort_order: Option<String>, // Sort order: asc, desc (default: desc)
+    pub sort_order: Option<String>, // Sort order: asc, desc (default depends on sort_by)
     pub page: Option<u32>,     // Page number (1-indexed)
     pub per_page: Option<u32>, // Items per page (default: 20, max: 100)
+    pub highlight: Option<bool>, // Report which fields matched `query` (default: false)
+    pub created_after: Option<DateTime<Utc>>, // Created at or after this time (RFC 3339)
+    pub created_before: Option<DateTime<Utc>>, // Created strictly before this time (RFC 3339)
+}
+
+/// Number of links a user added in one calendar month (UTC)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct TimelineMonth {
+    pub year: i32,
+    /// 1-12
+    pub month: i32,
+    pub count: i64,
+}
+
+/// Which fields of a link matched a text search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct SearchMatches {
+    pub title: bool,
+    pub description: bool,
+    pub url: bool,
+    pub domain: bool,
+}
+
+/// A link's id with its per-field search match flags
+#[derive(Debug, sqlx::FromRow)]
+struct SearchMatchRow {
+    id: Uuid,
+    title_match: bool,
+    description_match: bool,
+    url_match: bool,
+    domain_match: bool,
 }
 
 /// Paginated links response
@@ -96,41 +176,137 @@ pub struct PaginatedLinks {
     pub total_pages: u32,
 }
 
-impl Link {
-    /// Create a new link
+/// Build the ORDER BY clause for link searches
+///
+/// The sort field and direction come from a fixed allowlist to prevent SQL
+/// injection. `l.id` breaks ties so rows with equal sort values (e.g. the same
+/// star count) keep a stable order across pages.
+///
+/// Without an explicit `sort_order`, text fields (title, status) sort
+/// ascending and dates and star counts descending. Pinned links always come
+/// first, whatever the sort.
+fn order_by_clause(params: &LinkSearchParams) -> String {
+    let (sort_field, default_order) = match params.sort_by.as_deref() {
+        Some("title") => ("LOWER(l.title)", "ASC"),
+        Some("github_stars") => ("l.github_stars", "DESC"),
+        Some("status") => ("l.status", "ASC"),
+        Some("updated_at") => ("l.updated_at", "DESC"),
+        _ => ("l.created_at", "DESC"), // default
+    };
+
+    let sort_order = match params.sort_order.as_deref() {
+        Some("asc") => "ASC",
+        Some("desc") => "DESC",
+        _ => default_order,
+    };
+
+    format!(
+        "ORDER BY l.is_pinned DESC, {} {} NULLS LAST, l.id ASC",
+        sort_field, sort_order
+    )
+}
+
+/// Domain and path columns a link stores for its URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlParts {
+    pub domain: String,
+    pub domain_display: String,
+    pub path: Option<String>,
+}
+
+impl UrlParts {
+    /// Derive the parts from a URL with the configured `www.` and fragment
+    /// handling
     ///
-    /// Parses the URL to extract domain and path, then inserts into the database.
-    pub async fn create(
-        pool: &PgPool,
-        user_id: Uuid,
-        create_link: CreateLink,
-    ) -> Result<Link, AppError> {
-        // Parse and validate URL
-        let parsed_url = Url::parse(&create_link.url)
+    /// `None` if the URL doesn't parse or has no host.
+    pub fn from_url(url: &str, config: &Config) -> Option<Self> {
+        let parsed = Url::parse(url).ok()?;
+        let (domain, domain_display) = normalize_host(&parsed, config.strip_www)?;
+        Some(Self {
+            domain,
+            domain_display,
+            path: link_path(&parsed, config.strip_fragment),
+        })
+    }
+}
+
+/// A link's URL with the domain and path stored for it, for maintenance tasks
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StoredUrlParts {
+    pub id: Uuid,
+    pub url: String,
+    pub domain: String,
+    pub domain_display: Option<String>,
+    pub path: Option<String>,
+}
+
+impl StoredUrlParts {
+    /// Whether the stored columns already match `parts`
+    pub fn matches(&self, parts: &UrlParts) -> bool {
+        self.domain == parts.domain
+            && self.domain_display.as_deref() == Some(parts.domain_display.as_str())
+            && self.path == parts.path
+    }
+}
+
+/// Fields of `links` derived from the URL itself
+struct LinkUrlParts {
+    domain: String,
+    domain_display: String,
+    path: Option<String>,
+    is_github_repo: bool,
+}
+
+impl LinkUrlParts {
+    /// Parse and validate a link URL, extracting domain and path
+    fn parse(url: &str, config: &Config) -> Result<Self, AppError> {
+        let parsed_url = Url::parse(url)
             .map_err(|e| AppError::validation("url", &format!("Invalid URL: {}", e)))?;
+        crate::security::validate_url_scheme(&parsed_url, &config.allowed_url_schemes)?;
 
-        // Extract domain and path
-        let domain = parsed_url
-            .host_str()
-            .ok_or_else(|| AppError::validation("url", "URL must have a domain"))?
-            .to_string();
+        let (domain, domain_display) = normalize_host(&parsed_url, config.strip_www)
+            .ok_or_else(|| AppError::validation("url", "URL must have a domain"))?;
 
-        let path = {
-            let p = parsed_url.path();
-            if p.is_empty() || p == "/" {
-                None
-            } else {
-                Some(p.to_string())
-            }
-        };
+        let path = link_path(&parsed_url, config.strip_fragment);
 
         // Check if it's a GitHub repo
-        let is_github_repo = domain == "github.com"
+        let is_github_repo = domain == config.github.host
             && path.as_ref().map_or(false, |p| {
                 let parts: Vec<&str> = p.trim_matches('/').split('/').collect();
                 parts.len() >= 2
             });
 
+        Ok(Self {
+            domain,
+            domain_display,
+            path,
+            is_github_repo,
+        })
+    }
+}
+
+impl Link {
+    /// Check a URL would be accepted by `create`, without saving anything
+    pub fn validate_url(url: &str, config: &Config) -> Result<(), AppError> {
+        LinkUrlParts::parse(url, config).map(|_| ())
+    }
+
+    /// Create a new link
+    ///
+    /// Parses the URL to extract domain and path, then inserts into the database.
+    pub async fn create(
+        pool: &PgPool,
+        config: &Config,
+        user_id: Uuid,
+        create_link: CreateLink,
+    ) -> Result<Link, AppError> {
+        let LinkUrlParts {
+            domain,
+            domain_display,
+            path,
+            is_github_repo,
+        } = LinkUrlParts::parse(&create_link.url, config)?;
+
         tracing::info!(
             user_id = %user_id,
             url = %create_link.url,
@@ -140,8 +316,8 @@ impl Link {
 
         let link = sqlx::query_as::<_, Link>(
             r#"
-            INSERT INTO links (user_id, url, domain, path, title, description, logo, is_github_repo)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO links (user_id, url, domain, path, title, description, logo, is_github_repo, final_url, domain_display)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             RETURNING *
             "#,
         )
@@ -153,6 +329,8 @@ impl Link {
         .bind(&create_link.description)
         .bind(&create_link.logo)
         .bind(is_github_repo)
+        .bind(&create_link.final_url)
+        .bind(&domain_display)
         .fetch_one(pool)
         .await?;
 
@@ -161,6 +339,87 @@ impl Link {
         Ok(link)
     }
 
+    /// Copy a link to a new URL
+    ///
+    /// The copy keeps the title, description, logo, notes and documentation
+    /// links along with all categories, tags, languages and licenses. Remote
+    /// metadata (GitHub stats, check status) starts fresh since it belongs to
+    /// the original URL. Runs in a single transaction.
+    pub async fn duplicate(
+        pool: &PgPool,
+        config: &Config,
+        id: Uuid,
+        user_id: Uuid,
+        new_url: &str,
+    ) -> Result<Link, AppError> {
+        let LinkUrlParts {
+            domain,
+            domain_display,
+            path,
+            is_github_repo,
+        } = LinkUrlParts::parse(new_url, config)?;
+
+        let mut tx = pool.begin().await?;
+
+        let link = sqlx::query_as::<_, Link>(
+            r#"
+            INSERT INTO links (
+                user_id, url, domain, path, title, description, logo,
+                source_code_url, documentation_url, notes, is_github_repo, domain_display
+            )
+            SELECT user_id, $3, $4, $5, title, description, logo,
+                source_code_url, documentation_url, notes, $6, $7
+            FROM links
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(new_url)
+        .bind(&domain)
+        .bind(&path)
+        .bind(is_github_repo)
+        .bind(&domain_display)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::not_found("link", &id.to_string()))?;
+
+        sqlx::query(
+            "INSERT INTO link_categories (link_id, category_id)
+             SELECT $2, category_id FROM link_categories WHERE link_id = $1",
+        )
+        .bind(id)
+        .bind(link.id)
+        .execute(&mut *tx)
+        .await?;
+
+        for (table, column) in [
+            ("link_tags", "tag_id"),
+            ("link_languages", "language_id"),
+            ("link_licenses", "license_id"),
+        ] {
+            sqlx::query(&format!(
+                "INSERT INTO {table} (link_id, {column}, order_num)
+                 SELECT $2, {column}, order_num FROM {table} WHERE link_id = $1"
+            ))
+            .bind(id)
+            .bind(link.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        tracing::info!(
+            source_link_id = %id,
+            link_id = %link.id,
+            "Link duplicated"
+        );
+
+        Ok(link)
+    }
+
     /// Get a link by ID (must belong to user)
     pub async fn get_by_id(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Link, AppError> {
         let link = sqlx::query_as::<_, Link>(
@@ -178,15 +437,49 @@ impl Link {
         Ok(link)
     }
 
+    /// Get the domains of the user's links among `link_ids`
+    ///
+    /// Ids that don't exist or belong to another user are absent from the map.
+    pub async fn get_domains(
+        pool: &PgPool,
+        user_id: Uuid,
+        link_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, String>, AppError> {
+        let rows: Vec<(Uuid, String)> = sqlx::query_as(
+            r#"
+            SELECT id, domain FROM links
+            WHERE id = ANY($1) AND user_id = $2
+            "#,
+        )
+        .bind(link_ids)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
     /// Check if a link with the given URL exists for the user
-    pub async fn exists_by_url(pool: &PgPool, user_id: Uuid, url: &str) -> Result<bool, AppError> {
+    pub async fn exists_by_url(
+        pool: &PgPool,
+        config: &Config,
+        user_id: Uuid,
+        url: &str,
+    ) -> Result<bool, AppError> {
+        let (domain, path) = normalize_url(url, config).unzip();
         let exists = sqlx::query_scalar::<_, bool>(
             r#"
-            SELECT EXISTS(SELECT 1 FROM links WHERE user_id = $1 AND url = $2)
+            SELECT EXISTS(
+                SELECT 1 FROM links
+                WHERE user_id = $1
+                  AND (url = $2 OR (domain = $3 AND path IS NOT DISTINCT FROM $4))
+            )
             "#,
         )
         .bind(user_id)
         .bind(url)
+        .bind(domain)
+        .bind(path.flatten())
         .fetch_one(pool)
         .await?;
 
@@ -194,24 +487,49 @@ impl Link {
     }
 
     /// Find a link by URL for a user, returns None if not found
+    ///
+    /// Matches on the stored `(domain, path)` as well as the exact URL, so
+    /// `www.` and scheme variants of a saved link are found too.
     pub async fn find_by_url(
         pool: &PgPool,
+        config: &Config,
         user_id: Uuid,
         url: &str,
     ) -> Result<Option<Link>, AppError> {
+        let (domain, path) = normalize_url(url, config).unzip();
         let link = sqlx::query_as::<_, Link>(
             r#"
-            SELECT * FROM links WHERE user_id = $1 AND url = $2
+            SELECT * FROM links
+            WHERE user_id = $1
+              AND (url = $2 OR (domain = $3 AND path IS NOT DISTINCT FROM $4))
+            ORDER BY (url = $2) DESC
+            LIMIT 1
             "#,
         )
         .bind(user_id)
         .bind(url)
+        .bind(domain)
+        .bind(path.flatten())
         .fetch_optional(pool)
         .await?;
 
         Ok(link)
     }
 
+    /// Get a link by URL for a user, matching as `find_by_url` does
+    ///
+    /// Fails with `NotFound` when the user hasn't saved the URL.
+    pub async fn get_by_normalized_url(
+        pool: &PgPool,
+        config: &Config,
+        user_id: Uuid,
+        url: &str,
+    ) -> Result<Link, AppError> {
+        Self::find_by_url(pool, config, user_id, url)
+            .await?
+            .ok_or_else(|| AppError::not_found("link", url))
+    }
+
     /// Get all links for a user
     pub async fn get_all_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Link>, AppError> {
         let links = sqlx::query_as::<_, Link>(
@@ -228,6 +546,29 @@ impl Link {
         Ok(links)
     }
 
+    /// Get a user's links updated after `since`, newest first
+    ///
+    /// Used for incremental exports.
+    pub async fn get_updated_since(
+        pool: &PgPool,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Link>, AppError> {
+        let links = sqlx::query_as::<_, Link>(
+            r#"
+            SELECT * FROM links
+            WHERE user_id = $1 AND updated_at > $2
+            ORDER BY updated_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(links)
+    }
+
     /// Search links with text query and filters
     ///
     /// Searches across title, description, url, and domain fields.
@@ -251,20 +592,6 @@ impl Link {
             .as_ref()
             .map(|q| format!("%{}%", q.to_lowercase()));
 
-        // Validate and build sort clause to prevent SQL injection
-        let sort_field = match params.sort_by.as_deref() {
-            Some("title") => "LOWER(l.title)",
-            Some("github_stars") => "l.github_stars",
-            Some("status") => "l.status",
-            Some("updated_at") => "l.updated_at",
-            _ => "l.created_at", // default
-        };
-
-        let sort_order = match params.sort_order.as_deref() {
-            Some("asc") => "ASC",
-            _ => "DESC", // default
-        };
-
         // Build query with validated ORDER BY clause
         let query_str = format!(
             r#"
@@ -285,9 +612,12 @@ impl Link {
             AND ($6::uuid IS NULL OR lt.tag_id = $6)
             AND ($7::uuid IS NULL OR ll.language_id = $7)
             AND ($8::uuid IS NULL OR lli.license_id = $8)
-            ORDER BY {} {} NULLS LAST
+            AND ($9::bool IS NULL OR l.is_pinned = $9)
+            AND ($10::timestamptz IS NULL OR l.created_at >= $10)
+            AND ($11::timestamptz IS NULL OR l.created_at < $11)
+            {}
             "#,
-            sort_field, sort_order
+            order_by_clause(params)
         );
 
         let links = sqlx::query_as::<_, Link>(&query_str)
@@ -299,6 +629,9 @@ impl Link {
             .bind(params.tag_id)
             .bind(params.language_id)
             .bind(params.license_id)
+            .bind(params.is_pinned)
+            .bind(params.created_after)
+            .bind(params.created_before)
             .fetch_all(pool)
             .await?;
 
@@ -313,6 +646,8 @@ impl Link {
     /// * `pool` - Database connection pool
     /// * `user_id` - User ID to scope the search
     /// * `params` - Search parameters including pagination (page, per_page)
+    /// * `query_timeout_ms` - Statement timeout for the search queries; 0 for
+    ///   none. A cancelled query fails with `AppError::Timeout`.
     ///
     /// # Returns
     /// PaginatedLinks with links for the current page and pagination metadata
@@ -320,6 +655,7 @@ impl Link {
         pool: &PgPool,
         user_id: Uuid,
         params: &LinkSearchParams,
+        query_timeout_ms: u64,
     ) -> Result<PaginatedLinks, AppError> {
         let query_pattern = params
             .query
@@ -331,6 +667,9 @@ impl Link {
         let per_page = params.per_page.unwrap_or(20).min(100);
         let offset = ((page - 1) * per_page) as i64;
 
+        // Both queries share one transaction so the timeout covers them
+        let mut tx = super::begin_with_statement_timeout(pool, query_timeout_ms).await?;
+
         // Build WHERE clause for count query
         let count_query = r#"
             SELECT COUNT(DISTINCT l.id) as count
@@ -351,6 +690,9 @@ impl Link {
             AND ($6::uuid IS NULL OR lt.tag_id = $6)
             AND ($7::uuid IS NULL OR ll.language_id = $7)
             AND ($8::uuid IS NULL OR lli.license_id = $8)
+            AND ($9::bool IS NULL OR l.is_pinned = $9)
+            AND ($10::timestamptz IS NULL OR l.created_at >= $10)
+            AND ($11::timestamptz IS NULL OR l.created_at < $11)
         "#;
 
         let count_result: (i64,) = sqlx::query_as(count_query)
@@ -362,25 +704,14 @@ impl Link {
             .bind(params.tag_id)
             .bind(params.language_id)
             .bind(params.license_id)
-            .fetch_one(pool)
+            .bind(params.is_pinned)
+            .bind(params.created_after)
+            .bind(params.created_before)
+            .fetch_one(&mut *tx)
             .await?;
 
         let total = count_result.0;
 
-        // Validate and build sort clause
-        let sort_field = match params.sort_by.as_deref() {
-            Some("title") => "LOWER(l.title)",
-            Some("github_stars") => "l.github_stars",
-            Some("status") => "l.status",
-            Some("updated_at") => "l.updated_at",
-            _ => "l.created_at",
-        };
-
-        let sort_order = match params.sort_order.as_deref() {
-            Some("asc") => "ASC",
-            _ => "DESC",
-        };
-
         // Build query with pagination
         let query_str = format!(
             r#"
@@ -401,10 +732,13 @@ impl Link {
             AND ($6::uuid IS NULL OR lt.tag_id = $6)
             AND ($7::uuid IS NULL OR ll.language_id = $7)
             AND ($8::uuid IS NULL OR lli.license_id = $8)
-            ORDER BY {} {} NULLS LAST
-            LIMIT $9 OFFSET $10
+            AND ($9::bool IS NULL OR l.is_pinned = $9)
+            AND ($10::timestamptz IS NULL OR l.created_at >= $10)
+            AND ($11::timestamptz IS NULL OR l.created_at < $11)
+            {}
+            LIMIT $12 OFFSET $13
             "#,
-            sort_field, sort_order
+            order_by_clause(params)
         );
 
         let links = sqlx::query_as::<_, Link>(&query_str)
@@ -416,10 +750,14 @@ impl Link {
             .bind(params.tag_id)
             .bind(params.language_id)
             .bind(params.license_id)
+            .bind(params.is_pinned)
+            .bind(params.created_after)
+            .bind(params.created_before)
             .bind(per_page as i64)
             .bind(offset)
-            .fetch_all(pool)
+            .fetch_all(&mut *tx)
             .await?;
+        tx.commit().await?;
 
         let total_pages = if total == 0 {
             1
@@ -436,6 +774,50 @@ impl Link {
         })
     }
 
+    /// Report which fields of the given links match a text search query
+    ///
+    /// Uses the same case-insensitive substring match as `search`, so it is
+    /// meant to be called on the links a search returned.
+    pub async fn search_matches(
+        pool: &PgPool,
+        user_id: Uuid,
+        link_ids: &[Uuid],
+        query: &str,
+    ) -> Result<HashMap<Uuid, SearchMatches>, AppError> {
+        let rows = sqlx::query_as::<_, SearchMatchRow>(
+            r#"
+            SELECT
+                id,
+                COALESCE(LOWER(title) LIKE $3, false) AS title_match,
+                COALESCE(LOWER(description) LIKE $3, false) AS description_match,
+                LOWER(url) LIKE $3 AS url_match,
+                COALESCE(LOWER(domain) LIKE $3, false) AS domain_match
+            FROM links
+            WHERE user_id = $1 AND id = ANY($2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(link_ids)
+        .bind(format!("%{}%", query.to_lowercase()))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.id,
+                    SearchMatches {
+                        title: row.title_match,
+                        description: row.description_match,
+                        url: row.url_match,
+                        domain: row.domain_match,
+                    },
+                )
+            })
+            .collect())
+    }
+
     /// Get links that need refresh (not refreshed in the last N days)
     ///
     /// Returns links that are:
@@ -475,30 +857,45 @@ impl Link {
     /// Update a link
     pub async fn update(
         pool: &PgPool,
+        config: &Config,
         id: Uuid,
         user_id: Uuid,
         update: UpdateLink,
     ) -> Result<Link, AppError> {
         // Validate status if provided
         if let Some(ref status) = update.status {
-            let valid_statuses = ["active", "archived", "inaccessible", "repo_unavailable"];
-            if !valid_statuses.contains(&status.as_str()) {
+            if !LINK_STATUSES.contains(&status.as_str()) {
                 return Err(AppError::validation(
                     "status",
-                    "Status must be one of: active, archived, inaccessible, repo_unavailable",
+                    "Status must be one of: active, archived, inaccessible, repo_unavailable, private_repo, repo_takedown",
                 ));
             }
         }
 
+        if let Some(ref url) = update.url {
+            let parsed_url = Url::parse(url)
+                .map_err(|e| AppError::validation("url", &format!("Invalid URL: {}", e)))?;
+            crate::security::validate_url_scheme(&parsed_url, &config.allowed_url_schemes)?;
+        }
+
         // Update the link's basic fields
         let link = sqlx::query_as::<_, Link>(
             r#"
             UPDATE links
             SET
                 url = COALESCE($3, url),
+                -- Validators of the old page don't apply to a new URL
+                http_etag = CASE WHEN COALESCE($3, url) = url THEN http_etag END,
+                http_last_modified = CASE WHEN COALESCE($3, url) = url THEN http_last_modified END,
                 title = COALESCE($4, title),
+                title_manually_edited = title_manually_edited
+                    OR ($4::text IS NOT NULL AND $4 IS DISTINCT FROM title),
                 description = COALESCE($5, description),
                 status = COALESCE($6, status),
+                status_changed_at = CASE
+                    WHEN COALESCE($6, status) IS DISTINCT FROM status THEN NOW()
+                    ELSE status_changed_at
+                END,
                 logo = COALESCE($7, logo),
                 source_code_url = CASE WHEN $8::boolean THEN $9 ELSE source_code_url END,
                 documentation_url = CASE WHEN $10::boolean THEN $11 ELSE documentation_url END,
@@ -677,110 +1074,270 @@ impl Link {
         Ok(())
     }
 
-    /// Mark a link as active (sets status to "active")
-    pub async fn mark_as_active(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
-        Self::update_status(pool, id, "active").await
-    }
-
-    /// Add a category to a link
-    pub async fn add_category(
+    /// Pin or unpin a link (requires user_id for verification)
+    ///
+    /// Only touches `is_pinned`; the link's status is left alone.
+    pub async fn set_pinned(
         pool: &PgPool,
-        link_id: Uuid,
-        category_id: Uuid,
+        id: Uuid,
         user_id: Uuid,
-    ) -> Result<(), AppError> {
-        // Verify link belongs to user
-        let _ = Self::get_by_id(pool, link_id, user_id).await?;
-
-        // Verify category belongs to user
-        let _ = Category::get_by_id(pool, category_id, user_id).await?;
-
-        // Insert (ignore if already exists)
-        sqlx::query(
+        pinned: bool,
+    ) -> Result<Link, AppError> {
+        let link = sqlx::query_as::<_, Link>(
             r#"
-            INSERT INTO link_categories (link_id, category_id)
-            VALUES ($1, $2)
-            ON CONFLICT DO NOTHING
+            UPDATE links
+            SET is_pinned = $3, updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
             "#,
         )
-        .bind(link_id)
-        .bind(category_id)
-        .execute(pool)
-        .await?;
+        .bind(id)
+        .bind(user_id)
+        .bind(pinned)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::not_found("link", &id.to_string()))?;
 
-        Ok(())
+        tracing::info!(link_id = %id, pinned = pinned, "Link pin state updated");
+
+        Ok(link)
     }
 
-    /// Remove a category from a link
-    pub async fn remove_category(
-        pool: &PgPool,
-        link_id: Uuid,
-        category_id: Uuid,
-        user_id: Uuid,
-    ) -> Result<(), AppError> {
-        // Verify link belongs to user
-        let _ = Self::get_by_id(pool, link_id, user_id).await?;
+    /// Record that the user opened a link
+    ///
+    /// Leaves `updated_at` alone, since the link itself didn't change.
+    pub async fn record_visit(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        let result =
+            sqlx::query("UPDATE links SET last_visited_at = NOW() WHERE id = $1 AND user_id = $2")
+                .bind(id)
+                .bind(user_id)
+                .execute(pool)
+                .await?;
 
-        sqlx::query("DELETE FROM link_categories WHERE link_id = $1 AND category_id = $2")
-            .bind(link_id)
-            .bind(category_id)
-            .execute(pool)
-            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found("link", &id.to_string()));
+        }
 
         Ok(())
     }
 
-    /// Get categories for a link
-    pub async fn get_categories(
-        pool: &PgPool,
-        link_id: Uuid,
-        user_id: Uuid,
-    ) -> Result<Vec<Category>, AppError> {
-        // Verify link belongs to user
-        let _ = Self::get_by_id(pool, link_id, user_id).await?;
-
-        let categories = sqlx::query_as::<_, Category>(
+    /// Archive active, unpinned links not visited in `days` days
+    ///
+    /// Links never visited count from when they were created; links saved
+    /// before visits were tracked count from when tracking started
+    /// (`link_visit_tracking`). Only the status changes, so archived links can
+    /// be restored.
+    ///
+    /// # Returns
+    /// The archived links, as they were before archiving
+    pub async fn archive_unvisited(pool: &PgPool, days: u32) -> Result<Vec<Link>, AppError> {
+        let links = sqlx::query_as::<_, Link>(
             r#"
-            SELECT c.* FROM categories c
-            JOIN link_categories lc ON lc.category_id = c.id
-            WHERE lc.link_id = $1
-            ORDER BY c.name
+            WITH stale AS (
+                SELECT * FROM links
+                WHERE status = 'active'
+                  AND NOT is_pinned
+                  AND COALESCE(
+                      last_visited_at,
+                      GREATEST(created_at, (SELECT started_at FROM link_visit_tracking))
+                  ) < NOW() - make_interval(days => $1)
+                FOR UPDATE
+            )
+            UPDATE links l
+            SET status = 'archived', status_changed_at = NOW(), updated_at = NOW()
+            FROM stale
+            WHERE l.id = stale.id
+            RETURNING stale.*
             "#,
         )
-        .bind(link_id)
+        .bind(days as i32)
         .fetch_all(pool)
         .await?;
 
-        Ok(categories)
+        Ok(links)
     }
 
-    /// Add a tag to a link
-    pub async fn add_tag(
-        pool: &PgPool,
-        link_id: Uuid,
-        tag_id: Uuid,
-        user_id: Uuid,
-    ) -> Result<(), AppError> {
-        let _ = Self::get_by_id(pool, link_id, user_id).await?;
-        let _ = Tag::get_by_id(pool, tag_id, user_id).await?;
+    /// Every link's id, owner, URL and GitHub repository flag, across all users
+    pub async fn github_flags(pool: &PgPool) -> Result<Vec<(Uuid, Uuid, String, bool)>, AppError> {
+        let rows = sqlx::query_as::<_, (Uuid, Uuid, String, bool)>(
+            "SELECT id, user_id, url, is_github_repo FROM links",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// A page of links' stored URL parts, across all users, in id order
+    ///
+    /// For maintenance tasks that walk every link in batches: pass the last id
+    /// of the previous page as `after` to continue.
+    pub async fn url_parts_page(
+        pool: &PgPool,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<StoredUrlParts>, AppError> {
+        let rows = sqlx::query_as::<_, StoredUrlParts>(
+            r#"
+            SELECT id, url, domain, domain_display, path FROM links
+            WHERE $1::uuid IS NULL OR id > $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Overwrite a link's domain and path columns, regardless of owner
+    ///
+    /// For maintenance tasks. Fails with `Duplicate` when another of the
+    /// owner's links already has this domain and path.
+    pub async fn set_url_parts(pool: &PgPool, id: Uuid, parts: &UrlParts) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE links SET domain = $2, domain_display = $3, path = $4, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&parts.domain)
+        .bind(&parts.domain_display)
+        .bind(&parts.path)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set the GitHub repository flag on links, regardless of owner
+    ///
+    /// For maintenance tasks; returns the number of links changed.
+    pub async fn set_github_repo_flag(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        is_github_repo: bool,
+    ) -> Result<u64, AppError> {
+        if link_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = sqlx::query(
+            r#"
+            UPDATE links SET is_github_repo = $2, updated_at = NOW()
+            WHERE id = ANY($1) AND is_github_repo <> $2
+            "#,
+        )
+        .bind(link_ids)
+        .bind(is_github_repo)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Mark a link as active (sets status to "active")
+    pub async fn mark_as_active(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+        Self::update_status(pool, id, "active").await
+    }
 
-        let next_order: i32 = sqlx::query_scalar(
-            "SELECT COALESCE(MAX(order_num), -1) + 1 FROM link_tags WHERE link_id = $1",
+    /// Add a category to a link
+    pub async fn add_category(
+        pool: &PgPool,
+        link_id: Uuid,
+        category_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        // Verify link belongs to user
+        let _ = Self::get_by_id(pool, link_id, user_id).await?;
+
+        // Verify category belongs to user
+        let _ = Category::get_by_id(pool, category_id, user_id).await?;
+
+        // Insert (ignore if already exists)
+        sqlx::query(
+            r#"
+            INSERT INTO link_categories (link_id, category_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
         )
         .bind(link_id)
-        .fetch_one(pool)
+        .bind(category_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a category from a link
+    pub async fn remove_category(
+        pool: &PgPool,
+        link_id: Uuid,
+        category_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        // Verify link belongs to user
+        let _ = Self::get_by_id(pool, link_id, user_id).await?;
+
+        sqlx::query("DELETE FROM link_categories WHERE link_id = $1 AND category_id = $2")
+            .bind(link_id)
+            .bind(category_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get categories for a link
+    pub async fn get_categories(
+        pool: &PgPool,
+        link_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Category>, AppError> {
+        // Verify link belongs to user
+        let _ = Self::get_by_id(pool, link_id, user_id).await?;
+
+        let categories = sqlx::query_as::<_, Category>(
+            r#"
+            SELECT c.* FROM categories c
+            JOIN link_categories lc ON lc.category_id = c.id
+            WHERE lc.link_id = $1
+            ORDER BY c.name
+            "#,
+        )
+        .bind(link_id)
+        .fetch_all(pool)
         .await?;
 
+        Ok(categories)
+    }
+
+    /// Add a tag to a link
+    pub async fn add_tag(
+        pool: &PgPool,
+        link_id: Uuid,
+        tag_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        let _ = Self::get_by_id(pool, link_id, user_id).await?;
+        let _ = Tag::get_by_id(pool, tag_id, user_id).await?;
+
+        // Next position computed in the INSERT itself, not read back first
         sqlx::query(
             r#"
             INSERT INTO link_tags (link_id, tag_id, order_num)
-            VALUES ($1, $2, $3)
+            SELECT $1, $2, COALESCE(MAX(order_num), -1) + 1
+            FROM link_tags WHERE link_id = $1
             ON CONFLICT DO NOTHING
             "#,
         )
         .bind(link_id)
         .bind(tag_id)
-        .bind(next_order)
         .execute(pool)
         .await?;
 
@@ -838,23 +1395,17 @@ impl Link {
         let _ = Self::get_by_id(pool, link_id, user_id).await?;
         let _ = Language::get_by_id(pool, language_id, user_id).await?;
 
-        let next_order: i32 = sqlx::query_scalar(
-            "SELECT COALESCE(MAX(order_num), -1) + 1 FROM link_languages WHERE link_id = $1",
-        )
-        .bind(link_id)
-        .fetch_one(pool)
-        .await?;
-
+        // Next position computed in the INSERT itself, not read back first
         sqlx::query(
             r#"
             INSERT INTO link_languages (link_id, language_id, order_num)
-            VALUES ($1, $2, $3)
+            SELECT $1, $2, COALESCE(MAX(order_num), -1) + 1
+            FROM link_languages WHERE link_id = $1
             ON CONFLICT DO NOTHING
             "#,
         )
         .bind(link_id)
         .bind(language_id)
-        .bind(next_order)
         .execute(pool)
         .await?;
 
@@ -912,23 +1463,17 @@ impl Link {
         let _ = Self::get_by_id(pool, link_id, user_id).await?;
         let _ = License::get_by_id(pool, license_id, user_id).await?;
 
-        let next_order: i32 = sqlx::query_scalar(
-            "SELECT COALESCE(MAX(order_num), -1) + 1 FROM link_licenses WHERE link_id = $1",
-        )
-        .bind(link_id)
-        .fetch_one(pool)
-        .await?;
-
+        // Next position computed in the INSERT itself, not read back first
         sqlx::query(
             r#"
             INSERT INTO link_licenses (link_id, license_id, order_num)
-            VALUES ($1, $2, $3)
+            SELECT $1, $2, COALESCE(MAX(order_num), -1) + 1
+            FROM link_licenses WHERE link_id = $1
             ON CONFLICT DO NOTHING
             "#,
         )
         .bind(link_id)
         .bind(license_id)
-        .bind(next_order)
         .execute(pool)
         .await?;
 
@@ -976,6 +1521,310 @@ impl Link {
         Ok(licenses)
     }
 
+    /// Verify that every link in `link_ids` belongs to the user, inside `tx`
+    ///
+    /// Duplicate IDs are ignored. Returns NotFound if any link is missing or
+    /// owned by another user, so the caller can abort the whole batch.
+    async fn verify_ownership_batch(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        link_ids: &[Uuid],
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        let mut unique_ids = link_ids.to_vec();
+        unique_ids.sort();
+        unique_ids.dedup();
+
+        let owned: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM links WHERE user_id = $1 AND id = ANY($2)")
+                .bind(user_id)
+                .bind(&unique_ids)
+                .fetch_one(&mut **tx)
+                .await?;
+
+        if owned != unique_ids.len() as i64 {
+            return Err(AppError::not_found("link", "one or more links"));
+        }
+
+        Ok(())
+    }
+
+    /// Add a category to multiple links in a single transaction
+    ///
+    /// Either every link is updated or none are. Returns the number of links
+    /// that gained the category (links that already had it are not counted).
+    pub async fn bulk_add_category(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        category_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let _ = Category::get_by_id(pool, category_id, user_id).await?;
+
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO link_categories (link_id, category_id)
+            SELECT id, $2 FROM links WHERE id = ANY($1)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(link_ids)
+        .bind(category_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remove a category from multiple links in a single transaction
+    ///
+    /// Returns the number of links the category was removed from.
+    pub async fn bulk_remove_category(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        category_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result =
+            sqlx::query("DELETE FROM link_categories WHERE link_id = ANY($1) AND category_id = $2")
+                .bind(link_ids)
+                .bind(category_id)
+                .execute(&mut *tx)
+                .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Move multiple links into exactly one category in a single transaction
+    ///
+    /// Each link's other categories are removed and `category_id` is added if
+    /// missing. Returns the number of links whose categories changed.
+    pub async fn bulk_set_category(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        category_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let _ = Category::get_by_id(pool, category_id, user_id).await?;
+
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let changed: i64 = sqlx::query_scalar(
+            r#"
+            WITH removed AS (
+                DELETE FROM link_categories
+                WHERE link_id = ANY($1) AND category_id <> $2
+                RETURNING link_id
+            ),
+            added AS (
+                INSERT INTO link_categories (link_id, category_id)
+                SELECT id, $2 FROM links WHERE id = ANY($1)
+                ON CONFLICT DO NOTHING
+                RETURNING link_id
+            )
+            SELECT COUNT(*) FROM (
+                SELECT link_id FROM removed UNION SELECT link_id FROM added
+            ) changed
+            "#,
+        )
+        .bind(link_ids)
+        .bind(category_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(changed as u64)
+    }
+
+    /// Add a tag to multiple links in a single transaction
+    ///
+    /// The tag is appended after each link's existing tags. Returns the number
+    /// of links that gained the tag (links that already had it are not counted).
+    pub async fn bulk_add_tag(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        tag_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let _ = Tag::get_by_id(pool, tag_id, user_id).await?;
+
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO link_tags (link_id, tag_id, order_num)
+            SELECT l.id, $2,
+                COALESCE((SELECT MAX(lt.order_num) + 1 FROM link_tags lt WHERE lt.link_id = l.id), 0)
+            FROM links l
+            WHERE l.id = ANY($1)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(link_ids)
+        .bind(tag_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remove a tag from multiple links in a single transaction
+    ///
+    /// Returns the number of links the tag was removed from.
+    pub async fn bulk_remove_tag(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        tag_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result = sqlx::query("DELETE FROM link_tags WHERE link_id = ANY($1) AND tag_id = $2")
+            .bind(link_ids)
+            .bind(tag_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Add a language to multiple links in a single transaction
+    ///
+    /// The language is appended after each link's existing languages. Returns the
+    /// number of links that gained the language (links that already had it are
+    /// not counted).
+    pub async fn bulk_add_language(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        language_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let _ = Language::get_by_id(pool, language_id, user_id).await?;
+
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO link_languages (link_id, language_id, order_num)
+            SELECT l.id, $2,
+                COALESCE((SELECT MAX(x.order_num) + 1 FROM link_languages x WHERE x.link_id = l.id), 0)
+            FROM links l
+            WHERE l.id = ANY($1)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(link_ids)
+        .bind(language_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remove a language from multiple links in a single transaction
+    ///
+    /// Returns the number of links the language was removed from.
+    pub async fn bulk_remove_language(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        language_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result =
+            sqlx::query("DELETE FROM link_languages WHERE link_id = ANY($1) AND language_id = $2")
+                .bind(link_ids)
+                .bind(language_id)
+                .execute(&mut *tx)
+                .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Add a license to multiple links in a single transaction
+    ///
+    /// The license is appended after each link's existing licenses. Returns the
+    /// number of links that gained the license (links that already had it are
+    /// not counted).
+    pub async fn bulk_add_license(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        license_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let _ = License::get_by_id(pool, license_id, user_id).await?;
+
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO link_licenses (link_id, license_id, order_num)
+            SELECT l.id, $2,
+                COALESCE((SELECT MAX(x.order_num) + 1 FROM link_licenses x WHERE x.link_id = l.id), 0)
+            FROM links l
+            WHERE l.id = ANY($1)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(link_ids)
+        .bind(license_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remove a license from multiple links in a single transaction
+    ///
+    /// Returns the number of links the license was removed from.
+    pub async fn bulk_remove_license(
+        pool: &PgPool,
+        link_ids: &[Uuid],
+        license_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let mut tx = pool.begin().await?;
+        Self::verify_ownership_batch(&mut tx, link_ids, user_id).await?;
+
+        let result =
+            sqlx::query("DELETE FROM link_licenses WHERE link_id = ANY($1) AND license_id = $2")
+                .bind(link_ids)
+                .bind(license_id)
+                .execute(&mut *tx)
+                .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Get categories for multiple links in a single query
     pub async fn get_categories_batch(
         pool: &PgPool,
@@ -1118,13 +1967,20 @@ impl Link {
 
     /// Update scraped metadata for a link
     ///
-    /// Updates title, description, and logo from web scraping results.
+    /// Updates title, description, logo, final URL and the page's `ETag`/
+    /// `Last-Modified` from web scraping results.
     /// Does not update refreshed_at - use mark_refreshed() for that.
+    ///
+    /// By default a missing scraped title or description keeps the stored one,
+    /// and a title the user edited (`title_manually_edited`) is never replaced.
+    /// With `force_overwrite` the scraped values are written as-is, even when
+    /// empty, and a title that changes this way is no longer considered edited.
     pub async fn update_scraped_metadata(
         pool: &PgPool,
         link_id: Uuid,
         user_id: Uuid,
         metadata: crate::scraper::ScrapedMetadata,
+        force_overwrite: bool,
     ) -> Result<(), AppError> {
         // Verify link belongs to user
         let _ = Self::get_by_id(pool, link_id, user_id).await?;
@@ -1134,6 +1990,7 @@ impl Link {
             has_title = metadata.title.is_some(),
             has_description = metadata.description.is_some(),
             has_favicon = metadata.favicon.is_some(),
+            force_overwrite,
             "Updating scraped metadata for link"
         );
 
@@ -1143,9 +2000,18 @@ impl Link {
             r#"
             UPDATE links
             SET
-                title = COALESCE($2, title),
-                description = COALESCE($3, description),
+                title = CASE
+                    WHEN $7 THEN $2
+                    WHEN title_manually_edited THEN title
+                    ELSE COALESCE($2, title)
+                END,
+                title_manually_edited = title_manually_edited
+                    AND NOT ($7 AND $2 IS DISTINCT FROM title),
+                description = CASE WHEN $7 THEN $3 ELSE COALESCE($3, description) END,
                 logo = $4,
+                final_url = $6,
+                http_etag = $8,
+                http_last_modified = $9,
                 updated_at = NOW()
             WHERE id = $1 AND user_id = $5
             "#,
@@ -1155,6 +2021,10 @@ impl Link {
         .bind(&metadata.description)
         .bind(&metadata.favicon)
         .bind(user_id)
+        .bind(&metadata.final_url)
+        .bind(force_overwrite)
+        .bind(&metadata.validators.etag)
+        .bind(&metadata.validators.last_modified)
         .execute(pool)
         .await?;
 
@@ -1198,12 +2068,29 @@ impl Link {
     /// - "archived": User has archived the link
     /// - "inaccessible": Link returned an error or non-success status
     /// - "repo_unavailable": GitHub repository is unavailable (404, etc.)
+    /// - "private_repo": GitHub repository is private or access is forbidden (403);
+    ///   not re-checked by the scheduler
+    /// - "repo_takedown": GitHub repository is unavailable for legal reasons
+    ///   (451, e.g. DMCA); not re-checked by the scheduler
+    ///
+    /// `status_changed_at` is set when the status actually changes.
     pub async fn update_status(pool: &PgPool, id: Uuid, status: &str) -> Result<(), AppError> {
-        sqlx::query("UPDATE links SET status = $1, updated_at = NOW() WHERE id = $2")
-            .bind(status)
-            .bind(id)
-            .execute(pool)
-            .await?;
+        sqlx::query(
+            r#"
+            UPDATE links
+            SET status_changed_at = CASE
+                    WHEN status IS DISTINCT FROM $1 THEN NOW()
+                    ELSE status_changed_at
+                END,
+                status = $1,
+                updated_at = NOW()
+            WHERE id = $2
+            "#,
+        )
+        .bind(status)
+        .bind(id)
+        .execute(pool)
+        .await?;
         Ok(())
     }
 
@@ -1212,11 +2099,19 @@ impl Link {
     /// Increments the consecutive_failures counter. After 3 consecutive failures,
     /// automatically marks the link as inaccessible.
     ///
+    /// The increment happens in SQL (`SET x = x + 1`) rather than by writing
+    /// back a value computed from a previously loaded `Link`, so concurrent
+    /// checks of the same link can't lose updates. Counters added later should
+    /// do the same.
+    ///
     /// # Arguments
     /// * `pool` - Database connection pool
     /// * `id` - Link ID
-    pub async fn record_failure(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
-        sqlx::query(
+    ///
+    /// # Returns
+    /// The link's status and failure count after the update
+    pub async fn record_failure(pool: &PgPool, id: Uuid) -> Result<(String, i32), AppError> {
+        let row = sqlx::query_as::<_, (String, i32)>(
             r#"
             UPDATE links
             SET consecutive_failures = consecutive_failures + 1,
@@ -1224,14 +2119,19 @@ impl Link {
                     WHEN consecutive_failures >= 2 THEN 'inaccessible'
                     ELSE status
                 END,
+                status_changed_at = CASE
+                    WHEN consecutive_failures >= 2 AND status <> 'inaccessible' THEN NOW()
+                    ELSE status_changed_at
+                END,
                 updated_at = NOW()
             WHERE id = $1
+            RETURNING status, consecutive_failures
             "#,
         )
         .bind(id)
-        .execute(pool)
+        .fetch_one(pool)
         .await?;
-        Ok(())
+        Ok(row)
     }
 
     /// Reset failure count on successful access
@@ -1255,9 +2155,10 @@ impl Link {
     /// Updates GitHub-specific fields and sets refreshed_at timestamp.
     /// This function can be called for links where:
     /// - is_github_repo = true (main URL is GitHub), OR
-    /// - source_code_url starts with https://github.com/
+    /// - source_code_url is an https URL on the configured GitHub host
     pub async fn update_github_metadata(
         pool: &PgPool,
+        config: &Config,
         link_id: Uuid,
         user_id: Uuid,
         metadata: crate::github::GitHubRepoMetadata,
@@ -1270,7 +2171,7 @@ impl Link {
             || link
                 .source_code_url
                 .as_ref()
-                .map(|url| url.starts_with("https://github.com/"))
+                .map(|url| config.github.is_host_url(url))
                 .unwrap_or(false);
 
         if !has_github_source {
@@ -1297,7 +2198,9 @@ impl Link {
                 github_stars = $2,
                 github_archived = $3,
                 github_last_commit = $4,
+                github_owner_type = $6,
                 refreshed_at = NOW(),
+                github_refreshed_at = NOW(),
                 updated_at = NOW()
             WHERE id = $1 AND user_id = $5
             "#,
@@ -1307,11 +2210,92 @@ impl Link {
         .bind(metadata.archived)
         .bind(last_commit_date)
         .bind(user_id)
+        .bind(&metadata.owner_type)
         .execute(pool)
         .await?;
 
         tracing::info!(link_id = %link_id, "GitHub metadata updated successfully");
 
+        if let Some(ref new_full_name) = metadata.moved_to {
+            // A failed move (e.g. the new URL is already saved) keeps the old URL
+            if let Err(e) = Self::apply_repo_move(pool, &config.github, &link, new_full_name).await
+            {
+                tracing::warn!(
+                    link_id = %link_id,
+                    moved_to = %new_full_name,
+                    error = %e,
+                    "Failed to update link for moved GitHub repository"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Point a link at the new location of a renamed/transferred GitHub repo
+    ///
+    /// Rewrites the main URL (and path) for GitHub repo links, otherwise the
+    /// GitHub `source_code_url`, and records the previous URL in `renamed_from`.
+    async fn apply_repo_move(
+        pool: &PgPool,
+        github: &crate::github::GitHubHost,
+        link: &Link,
+        new_full_name: &str,
+    ) -> Result<(), AppError> {
+        if link.is_github_repo {
+            let Some(new_url) = github.replace_repo_in_url(&link.url, new_full_name) else {
+                return Ok(());
+            };
+            let new_path = Url::parse(&new_url)
+                .ok()
+                .map(|u| u.path().to_string())
+                .filter(|p| !p.is_empty() && p != "/");
+
+            sqlx::query(
+                r#"
+                UPDATE links
+                SET url = $2, path = $3, renamed_from = url,
+                    http_etag = NULL, http_last_modified = NULL, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(link.id)
+            .bind(&new_url)
+            .bind(&new_path)
+            .execute(pool)
+            .await?;
+
+            tracing::info!(
+                link_id = %link.id,
+                from = %link.url,
+                to = %new_url,
+                "Updated link URL for moved GitHub repository"
+            );
+        } else if let Some(ref source_code_url) = link.source_code_url {
+            let Some(new_url) = github.replace_repo_in_url(source_code_url, new_full_name) else {
+                return Ok(());
+            };
+
+            sqlx::query(
+                r#"
+                UPDATE links
+                SET source_code_url = $2, renamed_from = source_code_url, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(link.id)
+            .bind(&new_url)
+            .execute(pool)
+            .await?;
+
+            tracing::info!(
+                link_id = %link.id,
+                from = %source_code_url,
+                to = %new_url,
+                "Updated source code URL for moved GitHub repository"
+            );
+        }
+
         Ok(())
     }
 
@@ -1321,7 +2305,98 @@ impl Link {
         user_id: Uuid,
     ) -> Result<Vec<LinkWithCategories>, AppError> {
         let links = Self::get_all_by_user(pool, user_id).await?;
+        Self::with_metadata(pool, links).await
+    }
+
+    /// Find links related to a link by shared tags, categories and domain
+    ///
+    /// Candidates score `SIMILAR_CATEGORY_WEIGHT` per shared category,
+    /// `SIMILAR_TAG_WEIGHT` per shared tag and `SIMILAR_DOMAIN_BONUS` for the
+    /// same domain. Links with no overlap, archived links and the link itself
+    /// are excluded. Ties go to the most recently created link.
+    ///
+    /// # Returns
+    /// Up to `limit` links, highest score first, or `NotFound` if the link
+    /// doesn't exist or belong to the user
+    pub async fn find_similar(
+        pool: &PgPool,
+        user_id: Uuid,
+        link_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<LinkWithCategories>, AppError> {
+        let link = Self::get_by_id(pool, link_id, user_id).await?;
+
+        let links = sqlx::query_as::<_, Link>(
+            r#"
+            WITH shared_tags AS (
+                SELECT lt.link_id, COUNT(*) AS shared
+                FROM link_tags lt
+                WHERE lt.tag_id IN (SELECT tag_id FROM link_tags WHERE link_id = $1)
+                GROUP BY lt.link_id
+            ),
+            shared_categories AS (
+                SELECT lc.link_id, COUNT(*) AS shared
+                FROM link_categories lc
+                WHERE lc.category_id IN (SELECT category_id FROM link_categories WHERE link_id = $1)
+                GROUP BY lc.link_id
+            )
+            SELECT l.* FROM links l
+            LEFT JOIN shared_tags st ON st.link_id = l.id
+            LEFT JOIN shared_categories sc ON sc.link_id = l.id
+            WHERE l.user_id = $2
+              AND l.id <> $1
+              AND l.status <> 'archived'
+              AND (st.shared IS NOT NULL OR sc.shared IS NOT NULL OR l.domain = $3)
+            ORDER BY
+                COALESCE(sc.shared, 0) * $4
+                    + COALESCE(st.shared, 0) * $5
+                    + CASE WHEN l.domain = $3 THEN $6 ELSE 0 END DESC,
+                l.created_at DESC
+            LIMIT $7
+            "#,
+        )
+        .bind(link_id)
+        .bind(user_id)
+        .bind(&link.domain)
+        .bind(SIMILAR_CATEGORY_WEIGHT)
+        .bind(SIMILAR_TAG_WEIGHT)
+        .bind(SIMILAR_DOMAIN_BONUS)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
 
+        Self::with_metadata(pool, links).await
+    }
+
+    /// Get several of the user's links with their metadata, in `link_ids` order
+    ///
+    /// Ids that don't exist or belong to another user are skipped, and a
+    /// repeated id is returned once, at its first position.
+    pub async fn get_many_with_metadata(
+        pool: &PgPool,
+        user_id: Uuid,
+        link_ids: &[Uuid],
+    ) -> Result<Vec<LinkWithCategories>, AppError> {
+        let links = sqlx::query_as::<_, Link>(
+            r#"
+            SELECT * FROM links
+            WHERE id = ANY($1) AND user_id = $2
+            ORDER BY array_position($1, id)
+            "#,
+        )
+        .bind(link_ids)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Self::with_metadata(pool, links).await
+    }
+
+    /// Attach categories, tags, languages and licenses to links, keeping order
+    async fn with_metadata(
+        pool: &PgPool,
+        links: Vec<Link>,
+    ) -> Result<Vec<LinkWithCategories>, AppError> {
         let link_ids: Vec<Uuid> = links.iter().map(|l| l.id).collect();
         let mut categories_map = Self::get_categories_batch(pool, &link_ids).await?;
         let mut tags_map = Self::get_tags_batch(pool, &link_ids).await?;
@@ -1341,6 +2416,7 @@ impl Link {
                 tags,
                 languages,
                 licenses,
+                matches: None,
             });
         }
 
@@ -1381,6 +2457,155 @@ impl Link {
         Ok(links)
     }
 
+    /// Count links that are due for checking by the scheduler
+    ///
+    /// Uses the same criteria as `get_links_needing_check`, without a limit.
+    pub async fn count_needing_check(pool: &PgPool, interval_hours: u32) -> Result<i64, AppError> {
+        let check_threshold = Utc::now() - chrono::Duration::hours(interval_hours as i64);
+
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM links
+            WHERE status IN ('active', 'inaccessible', 'repo_unavailable')
+            AND (last_checked IS NULL OR last_checked < $1)
+            "#,
+        )
+        .bind(check_threshold)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    /// A user's links that broke after `since`, most recent first
+    ///
+    /// Returns links whose status changed to one of `FAILED_STATUSES` after
+    /// `since` and is still failed, for "your links are breaking" alerts.
+    pub async fn recently_failed(
+        pool: &PgPool,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Link>, AppError> {
+        let links = sqlx::query_as::<_, Link>(
+            r#"
+            SELECT * FROM links
+            WHERE user_id = $1
+              AND status = ANY($2)
+              AND status_changed_at > $3
+            ORDER BY status_changed_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(&FAILED_STATUSES[..])
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    /// All users' links that broke in `(since, until]`, grouped by user
+    ///
+    /// Same selection as `recently_failed`, across users and bounded on both
+    /// ends so consecutive windows don't report a link twice. Ordered by user,
+    /// then most recent first.
+    pub async fn failed_between(
+        pool: &PgPool,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Link>, AppError> {
+        let links = sqlx::query_as::<_, Link>(
+            r#"
+            SELECT * FROM links
+            WHERE status = ANY($1)
+              AND status_changed_at > $2
+              AND status_changed_at <= $3
+            ORDER BY user_id, status_changed_at DESC
+            "#,
+        )
+        .bind(&FAILED_STATUSES[..])
+        .bind(since)
+        .bind(until)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    /// A user's most recently added links for feeds, newest first
+    ///
+    /// Archived links are left out. Feed endpoints all select through this so
+    /// they list the same items.
+    pub async fn recent_for_feed(
+        pool: &PgPool,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Link>, AppError> {
+        let links = sqlx::query_as::<_, Link>(
+            r#"
+            SELECT * FROM links
+            WHERE user_id = $1 AND status <> 'archived'
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    /// Count a user's links per calendar month (UTC) of `created_at`
+    ///
+    /// Only months with at least one link are returned, oldest first.
+    pub async fn timeline(pool: &PgPool, user_id: Uuid) -> Result<Vec<TimelineMonth>, AppError> {
+        let months = sqlx::query_as::<_, TimelineMonth>(
+            r#"
+            SELECT
+                EXTRACT(YEAR FROM month)::int AS year,
+                EXTRACT(MONTH FROM month)::int AS month,
+                count
+            FROM (
+                SELECT date_trunc('month', created_at AT TIME ZONE 'UTC') AS month, COUNT(*) AS count
+                FROM links
+                WHERE user_id = $1
+                GROUP BY 1
+            ) months
+            ORDER BY months.month
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(months)
+    }
+
+    /// Count a user's links per status
+    ///
+    /// Every status in `LINK_STATUSES` is present (0 if unused), plus any
+    /// other status found in the table.
+    pub async fn status_counts(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<BTreeMap<String, i64>, AppError> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT status, COUNT(*) FROM links WHERE user_id = $1 GROUP BY status")
+                .bind(user_id)
+                .fetch_all(pool)
+                .await?;
+
+        let mut counts: BTreeMap<String, i64> = LINK_STATUSES
+            .iter()
+            .map(|status| (status.to_string(), 0))
+            .collect();
+        counts.extend(rows);
+
+        Ok(counts)
+    }
+
     /// Mark a link as checked by the scheduler
     ///
     /// Updates the last_checked timestamp to the current time.
@@ -1401,10 +2626,35 @@ impl Link {
 
         Ok(())
     }
+
+    /// Mark several links as checked in a single statement
+    ///
+    /// Used by the scheduler to flush a whole batch at once instead of issuing
+    /// one `mark_checked` UPDATE per link. Returns the number of rows updated.
+    pub async fn mark_checked_bulk(pool: &PgPool, link_ids: &[Uuid]) -> Result<u64, AppError> {
+        if link_ids.is_empty() {
+            return Ok(0);
+        }
+
+        tracing::debug!(count = link_ids.len(), "Marking links as checked");
+
+        let result = sqlx::query(
+            r#"
+            UPDATE links
+            SET last_checked = NOW()
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(link_ids)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 /// Link with its associated metadata
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 pub struct LinkWithCategories {
     #[serde(flatten)]
     pub link: Link,
@@ -1412,12 +2662,210 @@ pub struct LinkWithCategories {
     pub tags: Vec<Tag>,
     pub languages: Vec<Language>,
     pub licenses: Vec<License>,
+    /// Fields that matched the search query, when `highlight` was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches: Option<SearchMatches>,
+}
+
+/// Reduce a URL to the `(domain, path)` pair links are unique on
+///
+/// Returns `None` for URLs that don't parse or have no host; those can only
+/// match a saved link by exact URL.
+fn normalize_url(url: &str, config: &Config) -> Option<(String, Option<String>)> {
+    let parsed = Url::parse(url).ok()?;
+    let (domain, _) = normalize_host(&parsed, config.strip_www)?;
+    Some((domain, link_path(&parsed, config.strip_fragment)))
+}
+
+/// Stored `path` of a link, `None` for the site root
+///
+/// With fragment stripping off, a non-empty fragment is kept on the path
+/// (`/app#/settings`), so hash-routed pages stay separate links.
+fn link_path(url: &Url, strip_fragment: bool) -> Option<String> {
+    let p = url.path();
+    let fragment = url.fragment().filter(|f| !strip_fragment && !f.is_empty());
+    match fragment {
+        Some(fragment) => Some(format!("{}#{}", p, fragment)),
+        None if p.is_empty() || p == "/" => None,
+        None => Some(p.to_string()),
+    }
+}
+
+/// Split a URL's host into its stored (ASCII) and display (Unicode) forms
+///
+/// `url` already applies IDNA to domains, so `münchen.de` parses to
+/// `xn--mnchen-3ya.de`; the display form converts it back. IP hosts are the
+/// same in both forms, with IPv6 kept in brackets as in `host_str()`. With
+/// `strip_www`, a leading `www.` label is dropped so `www.example.com` and
+/// `example.com` group together.
+fn normalize_host(url: &Url, strip_www: bool) -> Option<(String, String)> {
+    match url.host()? {
+        url::Host::Domain(domain) => {
+            let mut ascii = domain.to_ascii_lowercase();
+            if strip_www {
+                if let Some(rest) = ascii.strip_prefix("www.") {
+                    // Keep `www.com`-style hosts, which have nothing left to group on
+                    if rest.contains('.') {
+                        ascii = rest.to_string();
+                    }
+                }
+            }
+            let (unicode, result) = idna::domain_to_unicode(&ascii);
+            let display = if result.is_ok() {
+                unicode
+            } else {
+                ascii.clone()
+            };
+            Some((ascii, display))
+        }
+        url::Host::Ipv4(addr) => Some((addr.to_string(), addr.to_string())),
+        url::Host::Ipv6(addr) => {
+            let host = format!("[{}]", addr);
+            Some((host.clone(), host))
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_normalize_host_idn() {
+        let url = Url::parse("https://münchen.de/stadt").unwrap();
+        let (domain, display) = normalize_host(&url, true).unwrap();
+        assert_eq!(domain, "xn--mnchen-3ya.de");
+        assert_eq!(display, "münchen.de");
+
+        // Punycode input yields the same stored and display forms
+        let url = Url::parse("https://XN--MNCHEN-3YA.de/").unwrap();
+        assert_eq!(
+            normalize_host(&url, true),
+            Some(("xn--mnchen-3ya.de".to_string(), "münchen.de".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_url_parts_from_url() {
+        let config = crate::config::test_config();
+        let parts = UrlParts::from_url("https://www.example.com/docs/", &config).unwrap();
+        assert_eq!(parts.domain, "example.com");
+        assert_eq!(parts.domain_display, "example.com");
+        assert_eq!(parts.path, Some("/docs/".to_string()));
+
+        assert_eq!(
+            UrlParts::from_url("https://example.com/", &config)
+                .unwrap()
+                .path,
+            None
+        );
+        assert!(UrlParts::from_url("not a url", &config).is_none());
+        assert!(UrlParts::from_url("mailto:someone@example.com", &config).is_none());
+    }
+
+    #[test]
+    fn test_stored_url_parts_matches() {
+        let config = crate::config::test_config();
+        let parts = UrlParts::from_url("https://example.com/a", &config).unwrap();
+        let mut stored = StoredUrlParts {
+            id: Uuid::new_v4(),
+            url: "https://example.com/a".to_string(),
+            domain: "example.com".to_string(),
+            domain_display: Some("example.com".to_string()),
+            path: Some("/a".to_string()),
+        };
+        assert!(stored.matches(&parts));
+
+        stored.domain_display = None;
+        assert!(!stored.matches(&parts));
+
+        stored.domain_display = Some("example.com".to_string());
+        stored.domain = "old.example.com".to_string();
+        assert!(!stored.matches(&parts));
+    }
+
+    #[test]
+    fn test_normalize_host_strip_www() {
+        let www = Url::parse("https://www.Example.com/docs").unwrap();
+        let bare = Url::parse("https://example.com/docs").unwrap();
+        let expected = Some(("example.com".to_string(), "example.com".to_string()));
+        assert_eq!(normalize_host(&www, true), expected);
+        assert_eq!(normalize_host(&bare, true), expected);
+
+        // Disabled, the `www.` variant stays a separate domain
+        assert_eq!(
+            normalize_host(&www, false),
+            Some(("www.example.com".to_string(), "www.example.com".to_string()))
+        );
+        assert_eq!(normalize_host(&bare, false), expected);
+
+        // Only a leading label is stripped, and never down to a bare TLD
+        let url = Url::parse("https://www2.www.example.com/").unwrap();
+        assert_eq!(
+            normalize_host(&url, true).unwrap().0,
+            "www2.www.example.com"
+        );
+        let url = Url::parse("https://www.com/").unwrap();
+        assert_eq!(normalize_host(&url, true).unwrap().0, "www.com");
+    }
+
+    #[test]
+    fn test_normalize_url_www_variants() {
+        let config = crate::config::test_config();
+        let expected = Some(("example.com".to_string(), Some("/a".to_string())));
+        assert_eq!(
+            normalize_url("https://www.example.com/a", &config),
+            expected
+        );
+        assert_eq!(
+            normalize_url("http://example.com/a?x=1#top", &config),
+            expected
+        );
+        assert_eq!(
+            normalize_url("https://www.example.com/", &config),
+            Some(("example.com".to_string(), None))
+        );
+        assert_eq!(normalize_url("not a url", &config), None);
+    }
+
+    #[test]
+    fn test_normalize_url_strips_fragment() {
+        let config = crate::config::test_config();
+        assert_eq!(
+            normalize_url("https://example.com/page#a", &config),
+            normalize_url("https://example.com/page#b", &config)
+        );
+        assert_eq!(
+            normalize_url("https://example.com/page#a", &config),
+            Some(("example.com".to_string(), Some("/page".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_link_path_keeps_fragment_when_not_stripping() {
+        let a = Url::parse("https://example.com/page#a").unwrap();
+        let b = Url::parse("https://example.com/page#b").unwrap();
+        assert_eq!(link_path(&a, false), Some("/page#a".to_string()));
+        assert_ne!(link_path(&a, false), link_path(&b, false));
+        assert_eq!(link_path(&a, true), Some("/page".to_string()));
+
+        let root = Url::parse("https://example.com/#/settings").unwrap();
+        assert_eq!(link_path(&root, false), Some("/#/settings".to_string()));
+        assert_eq!(link_path(&root, true), None);
+
+        // An empty fragment never distinguishes links
+        let empty = Url::parse("https://example.com/page#").unwrap();
+        assert_eq!(link_path(&empty, false), Some("/page".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_host_ipv6() {
+        let url = Url::parse("http://[2001:0db8:0000::1]:8080/admin").unwrap();
+        let (domain, display) = normalize_host(&url, true).unwrap();
+        assert_eq!(domain, "[2001:db8::1]");
+        assert_eq!(display, "[2001:db8::1]");
+    }
+
     #[test]
     fn test_url_parsing() {
         let url = Url::parse("https://github.com/rust-lang/rust").unwrap();
@@ -1523,6 +2971,57 @@ mod tests {
         assert!(params.per_page.is_none());
     }
 
+    #[test]
+    fn test_order_by_clause_has_id_tie_breaker() {
+        let params = LinkSearchParams::default();
+        assert_eq!(
+            order_by_clause(&params),
+            "ORDER BY l.is_pinned DESC, l.created_at DESC NULLS LAST, l.id ASC"
+        );
+
+        let params = LinkSearchParams {
+            sort_by: Some("github_stars".to_string()),
+            sort_order: Some("asc".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            order_by_clause(&params),
+            "ORDER BY l.is_pinned DESC, l.github_stars ASC NULLS LAST, l.id ASC"
+        );
+
+        // Unknown fields fall back to the default instead of reaching the SQL
+        let params = LinkSearchParams {
+            sort_by: Some("id; DROP TABLE links".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            order_by_clause(&params).starts_with("ORDER BY l.is_pinned DESC, l.created_at DESC")
+        );
+    }
+
+    #[test]
+    fn test_order_by_clause_default_direction_per_field() {
+        let order = |sort_by: &str, sort_order: Option<&str>| {
+            order_by_clause(&LinkSearchParams {
+                sort_by: Some(sort_by.to_string()),
+                sort_order: sort_order.map(str::to_string),
+                ..Default::default()
+            })
+            .strip_prefix("ORDER BY l.is_pinned DESC, ")
+            .unwrap()
+            .to_string()
+        };
+
+        assert!(order("title", None).starts_with("LOWER(l.title) ASC"));
+        assert!(order("status", None).starts_with("l.status ASC"));
+        assert!(order("updated_at", None).starts_with("l.updated_at DESC"));
+        assert!(order("github_stars", None).starts_with("l.github_stars DESC"));
+
+        // An explicit direction always wins
+        assert!(order("title", Some("desc")).starts_with("LOWER(l.title) DESC"));
+        assert!(order("created_at", Some("asc")).starts_with("l.created_at ASC"));
+    }
+
     #[test]
     fn test_update_link_defaults() {
         let update = UpdateLink::default();
@@ -9,6 +9,10 @@
 //! - `user` - User authentication and management
 //! - `link` - Bookmark links
 //! - `category` - Link categories
+//! - `session` - Server-side login sessions
+//! - `idempotency_key` - Replay protection for link creation
+//! - `user_settings` - Per-user preferences
+//! - `stats` - Aggregate statistics over a user's links
 //!
 //! Future modules will include:
 //! - `tag` - Link tags
@@ -16,19 +20,95 @@
 //! - `license` - Software licenses
 
 pub mod category;
+pub mod idempotency_key;
 pub mod language;
 pub mod license;
 pub mod link;
+pub mod session;
+pub mod stats;
 pub mod tag;
 pub mod user;
+pub mod user_settings;
 
 // Re-export commonly used types for convenience
 pub use category::{Category, CategoryWithChildren, CreateCategory};
+pub use idempotency_key::{IdempotencyKey, IdempotencyReservation};
 pub use language::Language;
 pub use license::License;
-pub use link::{CreateLink, Link, LinkSearchParams, LinkWithCategories, UpdateLink};
+pub use link::{
+    CreateLink, Link, LinkSearchParams, LinkWithCategories, SearchMatches, TimelineMonth,
+    UpdateLink,
+};
+pub use session::Session;
 pub use tag::Tag;
 pub use user::{
     check_user_exists, create_user, find_user_by_email, is_legacy_hash, upgrade_password_hash,
     verify_password, CreateUser, User,
 };
+pub use user_settings::{UpdateUserSettings, UserSettings};
+
+/// Tidy a tag or category name: trim it and collapse inner whitespace runs
+///
+/// `"  Web   Dev "` becomes `"Web Dev"`; a blank name becomes empty.
+pub fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Deduplicate names case-insensitively for the batched get-or-create helpers
+///
+/// Maps each lowercased name to its first normalized spelling (see
+/// `normalize_name`); blank names are dropped.
+pub(crate) fn unique_names(names: &[String]) -> std::collections::HashMap<String, String> {
+    let mut unique = std::collections::HashMap::new();
+    for name in names {
+        let name = normalize_name(name);
+        if !name.is_empty() {
+            unique.entry(name.to_lowercase()).or_insert(name);
+        }
+    }
+    unique
+}
+
+/// Begin a transaction whose statements Postgres cancels after `timeout_ms`
+///
+/// The limit is set with `SET LOCAL`, so it ends with the transaction and the
+/// pooled connection goes back without it. A cancelled statement surfaces as
+/// `AppError::Timeout`. A `timeout_ms` of 0 leaves the server default.
+pub async fn begin_with_statement_timeout(
+    pool: &sqlx::PgPool,
+    timeout_ms: u64,
+) -> Result<sqlx::Transaction<'static, sqlx::Postgres>, crate::error::AppError> {
+    let mut tx = pool.begin().await?;
+    if timeout_ms > 0 {
+        sqlx::query("SELECT set_config('statement_timeout', $1, true)")
+            .bind(format!("{}ms", timeout_ms))
+            .execute(&mut *tx)
+            .await?;
+    }
+    Ok(tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_names() {
+        let names = ["Rust", " rust ", "CLI", "", "  ", "Web  Dev", "web dev"].map(String::from);
+
+        let unique = unique_names(&names);
+
+        assert_eq!(unique.len(), 3);
+        assert_eq!(unique["rust"], "Rust");
+        assert_eq!(unique["cli"], "CLI");
+        assert_eq!(unique["web dev"], "Web Dev");
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name(" Rust "), "Rust");
+        assert_eq!(normalize_name("Web \t  Dev"), "Web Dev");
+        assert_eq!(normalize_name("RUST"), "RUST");
+        assert_eq!(normalize_name(" \n "), "");
+    }
+}
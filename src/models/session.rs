@@ -0,0 +1,31 @@
+//! Session model and database operations
+
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Server-side login session (`user_sessions` table)
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Session {
+    pub id: Uuid,
+    pub session_token_hash: Vec<u8>,
+    pub user_id: Uuid,
+    pub session_version: i32,
+    pub auth_via_oidc: bool,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    /// Delete sessions past their `expires_at`
+    ///
+    /// Returns the number of sessions removed.
+    pub async fn delete_expired(pool: &PgPool) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM user_sessions WHERE expires_at < NOW()")
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
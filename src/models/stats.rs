@@ -0,0 +1,44 @@
+//! Aggregate statistics over a user's links
+//!
+//! Read-only queries behind the dashboard endpoints under `/api/stats`.
+
+use crate::error::AppError;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Number of a user's links tagged with one programming language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct LanguageCount {
+    pub language: String,
+    pub count: i64,
+}
+
+/// Namespace for the statistics queries
+pub struct Stats;
+
+impl Stats {
+    /// Count a user's links per programming language
+    ///
+    /// Only languages attached to at least one of the user's links are
+    /// returned, most used first (ties by name). A link with several
+    /// languages counts once for each.
+    pub async fn by_language(pool: &PgPool, user_id: Uuid) -> Result<Vec<LanguageCount>, AppError> {
+        let counts = sqlx::query_as::<_, LanguageCount>(
+            r#"
+            SELECT lang.name AS language, COUNT(*) AS count
+            FROM link_languages ll
+            JOIN links l ON l.id = ll.link_id
+            JOIN languages lang ON lang.id = ll.language_id
+            WHERE l.user_id = $1
+            GROUP BY lang.id, lang.name
+            ORDER BY count DESC, lang.name
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(counts)
+    }
+}
@@ -1,13 +1,16 @@
 //! Tag model and database operations
 
+use super::{normalize_name, unique_names};
+use crate::config::Config;
 use crate::error::AppError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Tag entity
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct Tag {
     pub id: Uuid,
     pub user_id: Option<Uuid>,
@@ -23,7 +26,15 @@ pub struct CreateTag {
 
 impl Tag {
     /// Create a new tag
-    pub async fn create(pool: &PgPool, user_id: Uuid, name: &str) -> Result<Tag, AppError> {
+    ///
+    /// The name is normalized first (see `normalize_tag_name`).
+    pub async fn create(
+        pool: &PgPool,
+        config: &Config,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Tag, AppError> {
+        let name = validated_tag_name(name, config.normalize_tag_case)?;
         let tag = sqlx::query_as::<_, Tag>(
             r#"
             INSERT INTO tags (user_id, name)
@@ -63,40 +74,201 @@ impl Tag {
         Ok(tags)
     }
 
-    /// Get tag by name, or create it if it doesn't exist
+    /// Get a user's tags that aren't attached to any link, by name
+    pub async fn get_orphaned(pool: &PgPool, user_id: Uuid) -> Result<Vec<Tag>, AppError> {
+        let tags = sqlx::query_as::<_, Tag>(
+            r#"
+            SELECT * FROM tags t
+            WHERE t.user_id = $1
+              AND NOT EXISTS (SELECT 1 FROM link_tags lt WHERE lt.tag_id = t.id)
+            ORDER BY t.name
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    /// Delete a user's tags that aren't attached to any link
+    ///
+    /// Global tags are never deleted.
+    ///
+    /// # Returns
+    /// The number of tags deleted
+    pub async fn delete_orphaned(pool: &PgPool, user_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM tags t
+            WHERE t.user_id = $1
+              AND NOT EXISTS (SELECT 1 FROM link_tags lt WHERE lt.tag_id = t.id)
+            "#,
+        )
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            deleted = result.rows_affected(),
+            "Orphaned tags deleted"
+        );
+
+        Ok(result.rows_affected())
+    }
+
+    /// Find a global or user-owned tag by name, ignoring case
+    ///
+    /// Global tags take precedence over the user's own.
+    pub async fn find_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Tag>, AppError> {
+        let tag = sqlx::query_as::<_, Tag>(
+            r#"
+            SELECT * FROM tags
+            WHERE (user_id IS NULL OR user_id = $1) AND LOWER(name) = LOWER($2)
+            ORDER BY user_id NULLS FIRST
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    /// Get tag by name (case-insensitive), or create it if it doesn't exist
+    ///
+    /// "rust" resolves to an existing "Rust" tag instead of creating a
+    /// near-duplicate. The name is normalized first (see `normalize_tag_name`).
+    /// A tag created concurrently under another case is returned rather than
+    /// failing on the unique index.
     pub async fn get_or_create_by_name(
         pool: &PgPool,
+        config: &Config,
         user_id: Uuid,
         name: &str,
     ) -> Result<Tag, AppError> {
-        // Try to find existing tag with this name
-        let existing =
-            sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE user_id = $1 AND name = $2")
-                .bind(user_id)
-                .bind(name)
-                .fetch_optional(pool)
-                .await?;
-
-        if let Some(tag) = existing {
+        let name = validated_tag_name(name, config.normalize_tag_case)?;
+        let name = name.as_str();
+        if let Some(tag) = Self::find_by_name(pool, user_id, name).await? {
             return Ok(tag);
         }
 
-        // Create new tag
-        let tag = sqlx::query_as::<_, Tag>(
+        let created = sqlx::query_as::<_, Tag>(
             r#"
             INSERT INTO tags (user_id, name)
             VALUES ($1, $2)
+            ON CONFLICT (user_id, LOWER(name)) DO NOTHING
             RETURNING *
             "#,
         )
         .bind(user_id)
         .bind(name)
-        .fetch_one(pool)
+        .fetch_optional(pool)
         .await?;
 
-        tracing::info!(tag_id = %tag.id, name = %tag.name, "Tag created via import");
+        match created {
+            Some(tag) => {
+                tracing::info!(tag_id = %tag.id, name = %tag.name, "Tag created via import");
+                Ok(tag)
+            }
+            None => Self::find_by_name(pool, user_id, name)
+                .await?
+                .ok_or_else(|| AppError::not_found("tag", name)),
+        }
+    }
 
-        Ok(tag)
+    /// Get or create tags for many names at once
+    ///
+    /// Existing tags (global ones first) are fetched in one query and the
+    /// missing ones created in a single insert. Names are normalized (see
+    /// `normalize_tag_name`) and the map is keyed by the lowercased result,
+    /// matching the case-insensitive lookup of `get_or_create_by_name`; blank
+    /// names are skipped.
+    pub async fn get_or_create_many(
+        pool: &PgPool,
+        config: &Config,
+        user_id: Uuid,
+        names: &[String],
+    ) -> Result<HashMap<String, Tag>, AppError> {
+        let names: Vec<String> = names
+            .iter()
+            .map(|n| normalize_tag_name(n, config.normalize_tag_case))
+            .collect();
+        let wanted = unique_names(&names);
+        let keys: Vec<String> = wanted.keys().cloned().collect();
+
+        let mut tags = HashMap::new();
+        for tag in Self::find_many_by_name(pool, user_id, &keys).await? {
+            tags.entry(tag.name.to_lowercase()).or_insert(tag);
+        }
+
+        let missing: Vec<String> = wanted
+            .iter()
+            .filter(|(key, _)| !tags.contains_key(*key))
+            .map(|(_, name)| name.clone())
+            .collect();
+        if missing.is_empty() {
+            return Ok(tags);
+        }
+
+        let created = sqlx::query_as::<_, Tag>(
+            r#"
+            INSERT INTO tags (user_id, name)
+            SELECT $1, UNNEST($2::text[])
+            ON CONFLICT (user_id, LOWER(name)) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(&missing)
+        .fetch_all(pool)
+        .await?;
+        tracing::info!(count = created.len(), "Tags created via import");
+        for tag in created {
+            tags.insert(tag.name.to_lowercase(), tag);
+        }
+
+        // Names created concurrently under another case were skipped above
+        let raced: Vec<String> = missing
+            .iter()
+            .map(|name| name.to_lowercase())
+            .filter(|key| !tags.contains_key(key))
+            .collect();
+        if !raced.is_empty() {
+            for tag in Self::find_many_by_name(pool, user_id, &raced).await? {
+                tags.entry(tag.name.to_lowercase()).or_insert(tag);
+            }
+        }
+
+        Ok(tags)
+    }
+
+    /// Global and user-owned tags whose lowercased name is in `keys`, global first
+    async fn find_many_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        keys: &[String],
+    ) -> Result<Vec<Tag>, AppError> {
+        let tags = sqlx::query_as::<_, Tag>(
+            r#"
+            SELECT * FROM tags
+            WHERE (user_id IS NULL OR user_id = $1) AND LOWER(name) = ANY($2)
+            ORDER BY user_id NULLS FIRST
+            "#,
+        )
+        .bind(user_id)
+        .bind(keys)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tags)
     }
 
     /// Delete a tag
@@ -116,3 +288,49 @@ impl Tag {
         Ok(())
     }
 }
+
+/// Normalize a tag name as it is stored
+///
+/// Trims it and collapses inner whitespace, then lowercases it when
+/// `lowercase` (`NORMALIZE_TAG_CASE`) is on.
+pub fn normalize_tag_name(name: &str, lowercase: bool) -> String {
+    let name = normalize_name(name);
+    if lowercase {
+        name.to_lowercase()
+    } else {
+        name
+    }
+}
+
+/// Normalize a tag name, rejecting names left blank
+fn validated_tag_name(name: &str, lowercase: bool) -> Result<String, AppError> {
+    let name = normalize_tag_name(name, lowercase);
+    if name.is_empty() {
+        return Err(AppError::validation("name", "Tag name cannot be empty"));
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tag_name() {
+        assert_eq!(normalize_tag_name("  Rust  ", false), "Rust");
+        assert_eq!(normalize_tag_name("Web   Dev", false), "Web Dev");
+        assert_eq!(normalize_tag_name("RUST", false), "RUST");
+    }
+
+    #[test]
+    fn test_normalize_tag_name_lowercase() {
+        assert_eq!(normalize_tag_name("  RUST ", true), "rust");
+        assert_eq!(normalize_tag_name("Web   Dev", true), "web dev");
+    }
+
+    #[test]
+    fn test_validated_tag_name() {
+        assert_eq!(validated_tag_name(" Rust ", false).unwrap(), "Rust");
+        assert!(validated_tag_name("   ", false).is_err());
+    }
+}
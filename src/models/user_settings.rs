@@ -0,0 +1,184 @@
+//! Per-user settings model and database operations
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::models::Category;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// A user's preferences (`user_settings` table)
+///
+/// Users without a stored row get `UserSettings::default()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct UserSettings {
+    /// Category attached to new links created without any categories
+    pub default_category_id: Option<Uuid>,
+    /// A personal GitHub token is stored; the token itself is never returned
+    pub has_github_token: bool,
+}
+
+/// Columns selected into `UserSettings`
+const SETTINGS_COLUMNS: &str =
+    "default_category_id, github_token_encrypted IS NOT NULL AS has_github_token";
+
+/// Replacement settings; omitted fields are reset to their defaults
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateUserSettings {
+    #[serde(default)]
+    pub default_category_id: Option<Uuid>,
+}
+
+impl UserSettings {
+    /// Get a user's settings, falling back to defaults if none are stored
+    pub async fn get(pool: &PgPool, user_id: Uuid) -> Result<UserSettings, AppError> {
+        let settings = sqlx::query_as::<_, UserSettings>(&format!(
+            "SELECT {} FROM user_settings WHERE user_id = $1",
+            SETTINGS_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(settings.unwrap_or_default())
+    }
+
+    /// Store a user's settings
+    ///
+    /// The default category must belong to the user. The GitHub token is
+    /// managed separately and left unchanged.
+    pub async fn update(
+        pool: &PgPool,
+        user_id: Uuid,
+        update: UpdateUserSettings,
+    ) -> Result<UserSettings, AppError> {
+        if let Some(category_id) = update.default_category_id {
+            Category::get_by_id(pool, category_id, user_id)
+                .await
+                .map_err(|e| match e {
+                    AppError::NotFound { .. } => {
+                        AppError::validation("default_category_id", "Category not found")
+                    }
+                    other => other,
+                })?;
+        }
+
+        let settings = sqlx::query_as::<_, UserSettings>(&format!(
+            r#"
+            INSERT INTO user_settings (user_id, default_category_id)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET default_category_id = EXCLUDED.default_category_id, updated_at = NOW()
+            RETURNING {}
+            "#,
+            SETTINGS_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(update.default_category_id)
+        .fetch_one(pool)
+        .await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            default_category_id = ?settings.default_category_id,
+            "User settings updated"
+        );
+
+        Ok(settings)
+    }
+
+    /// Store a personal GitHub token for the user, encrypted at rest
+    ///
+    /// Requires `SETTINGS_ENCRYPTION_KEY`; replaces any previous token.
+    pub async fn set_github_token(
+        pool: &PgPool,
+        config: &Config,
+        user_id: Uuid,
+        token: &str,
+    ) -> Result<UserSettings, AppError> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(AppError::validation("token", "Token cannot be empty"));
+        }
+        if token.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(AppError::validation(
+                "token",
+                "Token cannot contain whitespace",
+            ));
+        }
+        let key = config.settings_encryption_key.as_deref().ok_or_else(|| {
+            AppError::validation(
+                "token",
+                "Personal GitHub tokens are not enabled on this server",
+            )
+        })?;
+        let encrypted = crate::security::encrypt_secret(key, token)?;
+
+        let settings = sqlx::query_as::<_, UserSettings>(&format!(
+            r#"
+            INSERT INTO user_settings (user_id, github_token_encrypted)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET github_token_encrypted = EXCLUDED.github_token_encrypted, updated_at = NOW()
+            RETURNING {}
+            "#,
+            SETTINGS_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(encrypted)
+        .fetch_one(pool)
+        .await?;
+
+        tracing::info!(user_id = %user_id, "Personal GitHub token stored");
+
+        Ok(settings)
+    }
+
+    /// Remove the user's personal GitHub token
+    pub async fn clear_github_token(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<UserSettings, AppError> {
+        sqlx::query(
+            "UPDATE user_settings SET github_token_encrypted = NULL, updated_at = NOW() WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(user_id = %user_id, "Personal GitHub token removed");
+
+        Self::get(pool, user_id).await
+    }
+
+    /// The user's decrypted GitHub token, if one is stored and readable
+    ///
+    /// A token that no longer decrypts (e.g. after the encryption key was
+    /// changed) is treated as absent so GitHub calls fall back to the
+    /// server-wide token.
+    pub async fn github_token(
+        pool: &PgPool,
+        config: &Config,
+        user_id: Uuid,
+    ) -> Result<Option<String>, AppError> {
+        let Some(key) = config.settings_encryption_key.as_deref() else {
+            return Ok(None);
+        };
+
+        let encrypted: Option<Vec<u8>> = sqlx::query_scalar(
+            "SELECT github_token_encrypted FROM user_settings WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+        Ok(encrypted.and_then(|encrypted| {
+            crate::security::decrypt_secret(key, &encrypted)
+                .inspect_err(|e| {
+                    tracing::warn!(user_id = %user_id, error = %e, "Ignoring unreadable GitHub token")
+                })
+                .ok()
+        }))
+    }
+}
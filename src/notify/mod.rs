@@ -0,0 +1,238 @@
+//! Notifications about newly broken links
+//!
+//! When `notify_email` is configured, the scheduler hands the links that broke
+//! during a cycle to a `BrokenLinkMailer`, which sends each affected user one
+//! plain-text summary over SMTP. The `webhook` submodule posts a signed cycle
+//! summary for chat integrations. Both are best-effort: failures are logged
+//! and never reported back to the scheduler.
+
+pub mod webhook;
+
+use crate::config::{Config, NotifyEmailConfig, SmtpTls};
+use crate::error::AppError;
+use crate::models::{Link, User};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How long a single SMTP exchange may take before it is abandoned
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sends broken-link summaries over SMTP
+pub struct BrokenLinkMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Option<Mailbox>,
+    host_url: String,
+}
+
+impl BrokenLinkMailer {
+    /// Build a mailer from the application configuration
+    ///
+    /// Returns `Ok(None)` when notifications are disabled. The SMTP server is
+    /// not contacted until the first email is sent.
+    pub fn from_config(config: &Config) -> Result<Option<Self>, AppError> {
+        config
+            .notify_email
+            .as_ref()
+            .map(|notify| Self::new(notify, &config.host_url))
+            .transpose()
+    }
+
+    fn new(notify: &NotifyEmailConfig, host_url: &str) -> Result<Self, AppError> {
+        let builder = match notify.smtp_tls {
+            SmtpTls::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&notify.smtp_host)
+            }
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&notify.smtp_host),
+            SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &notify.smtp_host,
+            )),
+        }
+        .map_err(|e| {
+            AppError::Configuration(format!("Invalid value for NOTIFY_EMAIL_SMTP_HOST: {}", e))
+        })?;
+
+        let mut builder = builder.port(notify.smtp_port).timeout(Some(SMTP_TIMEOUT));
+        if let (Some(username), Some(password)) = (&notify.smtp_username, &notify.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: parse_mailbox("NOTIFY_EMAIL_FROM", &notify.from)?,
+            to: notify
+                .to
+                .as_deref()
+                .map(|to| parse_mailbox("NOTIFY_EMAIL_TO", to))
+                .transpose()?,
+            host_url: host_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Email every user in `links` a summary of their broken links
+    ///
+    /// `links` must be grouped by user, as `Link::failed_between` returns
+    /// them. Each user's summary goes to `NOTIFY_EMAIL_TO` when set, otherwise
+    /// to their account email. Errors are logged per user and don't stop the
+    /// remaining summaries.
+    ///
+    /// # Returns
+    /// The number of emails sent
+    pub async fn send_summaries(&self, pool: &PgPool, links: &[Link]) -> usize {
+        let mut sent = 0;
+        for user_links in links.chunk_by(|a, b| a.user_id == b.user_id) {
+            let user_id = user_links[0].user_id;
+            match self.send_summary(pool, user_id, user_links).await {
+                Ok(()) => {
+                    sent += 1;
+                    tracing::info!(
+                        user_id = %user_id,
+                        count = user_links.len(),
+                        "Sent broken link summary"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        user_id = %user_id,
+                        error = %e,
+                        "Failed to send broken link summary"
+                    );
+                }
+            }
+        }
+        sent
+    }
+
+    async fn send_summary(
+        &self,
+        pool: &PgPool,
+        user_id: Uuid,
+        links: &[Link],
+    ) -> Result<(), AppError> {
+        let to = match &self.to {
+            Some(to) => to.clone(),
+            None => {
+                let user = User::find_by_id(pool, user_id)
+                    .await?
+                    .ok_or_else(|| AppError::not_found("user", &user_id.to_string()))?;
+                user.email.parse::<Mailbox>().map_err(|e| {
+                    AppError::Internal(format!("Invalid account email '{}': {}", user.email, e))
+                })?
+            }
+        };
+
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(summary_subject(links.len()))
+            .header(ContentType::TEXT_PLAIN)
+            .body(summary_body(links, &self.host_url))
+            .map_err(|e| AppError::Internal(format!("Failed to build email: {}", e)))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("SMTP: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Parse a configured address such as `Rusty Links <links@example.com>`
+fn parse_mailbox(var: &str, value: &str) -> Result<Mailbox, AppError> {
+    value
+        .parse()
+        .map_err(|e| AppError::Configuration(format!("Invalid value for {}: {}", var, e)))
+}
+
+fn summary_subject(count: usize) -> String {
+    if count == 1 {
+        "1 of your links is broken".to_string()
+    } else {
+        format!("{} of your links are broken", count)
+    }
+}
+
+/// Plain-text list of the broken links, one per line with its new status
+fn summary_body(links: &[Link], host_url: &str) -> String {
+    let mut body = String::from("These links stopped working since the last check:\n\n");
+    for link in links {
+        let status = match link.status.as_str() {
+            "inaccessible" => "unreachable",
+            "repo_unavailable" => "repository unavailable",
+            "repo_takedown" => "repository taken down",
+            other => other,
+        };
+        match link.title.as_deref().filter(|t| !t.is_empty()) {
+            Some(title) => body.push_str(&format!("- {} <{}> ({})\n", title, link.url, status)),
+            None => body.push_str(&format!("- {} ({})\n", link.url, status)),
+        }
+    }
+    body.push_str(&format!("\nReview them at {}/links\n", host_url));
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn broken_link(url: &str, title: Option<&str>, status: &str) -> Link {
+        let now = chrono::Utc::now();
+        Link {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            url: url.to_string(),
+            domain: "example.com".to_string(),
+            domain_display: None,
+            path: None,
+            title: title.map(str::to_string),
+            description: None,
+            logo: None,
+            source_code_url: None,
+            documentation_url: None,
+            notes: None,
+            is_github_repo: false,
+            github_stars: None,
+            github_archived: None,
+            github_last_commit: None,
+            github_owner_type: None,
+            status: status.to_string(),
+            consecutive_failures: 3,
+            refreshed_at: None,
+            last_checked: Some(now),
+            created_at: now,
+            updated_at: now,
+            final_url: None,
+            renamed_from: None,
+            github_refreshed_at: None,
+            is_pinned: false,
+            last_visited_at: None,
+            title_manually_edited: false,
+            http_etag: None,
+            http_last_modified: None,
+            status_changed_at: Some(now),
+        }
+    }
+
+    #[test]
+    fn test_summary_subject() {
+        assert_eq!(summary_subject(1), "1 of your links is broken");
+        assert_eq!(summary_subject(3), "3 of your links are broken");
+    }
+
+    #[test]
+    fn test_summary_body_lists_links_with_status() {
+        let links = vec![
+            broken_link("https://example.com/a", Some("Example A"), "inaccessible"),
+            broken_link("https://github.com/o/r", None, "repo_takedown"),
+        ];
+        let body = summary_body(&links, "https://links.example.com");
+        assert!(body.contains("- Example A <https://example.com/a> (unreachable)\n"));
+        assert!(body.contains("- https://github.com/o/r (repository taken down)\n"));
+        assert!(body.ends_with("Review them at https://links.example.com/links\n"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Signed webhook summarizing each scheduler cycle
+//!
+//! When `webhook_url` is configured, the scheduler POSTs the links that broke
+//! or recovered during a cycle as JSON. The body is signed with HMAC-SHA256
+//! using `webhook_url_secret` and the hex digest is sent in the
+//! `X-Webhook-Signature` header, the same scheme the inbound maintenance
+//! webhook verifies.
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::models::link::FAILED_STATUSES;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying the hex HMAC-SHA256 of the request body
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// How long the receiver gets to answer before the delivery is abandoned
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A status change the scheduler made to a link
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LinkStatusChange {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub url: String,
+    pub title: Option<String>,
+    pub previous_status: String,
+    pub status: String,
+}
+
+/// JSON body POSTed to `webhook_url` after a scheduler cycle
+#[derive(Debug, Serialize)]
+pub struct CycleSummary {
+    /// Always `scheduler_cycle_completed`
+    pub event: &'static str,
+    pub timestamp: DateTime<Utc>,
+    /// One-line summary, shown by chat tools that read a `text` field
+    pub text: String,
+    /// Links that moved from a working to a failed status
+    pub newly_broken: Vec<LinkStatusChange>,
+    /// Links that moved from a failed status back to `active`
+    pub recovered: Vec<LinkStatusChange>,
+}
+
+impl CycleSummary {
+    /// Summarize a cycle's status changes
+    ///
+    /// Returns `None` when no link broke or recovered, so quiet cycles don't
+    /// post anything.
+    pub fn from_changes(changes: Vec<LinkStatusChange>, timestamp: DateTime<Utc>) -> Option<Self> {
+        let is_failed = |status: &str| FAILED_STATUSES.contains(&status);
+
+        let (newly_broken, rest): (Vec<_>, Vec<_>) = changes
+            .into_iter()
+            .partition(|c| is_failed(&c.status) && !is_failed(&c.previous_status));
+        let recovered: Vec<_> = rest
+            .into_iter()
+            .filter(|c| c.status == "active" && is_failed(&c.previous_status))
+            .collect();
+
+        if newly_broken.is_empty() && recovered.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            event: "scheduler_cycle_completed",
+            timestamp,
+            text: format!(
+                "Rusty Links: {} link(s) broke, {} recovered",
+                newly_broken.len(),
+                recovered.len()
+            ),
+            newly_broken,
+            recovered,
+        })
+    }
+}
+
+/// Posts cycle summaries to the configured webhook
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    secret: String,
+}
+
+impl WebhookNotifier {
+    /// Build a notifier from the application configuration
+    ///
+    /// Returns `Ok(None)` when no `webhook_url` is configured.
+    pub fn from_config(config: &Config) -> Result<Option<Self>, AppError> {
+        let (Some(url), Some(secret)) = (&config.webhook_url, &config.webhook_url_secret) else {
+            return Ok(None);
+        };
+
+        let client = crate::security::outbound_client_builder(config)
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                AppError::Configuration(format!("Failed to build webhook HTTP client: {}", e))
+            })?;
+
+        Ok(Some(Self {
+            client,
+            url: url.clone(),
+            secret: secret.clone(),
+        }))
+    }
+
+    /// POST a summary to the webhook
+    ///
+    /// Any non-2xx answer is reported as an error; nothing is retried.
+    pub async fn send(&self, summary: &CycleSummary) -> Result<(), AppError> {
+        let body = serde_json::to_vec(summary)?;
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign(&self.secret, &body))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Webhook delivery failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Webhook answered {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Hex HMAC-SHA256 of `body` keyed with `secret`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(url: &str, previous_status: &str, status: &str) -> LinkStatusChange {
+        LinkStatusChange {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            url: url.to_string(),
+            title: None,
+            previous_status: previous_status.to_string(),
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cycle_summary_classifies_changes() {
+        let summary = CycleSummary::from_changes(
+            vec![
+                change("https://a.example", "active", "inaccessible"),
+                change("https://b.example", "inaccessible", "active"),
+                change("https://c.example", "active", "private_repo"),
+                change("https://d.example", "inaccessible", "repo_unavailable"),
+            ],
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert_eq!(summary.newly_broken.len(), 1);
+        assert_eq!(summary.newly_broken[0].url, "https://a.example");
+        assert_eq!(summary.recovered.len(), 1);
+        assert_eq!(summary.recovered[0].url, "https://b.example");
+
+        assert!(CycleSummary::from_changes(
+            vec![change("https://c.example", "active", "archived")],
+            Utc::now()
+        )
+        .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_send_posts_signed_payload() {
+        use axum::{body::Bytes, http::HeaderMap, routing::post, Router};
+        use tokio::sync::mpsc;
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let tx = tx.clone();
+                async move {
+                    let signature = headers
+                        .get(SIGNATURE_HEADER)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string);
+                    tx.send((signature, body)).unwrap();
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let notifier = WebhookNotifier {
+            client: reqwest::Client::new(),
+            url,
+            secret: "s3cret".to_string(),
+        };
+        let summary = CycleSummary::from_changes(
+            vec![change("https://a.example", "active", "inaccessible")],
+            Utc::now(),
+        )
+        .unwrap();
+        notifier.send(&summary).await.unwrap();
+
+        let (signature, body) = rx.recv().await.unwrap();
+        let mut mac = HmacSha256::new_from_slice(b"s3cret").unwrap();
+        mac.update(&body);
+        mac.verify_slice(&hex::decode(signature.unwrap()).unwrap())
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["event"], "scheduler_cycle_completed");
+        assert_eq!(json["text"], "Rusty Links: 1 link(s) broke, 0 recovered");
+        assert!(json["timestamp"].is_string());
+        assert_eq!(json["recovered"], serde_json::json!([]));
+        let broken = &json["newly_broken"][0];
+        assert_eq!(broken["url"], "https://a.example");
+        assert_eq!(broken["previous_status"], "active");
+        assert_eq!(broken["status"], "inaccessible");
+        assert!(broken["title"].is_null());
+        assert!(broken["id"].is_string());
+        assert!(broken["user_id"].is_string());
+    }
+}
@@ -5,15 +5,26 @@
 
 use crate::config::Config;
 use crate::error::AppError;
+use crate::events::{EventBus, LinkEvent};
 use crate::github;
-use crate::models::Link;
+use crate::models::link::UrlParts;
+use crate::models::{IdempotencyKey, Link, Session};
+use crate::notify::webhook::{CycleSummary, LinkStatusChange, WebhookNotifier};
+use crate::notify::BrokenLinkMailer;
 use crate::scraper;
 use crate::security;
+use chrono::{DateTime, Utc};
 use rand::Rng;
 use sqlx::PgPool;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Backlog multiple of `batch_size` above which the batch is raised
+const BACKLOG_MULTIPLIER: usize = 4;
+
+/// How often daily maintenance (session cleanup) runs
+const DAILY_TASK_INTERVAL: Duration = Duration::from_secs(24 * 3600);
 
 /// Background task scheduler
 ///
@@ -24,7 +35,7 @@ use std::time::Duration;
 ///
 /// # Example
 /// ```rust
-/// let scheduler = Scheduler::new(pool.clone(), config.clone());
+/// let scheduler = Scheduler::new(pool.clone(), config.clone(), EventBus::new());
 /// let handle = scheduler.start();
 /// // Scheduler now runs in background
 /// ```
@@ -32,6 +43,16 @@ pub struct Scheduler {
     pool: PgPool,
     config: Config,
     shutdown: Arc<AtomicBool>,
+    effective_batch_size: Arc<AtomicUsize>,
+    last_session_cleanup: Mutex<Option<Instant>>,
+    events: EventBus,
+    mailer: Option<Arc<BrokenLinkMailer>>,
+    /// End of the window covered by the last broken-link notice
+    last_failure_notice: Mutex<DateTime<Utc>>,
+    webhook: Option<Arc<WebhookNotifier>>,
+    /// Status changes made since the last webhook post; only kept when the
+    /// webhook is configured
+    cycle_changes: Mutex<Vec<LinkStatusChange>>,
 }
 
 impl Scheduler {
@@ -40,11 +61,35 @@ impl Scheduler {
     /// # Arguments
     /// * `pool` - Database connection pool
     /// * `config` - Application configuration
-    pub fn new(pool: PgPool, config: Config) -> Self {
+    /// * `events` - Bus on which link status changes are published
+    pub fn new(pool: PgPool, config: Config, events: EventBus) -> Self {
+        let mailer = match BrokenLinkMailer::from_config(&config) {
+            Ok(mailer) => mailer.map(Arc::new),
+            Err(e) => {
+                tracing::error!(error = %e, "Broken link emails disabled");
+                None
+            }
+        };
+
+        let webhook = match WebhookNotifier::from_config(&config) {
+            Ok(webhook) => webhook.map(Arc::new),
+            Err(e) => {
+                tracing::error!(error = %e, "Scheduler webhook disabled");
+                None
+            }
+        };
+
         Self {
             pool,
+            effective_batch_size: Arc::new(AtomicUsize::new(config.batch_size)),
             config,
             shutdown: Arc::new(AtomicBool::new(false)),
+            last_session_cleanup: Mutex::new(None),
+            events,
+            mailer,
+            last_failure_notice: Mutex::new(Utc::now()),
+            webhook,
+            cycle_changes: Mutex::new(Vec::new()),
         }
     }
 
@@ -53,6 +98,11 @@ impl Scheduler {
         Arc::clone(&self.shutdown)
     }
 
+    /// Get a handle to the batch size used by the most recent cycle
+    pub fn batch_size_handle(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.effective_batch_size)
+    }
+
     /// Start the scheduler in a background task
     ///
     /// This spawns a new tokio task that runs the scheduler loop.
@@ -76,9 +126,15 @@ impl Scheduler {
             update_interval_hours = self.config.update_interval_hours,
             batch_size = self.config.batch_size,
             jitter_percent = self.config.jitter_percent,
+            run_on_startup = self.config.run_on_startup,
             "Background scheduler started"
         );
 
+        if self.config.run_on_startup && !self.run_startup_pass().await {
+            tracing::info!("Scheduler stopped");
+            return;
+        }
+
         loop {
             // Calculate interval with jitter
             let base_interval_secs = self.config.update_interval_hours as u64 * 3600;
@@ -126,6 +182,33 @@ impl Scheduler {
         tracing::info!("Scheduler stopped");
     }
 
+    /// Run the tasks once after `initial_check_delay_secs`
+    ///
+    /// Returns `false` if shutdown was requested during the delay.
+    async fn run_startup_pass(&self) -> bool {
+        let delay = Duration::from_secs(self.config.initial_check_delay_secs);
+        tracing::debug!(delay_secs = delay.as_secs(), "Waiting before startup pass");
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = self.wait_for_shutdown() => {
+                tracing::info!("Scheduler shutting down gracefully");
+                return false;
+            }
+        }
+
+        if self.shutdown.load(Ordering::Relaxed) {
+            tracing::info!("Scheduler shutdown signal received");
+            return false;
+        }
+
+        tracing::debug!("Running startup pass...");
+        if let Err(e) = self.run_tasks().await {
+            tracing::error!(error = %e, "Startup task pass failed");
+        }
+        true
+    }
+
     /// Wait for shutdown signal
     async fn wait_for_shutdown(&self) {
         while !self.shutdown.load(Ordering::Relaxed) {
@@ -138,15 +221,153 @@ impl Scheduler {
     /// This function is called periodically by the scheduler loop.
     /// Currently implements:
     /// - Refresh stale link metadata (web scraping + GitHub)
-    /// - Clean up old login attempts and expired refresh tokens
+    /// - Archive links left unvisited, when `auto_archive_unvisited_days` is set
+    /// - Email users about newly broken links, when `notify_email` is set
+    /// - Post links that broke or recovered to `webhook_url`, when set
+    /// - Clean up old login attempts, expired refresh tokens and idempotency keys
+    /// - Purge expired sessions (at most once per day)
     async fn run_tasks(&self) -> Result<(), AppError> {
         self.refresh_stale_links().await?;
+        if let Some(days) = self.config.auto_archive_unvisited_days {
+            self.archive_unvisited_links(days).await;
+        }
+        self.notify_new_failures().await;
+        self.post_cycle_webhook();
         self.cleanup_expired_data().await;
+
+        let now = Instant::now();
+        let sessions_due = {
+            let mut last = self
+                .last_session_cleanup
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let due = is_due(*last, now, DAILY_TASK_INTERVAL);
+            if due {
+                *last = Some(now);
+            }
+            due
+        };
+        if sessions_due {
+            self.cleanup_sessions().await;
+        }
+
         Ok(())
     }
 
-    /// Clean up old login attempts and expired refresh tokens
+    /// Archive active, unpinned links not visited within `days` days
+    async fn archive_unvisited_links(&self, days: u32) {
+        match Link::archive_unvisited(&self.pool, days).await {
+            Ok(links) => {
+                for link in &links {
+                    tracing::info!(
+                        link_id = %link.id,
+                        user_id = %link.user_id,
+                        url = %link.url,
+                        last_visited_at = ?link.last_visited_at,
+                        "Auto-archived unvisited link"
+                    );
+                    self.publish_status_change(link, "archived");
+                }
+                if !links.is_empty() {
+                    tracing::info!(count = links.len(), days, "Auto-archived unvisited links");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to auto-archive unvisited links");
+            }
+        }
+    }
+
+    /// Email users about links that broke since the previous notice
+    ///
+    /// Only the query runs here; the emails are sent from a separate task so a
+    /// slow or unreachable SMTP server never holds up the scheduler. When the
+    /// query fails the window is kept and covered by the next cycle.
+    async fn notify_new_failures(&self) {
+        let Some(mailer) = &self.mailer else {
+            return;
+        };
+
+        let until = Utc::now();
+        let since = *self
+            .last_failure_notice
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let links = match Link::failed_between(&self.pool, since, until).await {
+            Ok(links) => links,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to list newly broken links");
+                return;
+            }
+        };
+        *self
+            .last_failure_notice
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = until;
+
+        if links.is_empty() {
+            return;
+        }
+        let mailer = Arc::clone(mailer);
+        let pool = self.pool.clone();
+        tokio::spawn(async move {
+            mailer.send_summaries(&pool, &links).await;
+        });
+    }
+
+    /// Post the status changes of the cycle to the webhook, if any
+    ///
+    /// Fire-and-forget: the request runs in its own task with a short timeout
+    /// and a failed delivery is only logged.
+    fn post_cycle_webhook(&self) {
+        let Some(webhook) = &self.webhook else {
+            return;
+        };
+
+        let changes =
+            std::mem::take(&mut *self.cycle_changes.lock().unwrap_or_else(|e| e.into_inner()));
+        let Some(summary) = CycleSummary::from_changes(changes, Utc::now()) else {
+            return;
+        };
+
+        let webhook = Arc::clone(webhook);
+        tokio::spawn(async move {
+            match webhook.send(&summary).await {
+                Ok(()) => tracing::info!(
+                    newly_broken = summary.newly_broken.len(),
+                    recovered = summary.recovered.len(),
+                    "Posted scheduler webhook"
+                ),
+                Err(e) => tracing::warn!(error = %e, "Failed to post scheduler webhook"),
+            }
+        });
+    }
+
+    /// Delete login sessions past their expiry
+    async fn cleanup_sessions(&self) {
+        match Session::delete_expired(&self.pool).await {
+            Ok(count) => {
+                tracing::info!(count, "Purged expired sessions");
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to purge expired sessions");
+            }
+        }
+    }
+
+    /// Clean up old login attempts, expired refresh tokens and idempotency keys
     async fn cleanup_expired_data(&self) {
+        match IdempotencyKey::delete_expired(&self.pool).await {
+            Ok(count) => {
+                if count > 0 {
+                    tracing::info!(count, "Cleaned up expired idempotency keys");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to clean up expired idempotency keys");
+            }
+        }
+
         match security::cleanup_old_login_attempts(&self.pool, 30).await {
             Ok(count) => {
                 if count > 0 {
@@ -183,22 +404,6 @@ impl Scheduler {
                 }
                 _ => {}
             }
-
-            match sqlx::query("DELETE FROM user_sessions WHERE expires_at < NOW()")
-                .execute(&self.pool)
-                .await
-            {
-                Ok(r) if r.rows_affected() > 0 => {
-                    tracing::info!(
-                        count = r.rows_affected(),
-                        "Cleaned up expired user_sessions"
-                    );
-                }
-                Err(e) => {
-                    tracing::warn!(error = %e, "Failed to clean up user_sessions");
-                }
-                _ => {}
-            }
         }
     }
 
@@ -210,13 +415,41 @@ impl Scheduler {
     /// For each link:
     /// - Scrapes metadata from the URL
     /// - If GitHub repo, fetches GitHub metadata
-    /// - Updates last_checked timestamp
+    ///
+    /// The last_checked timestamp of the whole batch is then updated at once.
     async fn refresh_stale_links(&self) -> Result<(), AppError> {
+        // Log the backlog so it's visible whether batch_size keeps up
+        let due =
+            match Link::count_needing_check(&self.pool, self.config.update_interval_hours).await {
+                Ok(due) => Some(due),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to count links due for checking");
+                    None
+                }
+            };
+
+        let batch_size = match due {
+            Some(due) => effective_batch_size(
+                self.config.batch_size,
+                self.config.max_batch_size,
+                due as usize,
+            ),
+            None => self.config.batch_size,
+        };
+        self.effective_batch_size
+            .store(batch_size, Ordering::Relaxed);
+
+        tracing::info!(
+            due = ?due,
+            batch_size,
+            "Links due for checking"
+        );
+
         // Get links that need checking
         let links_to_check = Link::get_links_needing_check(
             &self.pool,
             self.config.update_interval_hours,
-            self.config.batch_size as i64,
+            batch_size as i64,
         )
         .await?;
 
@@ -232,19 +465,14 @@ impl Scheduler {
 
         let mut successful = 0;
         let mut failed = 0;
+        // Every link is marked as checked regardless of outcome, so failing
+        // links aren't retried on every cycle; flushed once below
+        let mut checked_ids = Vec::with_capacity(links_to_check.len());
 
         for link in links_to_check {
             match self.refresh_single_link(&link).await {
                 Ok(()) => {
                     successful += 1;
-                    // Mark as checked regardless of outcome
-                    if let Err(e) = Link::mark_checked(&self.pool, link.id).await {
-                        tracing::error!(
-                            link_id = %link.id,
-                            error = %e,
-                            "Failed to mark link as checked"
-                        );
-                    }
                 }
                 Err(e) => {
                     failed += 1;
@@ -254,34 +482,72 @@ impl Scheduler {
                         error = %e,
                         "Failed to refresh link"
                     );
-                    // Still mark as checked to avoid repeatedly failing on the same link
-                    if let Err(mark_err) = Link::mark_checked(&self.pool, link.id).await {
-                        tracing::error!(
-                            link_id = %link.id,
-                            error = %mark_err,
-                            "Failed to mark link as checked after error"
-                        );
-                    }
                 }
             }
+            checked_ids.push(link.id);
+        }
+
+        // One UPDATE for the batch instead of one per link
+        if let Err(e) = Link::mark_checked_bulk(&self.pool, &checked_ids).await {
+            tracing::error!(
+                count = checked_ids.len(),
+                error = %e,
+                "Failed to mark links as checked"
+            );
         }
 
         tracing::info!(
             successful,
             failed,
             total = successful + failed,
+            mark_checked_statements_saved = checked_ids.len().saturating_sub(1),
             "Link check cycle completed"
         );
 
         Ok(())
     }
 
+    /// Set a link's status and notify the owner's live clients if it changed
+    async fn update_status(&self, link: &Link, status: &str) -> Result<(), AppError> {
+        Link::update_status(&self.pool, link.id, status).await?;
+        self.publish_status_change(link, status);
+        Ok(())
+    }
+
+    /// Tell the owner's live clients about a status change and note it for
+    /// the cycle webhook
+    fn publish_status_change(&self, link: &Link, status: &str) {
+        if link.status != status {
+            self.events.publish(
+                link.user_id,
+                LinkEvent::LinkStatusChanged {
+                    id: link.id,
+                    status: status.to_string(),
+                },
+            );
+            if self.webhook.is_some() {
+                self.cycle_changes
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(LinkStatusChange {
+                        id: link.id,
+                        user_id: link.user_id,
+                        url: link.url.clone(),
+                        title: link.title.clone(),
+                        previous_status: link.status.clone(),
+                        status: status.to_string(),
+                    });
+            }
+        }
+    }
+
     /// Refresh a single link's metadata
     ///
     /// Performs the following steps:
     /// 1. Checks URL health (accessibility)
     /// 2. Updates status if inaccessible or restores to active if recovered
-    /// 3. Scrapes URL for title, description, favicon (if healthy)
+    /// 3. Scrapes URL for title, description, favicon (if healthy), keeping the
+    ///    stored favicon unless it no longer validates and a replacement is found
     /// 4. If GitHub repo, fetches GitHub metadata (stars, archived, etc.)
     /// 5. Marks link as refreshed with current timestamp
     ///
@@ -294,16 +560,17 @@ impl Scheduler {
         tracing::debug!(link_id = %link.id, url = %link.url, "Refreshing link");
 
         // Check if URL is accessible
-        let is_healthy = scraper::check_url_health(&link.url).await?;
+        let is_healthy = scraper::check_url_health(&link.url, &self.config).await?;
 
         if !is_healthy {
+            let (status, consecutive_failures) = Link::record_failure(&self.pool, link.id).await?;
             tracing::warn!(
                 link_id = %link.id,
                 url = %link.url,
-                consecutive_failures = link.consecutive_failures + 1,
-                "Link is not accessible, recording failure"
+                consecutive_failures = consecutive_failures,
+                "Link is not accessible, recorded failure"
             );
-            Link::record_failure(&self.pool, link.id).await?;
+            self.publish_status_change(link, &status);
             Link::mark_refreshed(&self.pool, link.id, link.user_id).await?;
             return Ok(());
         }
@@ -318,32 +585,78 @@ impl Scheduler {
                 url = %link.url,
                 "Link is now accessible, restoring to active status"
             );
-            Link::update_status(&self.pool, link.id, "active").await?;
+            self.update_status(link, "active").await?;
         }
 
-        // Scrape metadata
-        let metadata = scraper::scrape_url(&link.url).await?;
-        Link::update_scraped_metadata(&self.pool, link.id, link.user_id, metadata).await?;
+        // Scrape metadata, unless the page hasn't changed since the last scrape
+        let validators = scraper::PageValidators {
+            etag: link.http_etag.clone(),
+            last_modified: link.http_last_modified.clone(),
+        };
+        match scraper::scrape_url_if_modified(&link.url, &self.config, &validators).await? {
+            Some(metadata) => self.apply_scraped_metadata(link, metadata).await?,
+            None => {
+                tracing::debug!(link_id = %link.id, "Page not modified, skipping metadata update");
+            }
+        }
 
-        // Refresh GitHub metadata if applicable
-        if link.is_github_repo {
-            if let Some((owner, repo)) = github::parse_repo_from_url(&link.url) {
-                match github::fetch_repo_metadata(&owner, &repo).await {
+        // Refresh GitHub metadata if applicable and not fetched recently
+        let github_due = github_refresh_due(
+            link.github_refreshed_at,
+            Utc::now(),
+            self.config.github_refresh_interval_hours,
+        );
+        if link.is_github_repo && !github_due {
+            tracing::debug!(
+                link_id = %link.id,
+                github_refreshed_at = ?link.github_refreshed_at,
+                "Skipping GitHub metadata refresh, fetched recently"
+            );
+        }
+        if link.is_github_repo && github_due {
+            if let Some((owner, repo)) = self.config.github.parse_repo_from_url(&link.url) {
+                match github::fetch_repo_metadata(&self.config, &owner, &repo).await {
                     Ok(gh_meta) => {
-                        Link::update_github_metadata(&self.pool, link.id, link.user_id, gh_meta)
-                            .await?;
+                        Link::update_github_metadata(
+                            &self.pool,
+                            &self.config,
+                            link.id,
+                            link.user_id,
+                            gh_meta,
+                        )
+                        .await?;
                     }
                     Err(e) => {
                         // Check if GitHub repo is unavailable (404, etc.)
                         let error_msg = e.to_string();
-                        if error_msg.contains("404") || error_msg.contains("Not Found") {
+                        if matches!(e, AppError::Forbidden(_)) {
+                            // Private repos won't become readable by retrying,
+                            // and private_repo links aren't selected for checks
+                            tracing::warn!(
+                                link_id = %link.id,
+                                url = %link.url,
+                                error = %e,
+                                "GitHub repository is private, marking as private_repo"
+                            );
+                            self.update_status(link, "private_repo").await?;
+                        } else if matches!(e, AppError::UnavailableForLegalReasons(_)) {
+                            // Takedowns are lasting too, and repo_takedown links
+                            // aren't selected for checks either
+                            tracing::warn!(
+                                link_id = %link.id,
+                                url = %link.url,
+                                error = %e,
+                                "GitHub repository was taken down, marking as repo_takedown"
+                            );
+                            self.update_status(link, "repo_takedown").await?;
+                        } else if error_msg.contains("404") || error_msg.contains("Not Found") {
                             tracing::warn!(
                                 link_id = %link.id,
                                 url = %link.url,
                                 error = %e,
                                 "GitHub repository not found, marking as repo_unavailable"
                             );
-                            Link::update_status(&self.pool, link.id, "repo_unavailable").await?;
+                            self.update_status(link, "repo_unavailable").await?;
                         } else {
                             tracing::warn!(
                                 link_id = %link.id,
@@ -364,6 +677,237 @@ impl Scheduler {
 
         Ok(())
     }
+
+    /// Store freshly scraped metadata for a link, keeping its favicon if still valid
+    async fn apply_scraped_metadata(
+        &self,
+        link: &Link,
+        metadata: scraper::ScrapedMetadata,
+    ) -> Result<(), AppError> {
+        // Keep the stored favicon while it still resolves to an image; otherwise
+        // take the freshly scraped candidate, if one validated
+        let existing_logo_valid = match link.logo.as_deref() {
+            // With favicon scraping off there is no candidate to replace it with
+            Some(_) if !self.config.scrape_favicon => true,
+            Some(logo) => scraper::validate_image_url(logo, &self.config)
+                .await
+                .unwrap_or(false),
+            None => false,
+        };
+        if link.logo.is_some() && !existing_logo_valid {
+            tracing::info!(
+                link_id = %link.id,
+                found_replacement = metadata.favicon.is_some(),
+                "Stored favicon is no longer valid"
+            );
+        }
+        let metadata = with_stored_logo(metadata, link.logo.clone(), existing_logo_valid);
+
+        // Automatic refreshes never overwrite the user's edits
+        Link::update_scraped_metadata(&self.pool, link.id, link.user_id, metadata, false).await?;
+
+        Ok(())
+    }
+}
+
+/// Outcome of `reclassify_github_repos`
+#[derive(Debug, Default, serde::Serialize)]
+pub struct GitHubReclassification {
+    /// Links checked
+    pub checked: usize,
+    /// Links now recognized as GitHub repositories, as `(link_id, user_id)`
+    pub now_github: Vec<(uuid::Uuid, uuid::Uuid)>,
+    /// Links no longer recognized as GitHub repositories
+    pub no_longer_github: usize,
+}
+
+impl GitHubReclassification {
+    /// Links whose flag changed
+    pub fn reclassified(&self) -> usize {
+        self.now_github.len() + self.no_longer_github
+    }
+}
+
+/// Re-evaluate `is_github_repo` for every link with the current detection
+///
+/// One-shot maintenance for links saved before GitHub detection (or the
+/// configured GitHub host) changed. Only the flag is updated; callers fetch
+/// metadata for the links in `now_github`. Metadata of links that stopped
+/// being repositories is kept.
+pub async fn reclassify_github_repos(
+    pool: &PgPool,
+    config: &Config,
+) -> Result<GitHubReclassification, AppError> {
+    let links = Link::github_flags(pool).await?;
+    let checked = links.len();
+    let (now_github, no_longer_github) = github_flag_changes(links, &config.github);
+
+    let github_ids: Vec<_> = now_github.iter().map(|(id, _)| *id).collect();
+    Link::set_github_repo_flag(pool, &github_ids, true).await?;
+    let no_longer_github =
+        Link::set_github_repo_flag(pool, &no_longer_github, false).await? as usize;
+
+    tracing::info!(
+        checked,
+        now_github = now_github.len(),
+        no_longer_github,
+        "Reclassified GitHub repository links"
+    );
+
+    Ok(GitHubReclassification {
+        checked,
+        now_github,
+        no_longer_github,
+    })
+}
+
+/// Split `(id, user_id, url, is_github_repo)` rows whose flag disagrees with
+/// `github.is_github_repo` into newly detected `(id, user_id)` pairs and ids
+/// no longer detected
+fn github_flag_changes(
+    links: Vec<(uuid::Uuid, uuid::Uuid, String, bool)>,
+    github: &github::GitHubHost,
+) -> (Vec<(uuid::Uuid, uuid::Uuid)>, Vec<uuid::Uuid>) {
+    let mut now_github = Vec::new();
+    let mut no_longer_github = Vec::new();
+
+    for (id, user_id, url, flagged) in links {
+        match (flagged, github.is_github_repo(&url)) {
+            (false, true) => now_github.push((id, user_id)),
+            (true, false) => no_longer_github.push(id),
+            _ => {}
+        }
+    }
+
+    (now_github, no_longer_github)
+}
+
+/// Links read and corrected per batch by `backfill_url_parts`
+const URL_PARTS_BATCH_SIZE: i64 = 500;
+
+/// Outcome of `backfill_url_parts`
+#[derive(Debug, Default, serde::Serialize)]
+pub struct UrlPartsBackfill {
+    /// Links checked
+    pub checked: usize,
+    /// Links whose domain or path was corrected
+    pub fixed: usize,
+    /// Links whose URL no longer parses (left unchanged)
+    pub unparseable: usize,
+    /// Links left unchanged because the corrected domain and path belong to
+    /// another of the owner's links
+    pub conflicts: usize,
+}
+
+/// Re-derive every link's `domain`, `domain_display` and `path` from its URL
+///
+/// One-shot maintenance for rows saved by older versions or buggy imports.
+/// Links are read and fixed `URL_PARTS_BATCH_SIZE` at a time, each with its
+/// own short update, so no long-running lock is held. URLs that no longer
+/// parse are logged and skipped.
+pub async fn backfill_url_parts(
+    pool: &PgPool,
+    config: &Config,
+) -> Result<UrlPartsBackfill, AppError> {
+    let mut result = UrlPartsBackfill::default();
+    let mut after = None;
+
+    loop {
+        let rows = Link::url_parts_page(pool, after, URL_PARTS_BATCH_SIZE).await?;
+        let Some(last) = rows.last() else {
+            break;
+        };
+        after = Some(last.id);
+        result.checked += rows.len();
+
+        for row in rows {
+            let Some(parts) = UrlParts::from_url(&row.url, config) else {
+                tracing::warn!(
+                    link_id = %row.id,
+                    url = %security::mask_sensitive_query_params(&row.url, &config.log_masked_query_params),
+                    "Link URL no longer parses, leaving domain and path unchanged"
+                );
+                result.unparseable += 1;
+                continue;
+            };
+            if row.matches(&parts) {
+                continue;
+            }
+
+            match Link::set_url_parts(pool, row.id, &parts).await {
+                Ok(()) => result.fixed += 1,
+                Err(AppError::Duplicate { .. }) => {
+                    tracing::warn!(
+                        link_id = %row.id,
+                        domain = %parts.domain,
+                        path = ?parts.path,
+                        "Corrected domain and path belong to another link, skipping"
+                    );
+                    result.conflicts += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    tracing::info!(
+        checked = result.checked,
+        fixed = result.fixed,
+        unparseable = result.unparseable,
+        conflicts = result.conflicts,
+        "Backfilled link domains and paths"
+    );
+
+    Ok(result)
+}
+
+/// Whether a periodic task should run, given when it last ran
+fn is_due(last_run: Option<Instant>, now: Instant, interval: Duration) -> bool {
+    last_run.is_none_or(|last| now.saturating_duration_since(last) >= interval)
+}
+
+/// Whether a link's GitHub metadata is old enough to fetch again
+fn github_refresh_due(
+    last_refresh: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    interval_hours: u32,
+) -> bool {
+    last_refresh.is_none_or(|last| now - last >= chrono::Duration::hours(interval_hours as i64))
+}
+
+/// Decide which favicon to store after a scheduled re-scrape
+///
+/// A still-valid stored logo is kept. A stale one is only replaced when the
+/// scrape produced a valid candidate, so a transient failure never clears it.
+fn with_stored_logo(
+    scraped: scraper::ScrapedMetadata,
+    existing: Option<String>,
+    existing_valid: bool,
+) -> scraper::ScrapedMetadata {
+    let stored = scraper::ScrapedMetadata {
+        favicon: existing,
+        ..Default::default()
+    };
+    if existing_valid {
+        stored.merge(scraped)
+    } else {
+        scraped.merge(stored)
+    }
+}
+
+/// Pick the batch size for a cycle given how many links are due
+///
+/// Stays at `batch_size` unless the backlog exceeds `BACKLOG_MULTIPLIER`
+/// batches, then grows to spread it over that many cycles, capped at
+/// `max_batch_size`.
+fn effective_batch_size(batch_size: usize, max_batch_size: usize, due: usize) -> usize {
+    if due > batch_size.saturating_mul(BACKLOG_MULTIPLIER) {
+        (due / BACKLOG_MULTIPLIER)
+            .min(max_batch_size)
+            .max(batch_size)
+    } else {
+        batch_size
+    }
 }
 
 #[cfg(test)]
@@ -371,6 +915,43 @@ mod tests {
     use super::*;
     use std::sync::atomic::Ordering;
 
+    #[test]
+    fn test_github_flag_changes() {
+        let ids: Vec<_> = (0..4).map(|_| uuid::Uuid::new_v4()).collect();
+        let user_id = uuid::Uuid::new_v4();
+        let links = vec![
+            (
+                ids[0],
+                user_id,
+                "https://github.com/rust-lang/rust".to_string(),
+                false,
+            ),
+            (
+                ids[1],
+                user_id,
+                "https://github.com/tokio-rs/tokio".to_string(),
+                true,
+            ),
+            (
+                ids[2],
+                user_id,
+                "https://github.com/features".to_string(),
+                true,
+            ),
+            (
+                ids[3],
+                user_id,
+                "https://example.com/a/b".to_string(),
+                false,
+            ),
+        ];
+
+        let (now_github, no_longer_github) =
+            github_flag_changes(links, &github::GitHubHost::public());
+        assert_eq!(now_github, vec![(ids[0], user_id)]);
+        assert_eq!(no_longer_github, vec![ids[2]]);
+    }
+
     #[test]
     fn test_shutdown_handle_initial_state() {
         // Verify shutdown starts as false
@@ -388,6 +969,80 @@ mod tests {
         assert!(shutdown.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_is_due() {
+        let now = Instant::now();
+        let interval = Duration::from_secs(3600);
+
+        // Never run yet
+        assert!(is_due(None, now, interval));
+        // Ran recently
+        assert!(!is_due(Some(now), now + Duration::from_secs(60), interval));
+        // Interval elapsed
+        assert!(is_due(Some(now), now + interval, interval));
+    }
+
+    #[test]
+    fn test_github_refresh_due() {
+        let now = Utc::now();
+
+        assert!(github_refresh_due(None, now, 24));
+        assert!(!github_refresh_due(
+            Some(now - chrono::Duration::hours(23)),
+            now,
+            24
+        ));
+        assert!(github_refresh_due(
+            Some(now - chrono::Duration::hours(24)),
+            now,
+            24
+        ));
+    }
+
+    #[test]
+    fn test_with_stored_logo() {
+        let old = Some("https://example.com/old.ico".to_string());
+        let new = Some("https://example.com/new.png".to_string());
+        let scraped = |favicon: Option<String>| scraper::ScrapedMetadata {
+            title: Some("Page".to_string()),
+            favicon,
+            ..Default::default()
+        };
+
+        // Valid stored logo is kept even if the page now offers another
+        let merged = with_stored_logo(scraped(new.clone()), old.clone(), true);
+        assert_eq!(merged.favicon, old);
+        assert_eq!(merged.title, Some("Page".to_string()));
+        // Stale logo is replaced by a valid scraped candidate
+        assert_eq!(
+            with_stored_logo(scraped(new.clone()), old.clone(), false).favicon,
+            new
+        );
+        // Stale logo is left alone when no replacement was found
+        assert_eq!(
+            with_stored_logo(scraped(None), old.clone(), false).favicon,
+            old
+        );
+        // Missing logo is filled in
+        assert_eq!(
+            with_stored_logo(scraped(new.clone()), None, false).favicon,
+            new
+        );
+    }
+
+    #[test]
+    fn test_effective_batch_size() {
+        // Backlog within threshold keeps the configured batch
+        assert_eq!(effective_batch_size(50, 200, 0), 50);
+        assert_eq!(effective_batch_size(50, 200, 200), 50);
+        // Large backlog grows the batch proportionally
+        assert_eq!(effective_batch_size(50, 200, 400), 100);
+        // ...but never beyond the maximum
+        assert_eq!(effective_batch_size(50, 200, 10_000), 200);
+        // Auto-tuning disabled when max equals batch
+        assert_eq!(effective_batch_size(50, 50, 10_000), 50);
+    }
+
     #[test]
     fn test_jitter_calculation() {
         let base_interval_secs: u64 = 24 * 3600; // 24 hours
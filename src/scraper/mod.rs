@@ -3,7 +3,9 @@
 //! This module provides functionality to scrape basic metadata from web pages
 //! including title, description, and favicon.
 
+use crate::config::{Config, HealthCheckMethod, ScrapeBackend};
 use crate::error::AppError;
+use crate::security::mask_sensitive_query_params;
 use scraper::{Html, Selector};
 use std::time::Duration;
 use url::Url;
@@ -11,12 +13,60 @@ use url::Url;
 /// Scraped metadata from a web page
 #[derive(Debug, Clone)]
 pub struct ScrapedMetadata {
-    /// Page title from <title> tag or og:title meta tag
+    /// Page title from og:title, twitter:title or the <title> tag
     pub title: Option<String>,
-    /// Page description from meta description or og:description
+    /// Page description from og:description, twitter:description or meta description
     pub description: Option<String>,
     /// Favicon URL (absolute)
     pub favicon: Option<String>,
+    /// Final URL after following redirects, if it differs from the requested URL
+    pub final_url: Option<String>,
+    /// Tag suggestions from `<meta name="keywords">`; never applied automatically
+    pub keyword_suggestions: Vec<String>,
+    /// `ETag`/`Last-Modified` of the fetched page, for the next conditional fetch
+    pub validators: PageValidators,
+    /// Response diagnostics for troubleshooting empty results (not persisted)
+    pub debug: ScrapeDebugInfo,
+}
+
+/// Cache validators of a fetched page
+///
+/// Sent back as `If-None-Match`/`If-Modified-Since` so an unchanged page is
+/// answered with 304 Not Modified instead of being downloaded again.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageValidators {
+    /// `ETag` response header
+    pub etag: Option<String>,
+    /// `Last-Modified` response header
+    pub last_modified: Option<String>,
+}
+
+impl PageValidators {
+    /// Read the validators from response headers
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        }
+    }
+}
+
+/// Diagnostics about the fetched response
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ScrapeDebugInfo {
+    /// Content-Type header of the final response
+    pub content_type: Option<String>,
+    /// Body size in bytes (from Content-Length for non-HTML responses, which
+    /// are not downloaded)
+    pub body_length: Option<u64>,
 }
 
 impl Default for ScrapedMetadata {
@@ -25,6 +75,49 @@ impl Default for ScrapedMetadata {
             title: None,
             description: None,
             favicon: None,
+            final_url: None,
+            keyword_suggestions: Vec::new(),
+            validators: PageValidators::default(),
+            debug: ScrapeDebugInfo::default(),
+        }
+    }
+}
+
+impl ScrapedMetadata {
+    /// Combine with a lower-priority source
+    ///
+    /// Each field keeps `self`'s value unless it is missing or blank, in which
+    /// case `other`'s is used. Keyword suggestions are taken as a whole from
+    /// the first source that has any. Chain calls from the highest priority
+    /// down, e.g. `user.merge(open_graph).merge(twitter_card).merge(html)`.
+    pub fn merge(self, other: ScrapedMetadata) -> ScrapedMetadata {
+        fn prefer(value: Option<String>, fallback: Option<String>) -> Option<String> {
+            value
+                .filter(|v| !v.trim().is_empty())
+                .or(fallback.filter(|v| !v.trim().is_empty()))
+        }
+
+        ScrapedMetadata {
+            title: prefer(self.title, other.title),
+            description: prefer(self.description, other.description),
+            favicon: prefer(self.favicon, other.favicon),
+            final_url: prefer(self.final_url, other.final_url),
+            keyword_suggestions: if self.keyword_suggestions.is_empty() {
+                other.keyword_suggestions
+            } else {
+                self.keyword_suggestions
+            },
+            validators: PageValidators {
+                etag: self.validators.etag.or(other.validators.etag),
+                last_modified: self
+                    .validators
+                    .last_modified
+                    .or(other.validators.last_modified),
+            },
+            debug: ScrapeDebugInfo {
+                content_type: self.debug.content_type.or(other.debug.content_type),
+                body_length: self.debug.body_length.or(other.debug.body_length),
+            },
         }
     }
 }
@@ -36,11 +129,30 @@ impl Default for ScrapedMetadata {
 ///
 /// # Arguments
 /// * `url` - The URL to scrape
+/// * `config` - Application configuration (scraper settings)
 ///
 /// # Returns
 /// * `Ok(ScrapedMetadata)` - Scraped metadata (fields may be None if not found)
 /// * `Err(AppError)` - Only returns error if the URL is completely invalid or unreachable
-pub async fn scrape_url(url: &str) -> Result<ScrapedMetadata, AppError> {
+pub async fn scrape_url(url: &str, config: &Config) -> Result<ScrapedMetadata, AppError> {
+    // Without validators there is nothing for the server to answer 304 to
+    let metadata = scrape_url_if_modified(url, config, &PageValidators::default()).await?;
+    Ok(metadata.unwrap_or_default())
+}
+
+/// Scrape metadata from a URL unless it is unchanged since an earlier fetch
+///
+/// Sends `validators` as `If-None-Match`/`If-Modified-Since` (static backend
+/// only; the render service always fetches the page).
+///
+/// # Returns
+/// * `Ok(None)` - The server answered 304 Not Modified; nothing was parsed
+/// * `Ok(Some(ScrapedMetadata))` - As for `scrape_url`
+pub async fn scrape_url_if_modified(
+    url: &str,
+    config: &Config,
+    validators: &PageValidators,
+) -> Result<Option<ScrapedMetadata>, AppError> {
     // Parse URL to validate and use for absolute URL construction
     let base_url =
         Url::parse(url).map_err(|e| AppError::validation("url", &format!("Invalid URL: {}", e)))?;
@@ -48,106 +160,371 @@ pub async fn scrape_url(url: &str) -> Result<ScrapedMetadata, AppError> {
     crate::security::validate_url_for_ssrf(url)?;
 
     // Build HTTP client with timeout and redirects
-    let client = reqwest::Client::builder()
+    let client = crate::security::outbound_client_builder(config)
         .timeout(Duration::from_secs(10))
-        .redirect(reqwest::redirect::Policy::limited(5))
+        .redirect(reqwest::redirect::Policy::limited(config.max_redirects))
+        .danger_accept_invalid_certs(accepts_invalid_certs(config, &base_url))
+        .default_headers(scrape_headers(config))
         .user_agent("Mozilla/5.0 (compatible; RustyLinks/1.0; +https://github.com/rusty-links)")
         .build()
         .map_err(|e| AppError::ExternalService(format!("Failed to create HTTP client: {}", e)))?;
 
-    // Fetch the page
-    let response = client
-        .get(url)
+    let page = match config.scrape_backend {
+        ScrapeBackend::Static => match fetch_page(&client, &base_url, validators, config).await? {
+            Some(page) => page,
+            None => {
+                tracing::debug!(
+                    url = %mask_sensitive_query_params(url, &config.log_masked_query_params),
+                    "Page not modified since last fetch"
+                );
+                return Ok(None);
+            }
+        },
+        ScrapeBackend::HttpJson => {
+            let service_url = config.render_service_url.as_deref().ok_or_else(|| {
+                AppError::Configuration("RENDER_SERVICE_URL is not set".to_string())
+            })?;
+            fetch_rendered_page(&client, service_url, &base_url, config).await?
+        }
+    };
+
+    let Some(html) = page.html else {
+        return Ok(Some(ScrapedMetadata {
+            final_url: page.final_url,
+            validators: page.validators,
+            debug: page.debug,
+            ..Default::default()
+        }));
+    };
+
+    tracing::debug!(
+        url = %mask_sensitive_query_params(url, &config.log_masked_query_params),
+        content_type = ?page.debug.content_type,
+        body_length = html.len(),
+        "Fetched HTML for scraping"
+    );
+
+    // Parse HTML and extract metadata synchronously
+    let (page_text, favicon_candidates, keyword_suggestions) = {
+        let document = Html::parse_document(&html);
+        (
+            extract_page_text(&document),
+            if config.scrape_favicon {
+                extract_favicon(&document, &base_url, &config.favicon_rel_types)
+            } else {
+                Vec::new()
+            },
+            if config.scrape_keywords {
+                extract_keywords(&document)
+            } else {
+                Vec::new()
+            },
+        )
+    };
+    // document is dropped here, before any await
+
+    // Create metadata
+    let mut metadata = ScrapedMetadata::default();
+    metadata.title = page_text
+        .title
+        .and_then(|t| clean_text(&t, config.max_title_len));
+    metadata.description = page_text
+        .description
+        .and_then(|d| clean_text(&d, config.max_description_len));
+    metadata.final_url = page.final_url;
+    metadata.keyword_suggestions = keyword_suggestions;
+    metadata.validators = page.validators;
+    metadata.debug = page.debug;
+
+    // Validate favicon candidates (async, no reference to Html)
+    if config.scrape_favicon {
+        let fallback = config
+            .favicon_fallback_service
+            .as_deref()
+            .and_then(|service| fallback_favicon_url(service, &base_url));
+        metadata.favicon =
+            validate_favicon_candidates(&client, favicon_candidates, fallback, config).await;
+    }
+
+    Ok(Some(metadata))
+}
+
+/// HTML obtained for a page, before metadata extraction
+struct FetchedPage {
+    /// `None` when the response isn't HTML
+    html: Option<String>,
+    final_url: Option<String>,
+    validators: PageValidators,
+    debug: ScrapeDebugInfo,
+}
+
+/// Fetch a page directly (the `static` backend)
+///
+/// Returns `None` when `validators` show the page is unchanged (304).
+async fn fetch_page(
+    client: &reqwest::Client,
+    url: &Url,
+    validators: &PageValidators,
+    config: &Config,
+) -> Result<Option<FetchedPage>, AppError> {
+    let _permit = config.outbound_limiter.acquire().await;
+    let mut request = client.get(url.as_str());
+    if let Some(etag) = &validators.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = request
         .send()
         .await
-        .map_err(|e| AppError::ExternalService(format!("Failed to fetch URL: {}", e)))?;
+        .map_err(|e| AppError::request_failed("Failed to fetch URL", e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+
+    // Record where the redirects ended up so the caller can surface it
+    let final_url = redirected_url(url, response.url());
+    let validators = PageValidators::from_headers(response.headers());
 
     // Check if response is HTML
     let content_type = response
         .headers()
         .get(reqwest::header::CONTENT_TYPE)
         .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
+        .unwrap_or("")
+        .to_string();
 
     if !content_type.contains("text/html") {
         tracing::debug!("Non-HTML response, content-type: {}", content_type);
-        return Ok(ScrapedMetadata::default());
+        return Ok(Some(FetchedPage {
+            html: None,
+            final_url,
+            validators,
+            debug: ScrapeDebugInfo {
+                content_type: Some(content_type),
+                body_length: response.content_length(),
+            },
+        }));
     }
 
-    // Get response text
     let html = response
         .text()
         .await
-        .map_err(|e| AppError::ExternalService(format!("Failed to read response: {}", e)))?;
+        .map_err(|e| AppError::request_failed("Failed to read response", e))?;
 
-    // Parse HTML and extract metadata synchronously
-    let (title, description, favicon_candidates) = {
-        let document = Html::parse_document(&html);
-        (
-            extract_title(&document),
-            extract_description(&document),
-            extract_favicon(&document, &base_url),
-        )
-    };
-    // document is dropped here, before any await
+    Ok(Some(FetchedPage {
+        final_url,
+        validators,
+        debug: ScrapeDebugInfo {
+            content_type: Some(content_type),
+            body_length: Some(html.len() as u64),
+        },
+        html: Some(html),
+    }))
+}
 
-    // Create metadata
-    let mut metadata = ScrapedMetadata::default();
-    metadata.title = title;
-    metadata.description = description;
+/// Request body sent to the render service
+#[derive(serde::Serialize)]
+struct RenderRequest<'a> {
+    url: &'a str,
+}
 
-    // Validate favicon candidates (async, no reference to Html)
-    metadata.favicon = validate_favicon_candidates(&client, favicon_candidates).await;
+/// Response expected from the render service
+#[derive(serde::Deserialize)]
+struct RenderResponse {
+    /// Page HTML after JavaScript has run
+    html: String,
+    /// URL the browser ended up at, if the service reports it
+    #[serde(default)]
+    final_url: Option<String>,
+}
 
-    Ok(metadata)
+/// Fetch a page through an external render service (the `http_json` backend)
+///
+/// Rendering a page in a headless browser is slow, so the request gets a
+/// longer timeout than a direct fetch.
+async fn fetch_rendered_page(
+    client: &reqwest::Client,
+    service_url: &str,
+    url: &Url,
+    config: &Config,
+) -> Result<FetchedPage, AppError> {
+    let _permit = config.outbound_limiter.acquire().await;
+    let response = client
+        .post(service_url)
+        .timeout(Duration::from_secs(30))
+        .json(&RenderRequest { url: url.as_str() })
+        .send()
+        .await
+        .map_err(|e| AppError::request_failed("Render service request failed", e))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalService(format!(
+            "Render service returned status {}",
+            response.status()
+        )));
+    }
+
+    let rendered: RenderResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::request_failed("Invalid render service response", e))?;
+
+    let final_url = rendered
+        .final_url
+        .as_deref()
+        .and_then(|u| Url::parse(u).ok())
+        .and_then(|final_url| redirected_url(url, &final_url));
+
+    Ok(FetchedPage {
+        final_url,
+        // The service fetches the page itself, so there is nothing to revalidate
+        validators: PageValidators::default(),
+        debug: ScrapeDebugInfo {
+            content_type: Some("text/html".to_string()),
+            body_length: Some(rendered.html.len() as u64),
+        },
+        html: Some(rendered.html),
+    })
+}
+
+/// Default headers for page scraping requests
+fn scrape_headers(config: &Config) -> reqwest::header::HeaderMap {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(value) = config
+        .scrape_accept_language
+        .as_deref()
+        .and_then(|v| reqwest::header::HeaderValue::from_str(v).ok())
+    {
+        headers.insert(reqwest::header::ACCEPT_LANGUAGE, value);
+    }
+    headers
+}
+
+/// Whether certificate errors are tolerated for this URL's host
+///
+/// The setting applies to the whole client, so redirects from an allowlisted
+/// host are fetched without verification as well.
+fn accepts_invalid_certs(config: &Config, url: &Url) -> bool {
+    let accept = url
+        .host_str()
+        .is_some_and(|host| config.accepts_invalid_certs(host));
+    if accept {
+        tracing::debug!(url = %mask_sensitive_query_params(url.as_str(), &config.log_masked_query_params), "Skipping TLS certificate verification");
+    }
+    accept
+}
+
+/// Return the response URL if redirects led somewhere other than the request URL
+fn redirected_url(requested: &Url, final_url: &Url) -> Option<String> {
+    if requested == final_url {
+        None
+    } else {
+        Some(final_url.to_string())
+    }
+}
+
+/// Outcome of a URL reachability check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlHealth {
+    /// The URL answered with a 2xx or 3xx status
+    pub reachable: bool,
+    /// Why the URL is unreachable, e.g. "HTTP 404 Not Found"; `None` when reachable
+    pub detail: Option<String>,
+}
+
+impl UrlHealth {
+    fn reachable() -> Self {
+        UrlHealth {
+            reachable: true,
+            detail: None,
+        }
+    }
+
+    fn unreachable(detail: String) -> Self {
+        UrlHealth {
+            reachable: false,
+            detail: Some(detail),
+        }
+    }
 }
 
 /// Check if a URL is accessible (returns HTTP 2xx or 3xx)
 ///
-/// Makes a HEAD request to the URL to check if it's accessible without
+/// Makes a HEAD request (or a GET for the first byte, per
+/// `config.health_check_method`) to check the URL is accessible without
 /// downloading the full page content. This is used to detect broken links.
 ///
 /// # Arguments
 /// * `url` - The URL to check
+/// * `config` - Application configuration (redirect and TLS settings)
 ///
 /// # Returns
 /// * `Ok(true)` - URL is accessible (returns 2xx or 3xx status)
 /// * `Ok(false)` - URL is not accessible (connection failed or non-success status)
 /// * `Err(AppError)` - Only returns error if client creation fails
-pub async fn check_url_health(url: &str) -> Result<bool, AppError> {
-    let _ = url::Url::parse(url)
+pub async fn check_url_health(url: &str, config: &Config) -> Result<bool, AppError> {
+    Ok(check_url_health_detailed(url, config).await?.reachable)
+}
+
+/// Check if a URL is accessible, reporting why it isn't
+///
+/// Same checks as `check_url_health`; an unreachable URL carries the final
+/// status code or connection error in `detail`.
+pub async fn check_url_health_detailed(url: &str, config: &Config) -> Result<UrlHealth, AppError> {
+    let parsed_url = url::Url::parse(url)
         .map_err(|e| AppError::validation("url", &format!("Invalid URL: {}", e)))?;
     crate::security::validate_url_for_ssrf(url)?;
 
-    let client = reqwest::Client::builder()
+    let client = crate::security::outbound_client_builder(config)
         .timeout(Duration::from_secs(10))
-        .redirect(reqwest::redirect::Policy::limited(5))
+        .redirect(reqwest::redirect::Policy::limited(config.max_redirects))
+        .danger_accept_invalid_certs(accepts_invalid_certs(config, &parsed_url))
         .build()
         .map_err(|e| AppError::Internal(e.to_string()))?;
 
-    // Try HEAD first
-    match client.head(url).send().await {
-        Ok(response) => {
-            let status = response.status();
-            if status.is_success() || status.is_redirection() {
-                return Ok(true);
+    Ok(probe_url(&client, url, config.health_check_method, config).await)
+}
+
+/// Request `url` with the given method preference and classify the response
+async fn probe_url(
+    client: &reqwest::Client,
+    url: &str,
+    method: HealthCheckMethod,
+    config: &Config,
+) -> UrlHealth {
+    let _permit = config.outbound_limiter.acquire().await;
+    if method != HealthCheckMethod::Get {
+        match client.head(url).send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() || status.is_redirection() {
+                    return UrlHealth::reachable();
+                }
+                // Fall back to GET for methods that reject HEAD
+                if method == HealthCheckMethod::Auto
+                    && (status == reqwest::StatusCode::METHOD_NOT_ALLOWED
+                        || status == reqwest::StatusCode::FORBIDDEN
+                        || status == reqwest::StatusCode::NOT_IMPLEMENTED)
+                {
+                    tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), status = %status, "HEAD rejected, falling back to GET with Range header");
+                } else {
+                    return UrlHealth::unreachable(format!("HTTP {}", status));
+                }
             }
-            // Fall back to GET for methods that reject HEAD
-            if status == reqwest::StatusCode::METHOD_NOT_ALLOWED
-                || status == reqwest::StatusCode::FORBIDDEN
-                || status == reqwest::StatusCode::NOT_IMPLEMENTED
-            {
-                tracing::debug!(url = %url, status = %status, "HEAD rejected, falling back to GET with Range header");
-            } else {
-                return Ok(false);
+            Err(e) if method == HealthCheckMethod::Auto => {
+                tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), error = %mask_sensitive_query_params(&e.to_string(), &config.log_masked_query_params), "HEAD request failed, falling back to GET with Range header");
+            }
+            Err(e) => {
+                tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), error = %mask_sensitive_query_params(&e.to_string(), &config.log_masked_query_params), "HEAD request failed");
+                return UrlHealth::unreachable(describe_request_error(&e));
             }
-        }
-        Err(e) => {
-            tracing::debug!(url = %url, error = %e, "HEAD request failed, falling back to GET with Range header");
         }
     }
 
-    // Fallback: GET with Range header to minimize data transfer
+    // GET with Range header to minimize data transfer
     match client
         .get(url)
         .header(reqwest::header::RANGE, "bytes=0-0")
@@ -156,71 +533,290 @@ pub async fn check_url_health(url: &str) -> Result<bool, AppError> {
     {
         Ok(response) => {
             let status = response.status();
-            Ok(status.is_success()
+            if status.is_success()
                 || status.is_redirection()
-                || status == reqwest::StatusCode::PARTIAL_CONTENT)
+                || status == reqwest::StatusCode::PARTIAL_CONTENT
+            {
+                UrlHealth::reachable()
+            } else {
+                UrlHealth::unreachable(format!("HTTP {}", status))
+            }
         }
         Err(e) => {
-            tracing::debug!(url = %url, error = %e, "GET fallback also failed");
-            Ok(false)
+            tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), error = %mask_sensitive_query_params(&e.to_string(), &config.log_masked_query_params), "GET request failed");
+            UrlHealth::unreachable(describe_request_error(&e))
         }
     }
 }
 
-/// Extract title from HTML document
-fn extract_title(document: &Html) -> Option<String> {
-    // Try og:title first
-    if let Ok(selector) = Selector::parse("meta[property='og:title']") {
-        if let Some(element) = document.select(&selector).next() {
-            if let Some(content) = element.value().attr("content") {
-                let title = content.trim();
-                if !title.is_empty() {
-                    return Some(title.to_string());
-                }
-            }
-        }
+/// Short, user-facing description of a failed request
+fn describe_request_error(error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        "Request timed out".to_string()
+    } else if error.is_connect() {
+        "Could not connect".to_string()
+    } else if error.is_redirect() {
+        "Too many redirects".to_string()
+    } else {
+        "Request failed".to_string()
     }
+}
 
-    // Try regular title tag
-    if let Ok(selector) = Selector::parse("title") {
-        if let Some(element) = document.select(&selector).next() {
-            let title = element.text().collect::<String>().trim().to_string();
-            if !title.is_empty() {
-                return Some(title);
-            }
-        }
+/// File extensions mapped to language names, matching the seeded `languages` rows
+const EXTENSION_LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("py", "Python"),
+    ("pyi", "Python"),
+    ("js", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("java", "Java"),
+    ("cs", "C#"),
+    ("cpp", "C++"),
+    ("cc", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("c", "C"),
+    ("h", "C"),
+    ("php", "PHP"),
+    ("rb", "Ruby"),
+    ("go", "Go"),
+    ("swift", "Swift"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("r", "R"),
+    ("dart", "Dart"),
+    ("scala", "Scala"),
+    ("pl", "Perl"),
+    ("pm", "Perl"),
+    ("lua", "Lua"),
+    ("hs", "Haskell"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+];
+
+/// Path segments code hosts put before a file path when showing a source file,
+/// e.g. GitLab's `/-/blob/<ref>/...` or GitHub's `/raw/<ref>/...`
+const SOURCE_VIEW_SEGMENTS: &[&str] = &["blob", "raw"];
+
+/// Infer a programming language from a URL's file extension
+///
+/// Best-effort detection for links to source files on GitLab, Bitbucket,
+/// raw file hosts, etc. Only URLs that look like a code host's file view are
+/// considered: a `blob`/`raw` path segment, Bitbucket's `/<owner>/<repo>/src/`
+/// layout, or a `raw.` host. Web pages such as `https://forum.example.com/index.php`
+/// are not source files and yield `None`.
+///
+/// Only the last path segment is considered, so
+/// `https://gitlab.com/o/r/-/blob/main/src/lib.rs` yields `Some("Rust")`.
+/// Returns `None` for URLs without a recognised extension.
+pub fn detect_language_from_url(url: &str) -> Option<&'static str> {
+    let parsed = Url::parse(url).ok()?;
+    let segments: Vec<&str> = parsed.path_segments()?.collect();
+    let (file_name, directories) = segments.split_last()?;
+
+    let raw_host = parsed
+        .host_str()
+        .is_some_and(|host| host.starts_with("raw."));
+    let source_view = directories
+        .iter()
+        .any(|segment| SOURCE_VIEW_SEGMENTS.contains(segment))
+        || directories.get(2) == Some(&"src");
+    if !raw_host && !source_view {
+        return None;
     }
 
-    None
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let extension = extension.to_ascii_lowercase();
+
+    EXTENSION_LANGUAGES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, language)| *language)
 }
 
-/// Extract description from HTML document
-fn extract_description(document: &Html) -> Option<String> {
-    // Try og:description first
-    if let Ok(selector) = Selector::parse("meta[property='og:description']") {
-        if let Some(element) = document.select(&selector).next() {
-            if let Some(content) = element.value().attr("content") {
-                let desc = content.trim();
-                if !desc.is_empty() {
-                    return Some(desc.to_string());
-                }
-            }
-        }
+/// Collapse whitespace in scraped text and cap its length
+///
+/// Runs of spaces, tabs and newlines become a single space. Text longer than
+/// `max_len` characters is cut at the last word boundary that fits (or mid-word
+/// if the first word alone is too long) and ends in "…", which counts toward
+/// `max_len`. Returns `None` for blank text.
+fn clean_text(text: &str, max_len: usize) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= max_len {
+        return Some(text);
     }
 
-    // Try standard meta description
-    if let Ok(selector) = Selector::parse("meta[name='description']") {
-        if let Some(element) = document.select(&selector).next() {
-            if let Some(content) = element.value().attr("content") {
-                let desc = content.trim();
-                if !desc.is_empty() {
-                    return Some(desc.to_string());
-                }
+    let budget = max_len.saturating_sub(1);
+    let cut = text
+        .char_indices()
+        .nth(budget)
+        .map_or(text.len(), |(i, _)| i);
+    let head = &text[..cut];
+    // Prefer ending on a whole word, unless that throws most of the text away
+    let head = if text[cut..].starts_with(' ') {
+        head
+    } else {
+        match head.rfind(' ') {
+            Some(space) if space >= cut / 2 => &head[..space],
+            _ => head,
+        }
+    };
+    let head = head.trim_end_matches(|c: char| c.is_whitespace() || c == ',' || c == ';');
+
+    Some(format!("{}…", head))
+}
+
+/// Decode HTML character references left in scraped text
+///
+/// The parser decodes markup once, but pages regularly double-escape meta
+/// `content` attributes (`Tom &amp;amp; Jerry`), so a second pass over the
+/// extracted text is still needed. Handles numeric references and the named
+/// entities that show up in practice; anything unrecognised is kept verbatim.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded_char = rest[1..]
+            .find(';')
+            .filter(|&end| end > 0 && end <= 10)
+            .and_then(|end| decode_entity(&rest[1..=end]).map(|c| (c, end + 2)));
+
+        match decoded_char {
+            Some((c, consumed)) => {
+                decoded.push(c);
+                rest = &rest[consumed..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
             }
         }
     }
+    decoded.push_str(rest);
 
-    None
+    decoded
+}
+
+/// Decode a single entity name (without the surrounding `&` and `;`)
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse::<u32>().ok()?,
+        };
+        return char::from_u32(code).filter(|c| *c != '\0');
+    }
+
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "middot" => '·',
+        "bull" => '•',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        _ => return None,
+    };
+    Some(c)
+}
+
+/// Extract title and description from HTML document
+///
+/// Each source only fills what the ones before it left empty: Open Graph
+/// tags, then Twitter card tags, then `<title>` and `<meta name="description">`.
+fn extract_page_text(document: &Html) -> ScrapedMetadata {
+    let open_graph = ScrapedMetadata {
+        title: meta_content(document, "meta[property='og:title']"),
+        description: meta_content(document, "meta[property='og:description']"),
+        ..Default::default()
+    };
+    let twitter_card = ScrapedMetadata {
+        title: meta_content(document, "meta[name='twitter:title']"),
+        description: meta_content(document, "meta[name='twitter:description']"),
+        ..Default::default()
+    };
+    let html = ScrapedMetadata {
+        title: title_tag(document),
+        description: meta_content(document, "meta[name='description']"),
+        ..Default::default()
+    };
+
+    open_graph.merge(twitter_card).merge(html)
+}
+
+/// Decoded `content` of the first element matching `selector`
+fn meta_content(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    let content = document.select(&selector).next()?.value().attr("content")?;
+    Some(decode_entities(content.trim()))
+}
+
+/// Decoded text of the `<title>` tag
+fn title_tag(document: &Html) -> Option<String> {
+    let selector = Selector::parse("title").ok()?;
+    let element = document.select(&selector).next()?;
+    Some(decode_entities(element.text().collect::<String>().trim()))
+}
+
+/// Most keyword suggestions taken from a page
+const MAX_KEYWORD_SUGGESTIONS: usize = 10;
+
+/// Extract tag suggestions from `<meta name="keywords">`
+///
+/// Splits on commas, trims, drops case-insensitive duplicates and keeps the
+/// first `MAX_KEYWORD_SUGGESTIONS`.
+fn extract_keywords(document: &Html) -> Vec<String> {
+    let Ok(selector) = Selector::parse("meta[name='keywords' i][content]") else {
+        return Vec::new();
+    };
+    let Some(content) = document
+        .select(&selector)
+        .next()
+        .and_then(|element| element.value().attr("content"))
+    else {
+        return Vec::new();
+    };
+
+    let mut keywords: Vec<String> = Vec::new();
+    for keyword in decode_entities(content).split(',') {
+        let keyword = keyword.split_whitespace().collect::<Vec<_>>().join(" ");
+        if keyword.is_empty()
+            || keywords
+                .iter()
+                .any(|existing| existing.to_lowercase() == keyword.to_lowercase())
+        {
+            continue;
+        }
+        keywords.push(keyword);
+        if keywords.len() == MAX_KEYWORD_SUGGESTIONS {
+            break;
+        }
+    }
+    keywords
 }
 
 /// Valid image MIME types for favicons
@@ -246,33 +842,45 @@ const VALID_IMAGE_EXTENSIONS: &[&str] = &[
 
 /// Validate that a favicon/image URL exists and returns a valid image
 ///
-/// Makes a HEAD request to check:
+/// Rejects URLs that fail the SSRF check without contacting them, then makes
+/// a HEAD request to check:
 /// 1. The URL returns HTTP 2xx status
 /// 2. The Content-Type is a valid image MIME type
 ///
 /// # Arguments
 /// * `url` - The favicon/image URL to validate
+/// * `config` - Application configuration (outbound client settings)
 ///
 /// # Returns
 /// * `Ok(true)` if the URL exists and is a valid image
-/// * `Ok(false)` if the URL doesn't exist or isn't a valid image
+/// * `Ok(false)` if the URL fails the SSRF check, doesn't exist or isn't a valid image
 /// * `Err` if the HTTP client couldn't be created
-pub async fn validate_image_url(url: &str) -> Result<bool, AppError> {
-    let client = reqwest::Client::builder()
+pub async fn validate_image_url(url: &str, config: &Config) -> Result<bool, AppError> {
+    if let Err(e) = crate::security::validate_url_for_ssrf(url) {
+        tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), error = %e, "Image URL failed SSRF check");
+        return Ok(false);
+    }
+
+    let client = crate::security::outbound_client_builder(config)
         .timeout(std::time::Duration::from_secs(5))
         .build()
         .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
 
-    Ok(validate_favicon_url_with_client(&client, url).await)
+    Ok(validate_favicon_url_with_client(&client, url, config).await)
 }
 
 /// Internal function to validate favicon URL with a provided client
-async fn validate_favicon_url_with_client(client: &reqwest::Client, url: &str) -> bool {
+async fn validate_favicon_url_with_client(
+    client: &reqwest::Client,
+    url: &str,
+    config: &Config,
+) -> bool {
+    let _permit = config.outbound_limiter.acquire().await;
     match client.head(url).send().await {
         Ok(response) => {
             // Check for success status
             if !response.status().is_success() {
-                tracing::debug!(url = %url, status = %response.status(), "Favicon URL returned non-success status");
+                tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), status = %response.status(), "Favicon URL returned non-success status");
                 return false;
             }
 
@@ -286,7 +894,7 @@ async fn validate_favicon_url_with_client(client: &reqwest::Client, url: &str) -
 
                     if !is_valid {
                         tracing::debug!(
-                            url = %url,
+                            url = %mask_sensitive_query_params(url, &config.log_masked_query_params),
                             content_type = %content_type_str,
                             "Favicon URL has invalid Content-Type"
                         );
@@ -303,70 +911,238 @@ async fn validate_favicon_url_with_client(client: &reqwest::Client, url: &str) -
                 .any(|ext| url_lower.ends_with(ext));
 
             if has_valid_extension {
-                tracing::debug!(url = %url, "Favicon URL has valid extension, accepting without Content-Type");
+                tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), "Favicon URL has valid extension, accepting without Content-Type");
                 return true;
             }
 
-            tracing::debug!(url = %url, "Favicon URL has no Content-Type and no valid extension");
+            tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), "Favicon URL has no Content-Type and no valid extension");
             false
         }
         Err(e) => {
-            tracing::debug!(url = %url, error = %e, "Failed to validate favicon URL");
+            tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), error = %mask_sensitive_query_params(&e.to_string(), &config.log_masked_query_params), "Failed to validate favicon URL");
             false
         }
     }
 }
 
-/// Extract favicon URL from HTML document
-fn extract_favicon(document: &Html, base_url: &Url) -> Vec<String> {
-    let mut candidates = Vec::new();
+/// Largest image `fetch_image_data_uri` embeds
+const MAX_EMBEDDED_IMAGE_BYTES: usize = 100 * 1024;
 
-    // Try various link rel attributes
-    let rel_attributes = [
-        "icon",
-        "shortcut icon",
-        "apple-touch-icon",
-        "apple-touch-icon-precomposed",
-    ];
+/// Download an image and return it as a `data:` URI, for embedding
+///
+/// Used to inline a link's favicon into generated preview cards, which can't
+/// load external resources. Returns `None` when the URL fails the SSRF check,
+/// the request fails, the response isn't a known image type or the image is
+/// larger than 100 KB.
+pub async fn fetch_image_data_uri(url: &str, config: &Config) -> Option<String> {
+    use futures::StreamExt;
 
-    for rel in &rel_attributes {
-        if let Ok(selector) = Selector::parse(&format!("link[rel='{}']", rel)) {
-            if let Some(element) = document.select(&selector).next() {
-                if let Some(href) = element.value().attr("href") {
-                    // Convert relative URLs to absolute
-                    if let Ok(favicon_url) = base_url.join(href) {
-                        candidates.push(favicon_url.to_string());
-                    }
-                }
+    crate::security::validate_url_for_ssrf(url).ok()?;
+    let client = crate::security::outbound_client_builder(config)
+        .timeout(Duration::from_secs(5))
+        .build()
+        .ok()?;
+
+    let _permit = config.outbound_limiter.acquire().await;
+    let response = match client.get(url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), status = %response.status(), "Image URL returned non-success status");
+            return None;
+        }
+        Err(e) => {
+            tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), error = %mask_sensitive_query_params(&e.to_string(), &config.log_masked_query_params), "Failed to fetch image");
+            return None;
+        }
+    };
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_lowercase())?;
+    if !VALID_IMAGE_MIME_TYPES.contains(&content_type.as_str())
+        || response
+            .content_length()
+            .is_some_and(|len| len > MAX_EMBEDDED_IMAGE_BYTES as u64)
+    {
+        return None;
+    }
+
+    // Content-Length can be missing or wrong, so stop reading once the body
+    // outgrows the cap instead of buffering all of it
+    let mut bytes = Vec::new();
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        bytes.extend_from_slice(&chunk.ok()?);
+        if bytes.len() > MAX_EMBEDDED_IMAGE_BYTES {
+            return None;
+        }
+    }
+    Some(format!(
+        "data:{};base64,{}",
+        content_type,
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes)
+    ))
+}
+
+/// Extract favicon candidate URLs from an HTML document
+///
+/// Reads every `<link>` whose `rel` matches one of `rel_types` (all of a
+/// type's tokens present, so `icon` also matches `shortcut icon`). Candidates
+/// are ordered by their largest declared `sizes` entry, biggest first, with
+/// `sizes="any"` (scalable) ahead of everything and undeclared sizes last.
+/// Ties keep the order of `rel_types`, then page order. `/favicon.ico` is
+/// always appended as a last resort.
+fn extract_favicon(document: &Html, base_url: &Url, rel_types: &[String]) -> Vec<String> {
+    let mut found = Vec::new();
+
+    if let Ok(selector) = Selector::parse("link[rel][href]") {
+        for element in document.select(&selector) {
+            let rel = element
+                .value()
+                .attr("rel")
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let rel_tokens: Vec<&str> = rel.split_whitespace().collect();
+            let Some(rank) = rel_types.iter().position(|rel_type| {
+                rel_type
+                    .split_whitespace()
+                    .all(|token| rel_tokens.contains(&token))
+            }) else {
+                continue;
+            };
+
+            let Some(href) = element.value().attr("href") else {
+                continue;
+            };
+            // Convert relative URLs to absolute
+            if let Ok(favicon_url) = base_url.join(href.trim()) {
+                let size = largest_icon_size(element.value().attr("sizes"));
+                found.push((size, rank, favicon_url.to_string()));
             }
         }
     }
 
+    // Stable sort keeps page order among equal candidates
+    found.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let mut candidates: Vec<String> = Vec::with_capacity(found.len() + 1);
+    for (_, _, url) in found {
+        if !candidates.contains(&url) {
+            candidates.push(url);
+        }
+    }
+
     // Fallback to default /favicon.ico
     if let Ok(default_favicon) = base_url.join("/favicon.ico") {
-        candidates.push(default_favicon.to_string());
+        let default_favicon = default_favicon.to_string();
+        if !candidates.contains(&default_favicon) {
+            candidates.push(default_favicon);
+        }
     }
 
     candidates
 }
 
+/// Largest edge declared by a `sizes` attribute (`"16x16 32x32"` -> 32)
+///
+/// `any` (a scalable icon) ranks above every pixel size; a missing or
+/// unparseable attribute counts as 0.
+fn largest_icon_size(sizes: Option<&str>) -> u32 {
+    sizes
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(|size| {
+            if size.eq_ignore_ascii_case("any") {
+                return Some(u32::MAX);
+            }
+            let (width, height) = size
+                .to_ascii_lowercase()
+                .split_once('x')
+                .map(|(w, h)| (w.parse::<u32>().ok(), h.parse::<u32>().ok()))?;
+            Some(width?.max(height?))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Build the fallback favicon URL for a page from a service template
+///
+/// The template's `{domain}` placeholder is replaced with the page's host.
+/// Returns `None` if the page URL has no host.
+fn fallback_favicon_url(service: &str, base_url: &Url) -> Option<String> {
+    let host = base_url.host_str()?;
+    Some(service.replace("{domain}", &urlencoding::encode(host)))
+}
+
 /// Validate favicon candidates and return the first valid one
 ///
 /// Tries multiple favicon candidates and returns the first one that:
 /// 1. Returns HTTP 2xx status
 /// 2. Has a valid image Content-Type
+///
+/// The optional `fallback` (from the configured favicon service) is tried
+/// last, after every candidate declared by the page and `/favicon.ico`.
+/// Up to `favicon_validation_concurrency` candidates are checked at once.
 async fn validate_favicon_candidates(
     client: &reqwest::Client,
-    candidates: Vec<String>,
+    mut candidates: Vec<String>,
+    fallback: Option<String>,
+    config: &Config,
 ) -> Option<String> {
-    for candidate in candidates {
-        if validate_favicon_url_with_client(client, &candidate).await {
-            tracing::debug!(url = %candidate, "Found valid favicon");
+    candidates.extend(fallback);
+
+    let favicon =
+        first_valid_in_order(
+            candidates,
+            config.favicon_validation_concurrency,
+            |candidate| async move {
+                validate_favicon_url_with_client(client, &candidate, config).await
+            },
+        )
+        .await;
+
+    match &favicon {
+        Some(url) => {
+            tracing::debug!(url = %mask_sensitive_query_params(url, &config.log_masked_query_params), "Found valid favicon")
+        }
+        None => tracing::debug!("No valid favicon found"),
+    }
+    favicon
+}
+
+/// Run `check` over `candidates` with bounded concurrency and return the
+/// earliest candidate (in list order) that passes
+///
+/// Results are consumed in order, so a later candidate finishing first never
+/// wins over an earlier valid one. Checks still pending once the answer is
+/// known are dropped, cancelling their requests.
+async fn first_valid_in_order<F, Fut>(
+    candidates: Vec<String>,
+    concurrency: usize,
+    check: F,
+) -> Option<String>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    use futures::StreamExt;
+
+    let mut results = futures::stream::iter(candidates)
+        .map(|candidate| {
+            let valid = check(candidate.clone());
+            async move { (candidate, valid.await) }
+        })
+        .buffered(concurrency.max(1));
+
+    while let Some((candidate, valid)) = results.next().await {
+        if valid {
             return Some(candidate);
         }
     }
 
-    tracing::debug!("No valid favicon found");
     None
 }
 
@@ -383,7 +1159,7 @@ mod tests {
             </html>
         "#;
         let document = Html::parse_document(html);
-        let title = extract_title(&document);
+        let title = extract_page_text(&document).title;
         assert_eq!(title, Some("Test Page".to_string()));
     }
 
@@ -399,7 +1175,7 @@ mod tests {
             </html>
         "#;
         let document = Html::parse_document(html);
-        let title = extract_title(&document);
+        let title = extract_page_text(&document).title;
         assert_eq!(title, Some("OG Title".to_string()));
     }
 
@@ -407,14 +1183,14 @@ mod tests {
     fn test_extract_title_none_when_missing() {
         let html = r#"<html><head></head><body></body></html>"#;
         let document = Html::parse_document(html);
-        assert_eq!(extract_title(&document), None);
+        assert_eq!(extract_page_text(&document).title, None);
     }
 
     #[test]
     fn test_extract_title_empty_title_tag() {
         let html = r#"<html><head><title>   </title></head></html>"#;
         let document = Html::parse_document(html);
-        assert_eq!(extract_title(&document), None);
+        assert_eq!(extract_page_text(&document).title, None);
     }
 
     #[test]
@@ -428,7 +1204,182 @@ mod tests {
             </html>
         "#;
         let document = Html::parse_document(html);
-        assert_eq!(extract_title(&document), Some("Fallback Title".to_string()));
+        assert_eq!(
+            extract_page_text(&document).title,
+            Some("Fallback Title".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_title_twitter_card_before_title_tag() {
+        let html = r#"
+            <html>
+                <head>
+                    <meta property="og:title" content="" />
+                    <meta name="twitter:title" content="Card Title" />
+                    <meta name="twitter:description" content="Card desc" />
+                    <meta name="description" content="Regular desc" />
+                    <title>Regular Title</title>
+                </head>
+            </html>
+        "#;
+        let document = Html::parse_document(html);
+        let text = extract_page_text(&document);
+        assert_eq!(text.title, Some("Card Title".to_string()));
+        assert_eq!(text.description, Some("Card desc".to_string()));
+    }
+
+    #[test]
+    fn test_merge_prefers_non_empty_higher_priority() {
+        let user = ScrapedMetadata {
+            title: Some("My title".to_string()),
+            description: Some("  ".to_string()),
+            ..Default::default()
+        };
+        let scraped = ScrapedMetadata {
+            title: Some("Page title".to_string()),
+            description: Some("Page description".to_string()),
+            favicon: Some("https://example.com/favicon.ico".to_string()),
+            keyword_suggestions: vec!["rust".to_string()],
+            ..Default::default()
+        };
+
+        let merged = user.merge(scraped);
+        assert_eq!(merged.title, Some("My title".to_string()));
+        assert_eq!(merged.description, Some("Page description".to_string()));
+        assert_eq!(
+            merged.favicon,
+            Some("https://example.com/favicon.ico".to_string())
+        );
+        assert_eq!(merged.keyword_suggestions, vec!["rust".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_all_empty() {
+        let blank = ScrapedMetadata {
+            title: Some(String::new()),
+            description: Some(" \n ".to_string()),
+            ..Default::default()
+        };
+
+        let merged = blank.merge(ScrapedMetadata::default());
+        assert_eq!(merged.title, None);
+        assert_eq!(merged.description, None);
+        assert_eq!(merged.favicon, None);
+        assert_eq!(merged.final_url, None);
+        assert!(merged.keyword_suggestions.is_empty());
+
+        let merged = ScrapedMetadata::default().merge(ScrapedMetadata::default());
+        assert_eq!(merged.title, None);
+    }
+
+    #[test]
+    fn test_clean_text_collapses_multiline_title() {
+        let html = "<html><head><title>\n    Rusty   Links\n\t| Home\n  </title></head></html>";
+        let document = Html::parse_document(html);
+        let title = extract_page_text(&document).title.unwrap();
+        assert_eq!(
+            clean_text(&title, 300),
+            Some("Rusty Links | Home".to_string())
+        );
+        assert_eq!(clean_text(" \n\t ", 300), None);
+    }
+
+    #[test]
+    fn test_clean_text_truncates_on_word_boundary() {
+        let description = "word ".repeat(500);
+        let cleaned = clean_text(&description, 1000).unwrap();
+        assert!(cleaned.chars().count() <= 1000);
+        assert!(cleaned.ends_with("word…"));
+
+        assert_eq!(
+            clean_text("The quick brown fox jumps", 16),
+            Some("The quick brown…".to_string())
+        );
+        assert_eq!(
+            clean_text("The quick, brown fox", 13),
+            Some("The quick…".to_string())
+        );
+        // A single over-long word is cut mid-word
+        assert_eq!(
+            clean_text(&"a".repeat(20), 10),
+            Some(format!("{}…", "a".repeat(9)))
+        );
+        // Multi-byte characters are counted, not bytes
+        assert_eq!(
+            clean_text("ééééé ééééé", 11),
+            Some("ééééé ééééé".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("Tom &amp; Jerry"), "Tom & Jerry");
+        assert_eq!(decode_entities("It&#39;s here"), "It's here");
+        assert_eq!(decode_entities("&#8220;Quoted&#x201D;"), "“Quoted”");
+        assert_eq!(decode_entities("A &mdash; B&hellip;"), "A — B…");
+        // Unknown or malformed references are left alone
+        assert_eq!(decode_entities("R&D &bogus; a & b"), "R&D &bogus; a & b");
+        assert_eq!(decode_entities("&#xZZ; &#0; &"), "&#xZZ; &#0; &");
+    }
+
+    #[test]
+    fn test_extract_title_decodes_og_title_entities() {
+        let html = r#"<html><head>
+            <meta property="og:title" content="Tom &amp;amp; Jerry&amp;#39;s &amp;#8212; Show">
+        </head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(
+            extract_page_text(&document).title,
+            Some("Tom & Jerry's — Show".to_string())
+        );
+
+        let html = r#"<html><head>
+            <meta property="og:title" content="Tom &amp; Jerry&#39;s &#8212; Show">
+        </head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(
+            extract_page_text(&document).title,
+            Some("Tom & Jerry's — Show".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_description_decodes_entities() {
+        let html = r#"<html><head>
+            <meta name="description" content="Fish &amp;amp; chips &amp;#x2013; fresh">
+        </head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(
+            extract_page_text(&document).description,
+            Some("Fish & chips – fresh".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_keywords() {
+        let html = r#"<html><head>
+            <meta name="Keywords" content=" Rust, web  framework,,rust, async &amp; await ">
+        </head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(
+            extract_keywords(&document),
+            vec!["Rust", "web framework", "async & await"]
+        );
+    }
+
+    #[test]
+    fn test_extract_keywords_capped_and_missing() {
+        let keywords: Vec<String> = (1..=15).map(|i| format!("kw{}", i)).collect();
+        let html = format!(
+            r#"<html><head><meta name="keywords" content="{}"></head></html>"#,
+            keywords.join(",")
+        );
+        let document = Html::parse_document(&html);
+        assert_eq!(extract_keywords(&document), keywords[..10].to_vec());
+
+        let document = Html::parse_document("<html><head></head></html>");
+        assert!(extract_keywords(&document).is_empty());
     }
 
     #[test]
@@ -442,7 +1393,7 @@ mod tests {
             </html>
         "#;
         let document = Html::parse_document(html);
-        let desc = extract_description(&document);
+        let desc = extract_page_text(&document).description;
         assert_eq!(desc, Some("Test description".to_string()));
     }
 
@@ -457,21 +1408,107 @@ mod tests {
             </html>
         "#;
         let document = Html::parse_document(html);
-        assert_eq!(extract_description(&document), Some("OG desc".to_string()));
+        assert_eq!(
+            extract_page_text(&document).description,
+            Some("OG desc".to_string())
+        );
     }
 
     #[test]
     fn test_extract_description_none_when_missing() {
         let html = r#"<html><head></head></html>"#;
         let document = Html::parse_document(html);
-        assert_eq!(extract_description(&document), None);
+        assert_eq!(extract_page_text(&document).description, None);
     }
 
     #[test]
     fn test_extract_description_empty_content() {
         let html = r#"<html><head><meta name="description" content="  " /></head></html>"#;
         let document = Html::parse_document(html);
-        assert_eq!(extract_description(&document), None);
+        assert_eq!(extract_page_text(&document).description, None);
+    }
+
+    fn default_rel_types() -> Vec<String> {
+        crate::config::DEFAULT_FAVICON_REL_TYPES
+            .iter()
+            .map(|rel| rel.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_favicon_prefers_largest_declared_size() {
+        let base = Url::parse("https://example.com/").unwrap();
+        let html = r#"
+            <html><head>
+                <link rel="icon" href="/icon-16.png" sizes="16x16" />
+                <link rel="icon" href="/icon-multi.ico" sizes="16x16 48x48" />
+                <link rel="icon" href="/icon-plain.png" />
+                <link rel="icon" href="/icon-192.png" sizes="192x192" />
+                <link rel="apple-touch-icon" href="/apple-180.png" sizes="180x180" />
+                <link rel="icon" href="/icon-32.png" sizes="32X32" />
+            </head></html>
+        "#;
+        let document = Html::parse_document(html);
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
+        assert_eq!(
+            candidates,
+            vec![
+                "https://example.com/icon-192.png",
+                "https://example.com/apple-180.png",
+                "https://example.com/icon-multi.ico",
+                "https://example.com/icon-32.png",
+                "https://example.com/icon-16.png",
+                "https://example.com/icon-plain.png",
+                "https://example.com/favicon.ico",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_favicon_extra_rel_types_and_configured_order() {
+        let base = Url::parse("https://example.com/").unwrap();
+        let html = r#"
+            <html><head>
+                <link rel="mask-icon" href="/mask.svg" />
+                <link rel="fluid-icon" href="/fluid.png" title="Example" />
+                <link rel="shortcut icon" href="/short.ico" />
+                <link rel="stylesheet" href="/style.css" />
+            </head></html>
+        "#;
+        let document = Html::parse_document(html);
+
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
+        assert_eq!(
+            candidates,
+            vec![
+                "https://example.com/short.ico",
+                "https://example.com/mask.svg",
+                "https://example.com/fluid.png",
+                "https://example.com/favicon.ico",
+            ]
+        );
+
+        // Only configured types are read, in the configured preference order
+        let rel_types = vec!["fluid-icon".to_string(), "icon".to_string()];
+        let candidates = extract_favicon(&document, &base, &rel_types);
+        assert_eq!(
+            candidates,
+            vec![
+                "https://example.com/fluid.png",
+                "https://example.com/short.ico",
+                "https://example.com/favicon.ico",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_largest_icon_size() {
+        assert_eq!(largest_icon_size(None), 0);
+        assert_eq!(largest_icon_size(Some("")), 0);
+        assert_eq!(largest_icon_size(Some("32x32")), 32);
+        assert_eq!(largest_icon_size(Some("16x16 64x32 48x48")), 64);
+        assert_eq!(largest_icon_size(Some("any")), u32::MAX);
+        assert_eq!(largest_icon_size(Some("huge 24x24")), 24);
     }
 
     #[test]
@@ -485,7 +1522,7 @@ mod tests {
             </html>
         "#;
         let document = Html::parse_document(html);
-        let candidates = extract_favicon(&document, &base);
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
         assert!(candidates.contains(&"https://example.com/favicon.ico".to_string()));
     }
 
@@ -498,7 +1535,7 @@ mod tests {
             </html>
         "#;
         let document = Html::parse_document(html);
-        let candidates = extract_favicon(&document, &base);
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
         assert_eq!(
             candidates,
             vec!["https://example.com/favicon.ico".to_string()]
@@ -510,7 +1547,7 @@ mod tests {
         let base = Url::parse("https://example.com/blog/post").unwrap();
         let html = r#"<html><head><link rel="icon" href="icon.png" /></head></html>"#;
         let document = Html::parse_document(html);
-        let candidates = extract_favicon(&document, &base);
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
         assert!(candidates.contains(&"https://example.com/blog/icon.png".to_string()));
     }
 
@@ -519,7 +1556,7 @@ mod tests {
         let base = Url::parse("https://example.com/page").unwrap();
         let html = r#"<html><head><link rel="icon" href="https://cdn.example.com/icon.png" /></head></html>"#;
         let document = Html::parse_document(html);
-        let candidates = extract_favicon(&document, &base);
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
         assert!(candidates.contains(&"https://cdn.example.com/icon.png".to_string()));
     }
 
@@ -529,7 +1566,7 @@ mod tests {
         let html =
             r#"<html><head><link rel="apple-touch-icon" href="/apple-icon.png" /></head></html>"#;
         let document = Html::parse_document(html);
-        let candidates = extract_favicon(&document, &base);
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
         assert!(candidates.contains(&"https://example.com/apple-icon.png".to_string()));
     }
 
@@ -543,17 +1580,145 @@ mod tests {
             </head></html>
         "#;
         let document = Html::parse_document(html);
-        let candidates = extract_favicon(&document, &base);
+        let candidates = extract_favicon(&document, &base, &default_rel_types());
         // Should have declared icons + default fallback
         assert!(candidates.len() >= 3);
     }
 
+    #[test]
+    fn test_fallback_favicon_url_substitutes_domain() {
+        let base = Url::parse("https://docs.example.com/guide/intro").unwrap();
+        let url = fallback_favicon_url("https://www.google.com/s2/favicons?domain={domain}", &base);
+        assert_eq!(
+            url,
+            Some("https://www.google.com/s2/favicons?domain=docs.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fallback_favicon_url_self_hosted_path() {
+        let base = Url::parse("http://intranet.local:8080/").unwrap();
+        let url = fallback_favicon_url("https://icons.internal/{domain}.ico", &base);
+        assert_eq!(
+            url,
+            Some("https://icons.internal/intranet.local.ico".to_string())
+        );
+    }
+
+    #[test]
+    fn test_detect_language_from_url() {
+        assert_eq!(
+            detect_language_from_url("https://gitlab.com/o/r/-/blob/main/src/lib.rs"),
+            Some("Rust")
+        );
+        assert_eq!(
+            detect_language_from_url("https://bitbucket.org/o/r/src/main/app.py"),
+            Some("Python")
+        );
+        assert_eq!(
+            detect_language_from_url("https://raw.example.com/Main.JAVA?raw=1"),
+            Some("Java")
+        );
+        assert_eq!(
+            detect_language_from_url("https://example.com/o/r/raw/main/y.hpp"),
+            Some("C++")
+        );
+    }
+
+    #[test]
+    fn test_detect_language_from_url_none() {
+        assert_eq!(detect_language_from_url("https://gitlab.com/o/r"), None);
+        assert_eq!(detect_language_from_url("https://example.com/"), None);
+        assert_eq!(
+            detect_language_from_url("https://example.com/notes.txt"),
+            None
+        );
+        assert_eq!(detect_language_from_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_detect_language_from_url_ignores_web_pages() {
+        assert_eq!(
+            detect_language_from_url("https://forum.example.com/viewtopic.php?t=42"),
+            None
+        );
+        assert_eq!(
+            detect_language_from_url("https://example.com/cgi-bin/search.pl"),
+            None
+        );
+        assert_eq!(
+            detect_language_from_url("https://example.com/videos/stream.ts"),
+            None
+        );
+        assert_eq!(
+            detect_language_from_url("https://example.com/docs/intro.r"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_first_valid_in_order_prefers_earlier_candidates() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let candidates = vec![
+            "https://example.com/missing.png".to_string(),
+            "https://example.com/slow.png".to_string(),
+            "https://example.com/fast.png".to_string(),
+            "https://example.com/favicon.ico".to_string(),
+        ];
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let result = first_valid_in_order(candidates, 2, |candidate| {
+            let in_flight = Arc::clone(&in_flight);
+            let max_in_flight = Arc::clone(&max_in_flight);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                // The slow candidate is valid but finishes after the fast one
+                let delay = if candidate.contains("slow") { 50 } else { 5 };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                !candidate.contains("missing")
+            }
+        })
+        .await;
+
+        assert_eq!(result, Some("https://example.com/slow.png".to_string()));
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_first_valid_in_order_none_valid() {
+        let candidates = vec![
+            "https://example.com/a.png".to_string(),
+            "https://example.com/b.png".to_string(),
+        ];
+        let result = first_valid_in_order(candidates, 4, |_| async { false }).await;
+        assert_eq!(result, None);
+    }
+
     #[test]
     fn test_scraped_metadata_default() {
         let meta = ScrapedMetadata::default();
         assert!(meta.title.is_none());
         assert!(meta.description.is_none());
         assert!(meta.favicon.is_none());
+        assert!(meta.final_url.is_none());
+        assert!(meta.debug.content_type.is_none());
+        assert!(meta.debug.body_length.is_none());
+    }
+
+    #[test]
+    fn test_redirected_url() {
+        let requested = Url::parse("http://example.com/old").unwrap();
+        let moved = Url::parse("https://example.com/new").unwrap();
+        assert_eq!(
+            redirected_url(&requested, &moved),
+            Some("https://example.com/new".to_string())
+        );
+        assert_eq!(redirected_url(&requested, &requested.clone()), None);
     }
 
     #[test]
@@ -571,4 +1736,287 @@ mod tests {
         assert!(VALID_IMAGE_EXTENSIONS.contains(&".svg"));
         assert!(VALID_IMAGE_EXTENSIONS.contains(&".png"));
     }
+
+    #[tokio::test]
+    async fn test_fetch_rendered_page_uses_service_html() {
+        use axum::{routing::post, Json, Router};
+
+        let config = crate::config::test_config();
+
+        let app = Router::new().route(
+            "/render",
+            post(|Json(body): Json<serde_json::Value>| async move {
+                assert_eq!(body["url"], "https://spa.example.com/");
+                Json(serde_json::json!({
+                    "html": "<html><head><title>Rendered</title></head></html>",
+                    "final_url": "https://spa.example.com/home"
+                }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let service = format!("http://{}/render", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let url = Url::parse("https://spa.example.com/").unwrap();
+        let page = fetch_rendered_page(&client, &service, &url, &config)
+            .await
+            .unwrap();
+
+        let html = page.html.unwrap();
+        let document = Html::parse_document(&html);
+        assert_eq!(
+            extract_page_text(&document).title,
+            Some("Rendered".to_string())
+        );
+        assert_eq!(
+            page.final_url,
+            Some("https://spa.example.com/home".to_string())
+        );
+        assert_eq!(page.debug.body_length, Some(html.len() as u64));
+    }
+
+    /// Serve a page that records request methods and answers HEAD with `head_status`
+    async fn spawn_health_server(
+        head_status: axum::http::StatusCode,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        use axum::{http::Method, routing::any, Router};
+
+        let methods = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = methods.clone();
+        let app = Router::new().route(
+            "/",
+            any(move |method: Method| {
+                let seen = seen.clone();
+                async move {
+                    seen.lock().unwrap().push(method.to_string());
+                    if method == Method::HEAD {
+                        head_status
+                    } else {
+                        axum::http::StatusCode::PARTIAL_CONTENT
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, methods)
+    }
+
+    #[tokio::test]
+    async fn test_probe_url_auto_falls_back_to_get() {
+        use axum::http::StatusCode;
+
+        let config = crate::config::test_config();
+        let client = reqwest::Client::new();
+
+        let (url, methods) = spawn_health_server(StatusCode::OK).await;
+        assert!(
+            probe_url(&client, &url, HealthCheckMethod::Auto, &config)
+                .await
+                .reachable
+        );
+        assert_eq!(*methods.lock().unwrap(), vec!["HEAD"]);
+
+        let (url, methods) = spawn_health_server(StatusCode::METHOD_NOT_ALLOWED).await;
+        assert!(
+            probe_url(&client, &url, HealthCheckMethod::Auto, &config)
+                .await
+                .reachable
+        );
+        assert_eq!(*methods.lock().unwrap(), vec!["HEAD", "GET"]);
+    }
+
+    #[tokio::test]
+    async fn test_probe_url_failed_tls_handshake_is_unreachable() {
+        let config = crate::config::test_config();
+        // Stands in for a server that only offers TLS below the minimum: the
+        // handshake fails and the link is reported unreachable
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                drop(socket);
+            }
+        });
+        let client = crate::security::outbound_client_builder(&config)
+            .build()
+            .unwrap();
+
+        let health = probe_url(
+            &client,
+            &format!("https://{}/", addr),
+            HealthCheckMethod::Auto,
+            &config,
+        )
+        .await;
+        assert!(!health.reachable);
+        assert!(health.detail.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_probe_url_head_only() {
+        use axum::http::StatusCode;
+
+        let config = crate::config::test_config();
+        let client = reqwest::Client::new();
+
+        let (url, methods) = spawn_health_server(StatusCode::OK).await;
+        assert!(
+            probe_url(&client, &url, HealthCheckMethod::Head, &config)
+                .await
+                .reachable
+        );
+        assert_eq!(*methods.lock().unwrap(), vec!["HEAD"]);
+
+        let (url, methods) = spawn_health_server(StatusCode::METHOD_NOT_ALLOWED).await;
+        let health = probe_url(&client, &url, HealthCheckMethod::Head, &config).await;
+        assert!(!health.reachable);
+        assert_eq!(
+            health.detail.as_deref(),
+            Some("HTTP 405 Method Not Allowed")
+        );
+        assert_eq!(*methods.lock().unwrap(), vec!["HEAD"]);
+    }
+
+    #[tokio::test]
+    async fn test_probe_url_get_only() {
+        use axum::http::StatusCode;
+
+        let config = crate::config::test_config();
+        let client = reqwest::Client::new();
+
+        let (url, methods) = spawn_health_server(StatusCode::INTERNAL_SERVER_ERROR).await;
+        assert!(
+            probe_url(&client, &url, HealthCheckMethod::Get, &config)
+                .await
+                .reachable
+        );
+        assert_eq!(*methods.lock().unwrap(), vec!["GET"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_sends_validators_and_handles_not_modified() {
+        use axum::{
+            http::{header, HeaderMap, StatusCode},
+            response::IntoResponse,
+            routing::get,
+            Router,
+        };
+
+        let config = crate::config::test_config();
+
+        let app = Router::new().route(
+            "/",
+            get(|headers: HeaderMap| async move {
+                if headers
+                    .get(header::IF_NONE_MATCH)
+                    .is_some_and(|v| v == "\"v1\"")
+                {
+                    return StatusCode::NOT_MODIFIED.into_response();
+                }
+                (
+                    [
+                        (header::CONTENT_TYPE, "text/html"),
+                        (header::ETAG, "\"v1\""),
+                        (header::LAST_MODIFIED, "Wed, 14 Oct 2026 08:00:00 GMT"),
+                    ],
+                    "<html><head><title>Page</title></head></html>",
+                )
+                    .into_response()
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::new();
+
+        let page = fetch_page(&client, &url, &PageValidators::default(), &config)
+            .await
+            .unwrap()
+            .expect("first fetch returns the page");
+        assert!(page.html.is_some());
+        assert_eq!(
+            page.validators,
+            PageValidators {
+                etag: Some("\"v1\"".to_string()),
+                last_modified: Some("Wed, 14 Oct 2026 08:00:00 GMT".to_string()),
+            }
+        );
+
+        let unchanged = fetch_page(&client, &url, &page.validators, &config)
+            .await
+            .unwrap();
+        assert!(unchanged.is_none());
+
+        let changed = PageValidators {
+            etag: Some("\"v0\"".to_string()),
+            last_modified: None,
+        };
+        assert!(fetch_page(&client, &url, &changed, &config)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_timeout_maps_to_timeout_error() {
+        use axum::{routing::get, Router};
+
+        let config = crate::config::test_config();
+
+        let app = Router::new().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "too late"
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/slow", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let err = match fetch_page(&client, &url, &PageValidators::default(), &config).await {
+            Err(err) => err,
+            Ok(_) => panic!("slow page should time out"),
+        };
+
+        assert!(matches!(err, AppError::Timeout(ref host) if host == "127.0.0.1"));
+        assert_eq!(err.status_code(), 504);
+        assert_eq!(err.error_code(), "TIMEOUT");
+    }
+    #[tokio::test]
+    async fn test_validate_image_url_rejects_private_address() {
+        use axum::{http::header, routing::get, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let config = crate::config::test_config();
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = Router::new().route(
+            "/favicon.png",
+            get(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { ([(header::CONTENT_TYPE, "image/png")], "png") }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/favicon.png", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        assert!(!validate_image_url(&url, &config).await.unwrap());
+        assert!(
+            !validate_image_url("http://169.254.169.254/latest/meta-data", &config)
+                .await
+                .unwrap()
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+    }
 }
@@ -1,10 +1,185 @@
 use chrono::{Duration, Utc};
 use sqlx::PgPool;
 use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
+use std::sync::Arc;
 use url::Url;
 
+use crate::config::Config;
 use crate::error::AppError;
 
+/// Reject link URLs whose scheme isn't on the allowlist
+///
+/// Keeps `javascript:`, `file:` and similar URLs out of the database, where
+/// they would otherwise end up as clickable links in the UI. `allowed` is the
+/// configured `allowed_url_schemes`.
+pub fn validate_url_scheme<S: AsRef<str>>(url: &Url, allowed: &[S]) -> Result<(), AppError> {
+    if allowed.iter().any(|s| s.as_ref() == url.scheme()) {
+        Ok(())
+    } else {
+        Err(AppError::validation(
+            "url",
+            &format!("Unsupported URL scheme: {}", url.scheme()),
+        ))
+    }
+}
+
+/// Mask the values of sensitive query parameters before a URL is logged
+///
+/// `https://a.example/feed?token=secret&page=2` becomes
+/// `https://a.example/feed?token=****&page=2`. Works on any text containing
+/// URLs, such as request error messages, and leaves everything else as is.
+/// Fragment parameters are masked as well, since OAuth flows put tokens there.
+/// `names` is the configured `log_masked_query_params`.
+pub fn mask_sensitive_query_params<S: AsRef<str>>(text: &str, names: &[S]) -> String {
+    let is_value_end = |c: char| matches!(c, '&' | '#' | ')' | '"' | '\'') || c.is_whitespace();
+
+    let mut masked = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(['?', '&', '#']) {
+        let (before, after) = rest.split_at(pos + 1);
+        masked.push_str(before);
+        rest = after;
+
+        let Some(eq) = rest.find('=') else {
+            continue;
+        };
+        let name = &rest[..eq];
+        if name.contains(is_value_end)
+            || !names
+                .iter()
+                .any(|masked_name| masked_name.as_ref().eq_ignore_ascii_case(name))
+        {
+            continue;
+        }
+
+        let value_end = rest[eq + 1..]
+            .find(is_value_end)
+            .map_or(rest.len(), |end| eq + 1 + end);
+        masked.push_str(&rest[..=eq]);
+        if value_end > eq + 1 {
+            masked.push_str("****");
+        }
+        rest = &rest[value_end..];
+    }
+    masked.push_str(rest);
+    masked
+}
+
+/// Semaphore bounding how many outbound requests are in flight
+///
+/// Clones share the same semaphore, so the copy in every clone of `Config`
+/// enforces one cap for the whole process.
+#[derive(Debug, Clone)]
+pub struct OutboundLimiter {
+    semaphore: Arc<tokio::sync::Semaphore>,
+}
+
+impl OutboundLimiter {
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(max_concurrency)),
+        }
+    }
+
+    /// Wait for a slot under the cap on simultaneous outbound requests
+    ///
+    /// Hold the permit until the response body has been read; dropping it
+    /// frees the slot. The scheduler and API handlers share the cap, so a
+    /// large batch of checks can't starve the network for everything else.
+    /// Don't request a second permit while holding one, or a full cap
+    /// deadlocks.
+    pub async fn acquire(&self) -> tokio::sync::SemaphorePermit<'_> {
+        self.semaphore
+            .acquire()
+            .await
+            .expect("outbound semaphore is never closed")
+    }
+}
+
+/// Start an HTTP client with the outbound proxy and TLS settings applied
+///
+/// Every client that talks to the outside world should be built from this so
+/// proxy settings apply uniformly. Without a configured proxy the system proxy
+/// variables are ignored too, since `Config` has already folded them in.
+///
+/// Sites that only offer TLS older than the minimum fail the handshake, which
+/// surfaces as an ordinary request error.
+pub fn outbound_client_builder(config: &Config) -> reqwest::ClientBuilder {
+    let proxy = config.outbound_proxy_url.as_deref().and_then(|url| {
+        build_outbound_proxy(url, &config.outbound_no_proxy)
+            .inspect_err(|e| tracing::error!(error = %e, "Ignoring invalid outbound proxy"))
+            .ok()
+    });
+    with_proxy(
+        reqwest::Client::builder().min_tls_version(config.min_tls_version.as_reqwest()),
+        proxy,
+    )
+}
+
+fn build_outbound_proxy(url: &str, no_proxy: &[String]) -> Result<reqwest::Proxy, AppError> {
+    let proxy = reqwest::Proxy::all(url)
+        .map_err(|e| AppError::Configuration(format!("Invalid outbound proxy URL: {}", e)))?;
+    Ok(proxy.no_proxy(reqwest::NoProxy::from_string(&no_proxy.join(","))))
+}
+
+fn with_proxy(
+    builder: reqwest::ClientBuilder,
+    proxy: Option<reqwest::Proxy>,
+) -> reqwest::ClientBuilder {
+    match proxy {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder.no_proxy(),
+    }
+}
+
+/// Length of the random nonce prefixed to each encrypted secret
+const SECRET_NONCE_LEN: usize = 12;
+
+fn secret_cipher(key: &str) -> chacha20poly1305::ChaCha20Poly1305 {
+    use chacha20poly1305::KeyInit;
+    use sha2::Digest;
+
+    let key = sha2::Sha256::digest(key.as_bytes());
+    chacha20poly1305::ChaCha20Poly1305::new(&key)
+}
+
+/// Encrypt a secret for storage, e.g. a user's GitHub token
+///
+/// `key` is the configured `SETTINGS_ENCRYPTION_KEY`. The result is a random
+/// nonce followed by the authenticated ciphertext.
+pub fn encrypt_secret(key: &str, plaintext: &str) -> Result<Vec<u8>, AppError> {
+    use chacha20poly1305::aead::Aead;
+
+    let nonce: [u8; SECRET_NONCE_LEN] = rand::random();
+    let ciphertext = secret_cipher(key)
+        .encrypt(&nonce.into(), plaintext.as_bytes())
+        .map_err(|_| AppError::Internal("Failed to encrypt secret".to_string()))?;
+
+    let mut stored = nonce.to_vec();
+    stored.extend_from_slice(&ciphertext);
+    Ok(stored)
+}
+
+/// Decrypt a secret produced by `encrypt_secret`
+///
+/// Fails if the data was tampered with or encrypted under a different key.
+pub fn decrypt_secret(key: &str, stored: &[u8]) -> Result<String, AppError> {
+    use chacha20poly1305::aead::Aead;
+
+    if stored.len() < SECRET_NONCE_LEN {
+        return Err(AppError::Internal(
+            "Encrypted secret is truncated".to_string(),
+        ));
+    }
+    let (nonce, ciphertext) = stored.split_at(SECRET_NONCE_LEN);
+    let plaintext = secret_cipher(key)
+        .decrypt(nonce.into(), ciphertext)
+        .map_err(|_| AppError::Internal("Failed to decrypt secret".to_string()))?;
+
+    String::from_utf8(plaintext)
+        .map_err(|_| AppError::Internal("Decrypted secret is not valid UTF-8".to_string()))
+}
+
 /// Validate that a URL is safe to fetch (SSRF protection)
 ///
 /// Parses the URL, verifies the scheme is http/https, resolves the hostname,
@@ -220,6 +395,63 @@ pub async fn cleanup_expired_refresh_tokens(pool: &PgPool) -> Result<u64, sqlx::
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DEFAULT_LOG_MASKED_QUERY_PARAMS;
+
+    #[test]
+    fn test_mask_sensitive_query_params() {
+        let mask = |text: &str| mask_sensitive_query_params(text, DEFAULT_LOG_MASKED_QUERY_PARAMS);
+        assert_eq!(
+            mask("https://a.example/feed?token=secret"),
+            "https://a.example/feed?token=****"
+        );
+        assert_eq!(
+            mask("https://a.example/?page=2&API_KEY=abc123&q=rust#access_token=xyz"),
+            "https://a.example/?page=2&API_KEY=****&q=rust#access_token=****"
+        );
+        // Names must match exactly, and URLs without secrets are unchanged
+        assert_eq!(
+            mask("https://a.example/?tokenizer=bpe&monkey=1"),
+            "https://a.example/?tokenizer=bpe&monkey=1"
+        );
+        assert_eq!(mask("https://a.example/path"), "https://a.example/path");
+        // URLs embedded in error messages are masked too
+        assert_eq!(
+            mask("error sending request for url (https://a.example/?token=secret)"),
+            "error sending request for url (https://a.example/?token=****)"
+        );
+    }
+
+    #[test]
+    fn test_mask_query_params_custom_list() {
+        let names = ["session"];
+        assert_eq!(
+            mask_sensitive_query_params("https://a.example/?session=s1&token=t1", &names),
+            "https://a.example/?session=****&token=t1"
+        );
+        let none: [&str; 0] = [];
+        assert_eq!(
+            mask_sensitive_query_params("https://a.example/?token=t1", &none),
+            "https://a.example/?token=t1"
+        );
+    }
+
+    #[test]
+    fn test_url_scheme_allowlist() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        let allowed = ["http", "https"];
+
+        assert!(validate_url_scheme(&url("https://example.com"), &allowed).is_ok());
+        assert!(validate_url_scheme(&url("http://example.com"), &allowed).is_ok());
+        assert!(validate_url_scheme(&url("ftp://ftp.example.com/pub/file.txt"), &allowed).is_err());
+        assert!(validate_url_scheme(&url("javascript:alert(1)"), &allowed).is_err());
+        assert!(validate_url_scheme(&url("mailto:someone@example.com"), &allowed).is_err());
+        assert!(validate_url_scheme(&url("file:///etc/passwd"), &allowed).is_err());
+
+        // A configured allowlist replaces the default
+        let allowed = ["https", "ftp"];
+        assert!(validate_url_scheme(&url("ftp://ftp.example.com/pub/file.txt"), &allowed).is_ok());
+        assert!(validate_url_scheme(&url("http://example.com"), &allowed).is_err());
+    }
 
     #[test]
     fn test_validate_password_valid() {
@@ -383,6 +615,122 @@ mod tests {
         assert!(validate_password("ABCDEF1!").is_ok());
     }
 
+    /// Accept one connection on a local "proxy" and return the request line
+    async fn capture_proxied_request(use_proxy: bool, url: &str) -> Option<String> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = use_proxy.then(|| {
+            build_outbound_proxy(
+                &format!("http://{}", listener.local_addr().unwrap()),
+                &["internal.test".to_string()],
+            )
+            .unwrap()
+        });
+        let client = with_proxy(reqwest::Client::builder(), proxy)
+            .timeout(std::time::Duration::from_secs(2))
+            .build()
+            .unwrap();
+
+        let accept = async {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut line = String::new();
+            BufReader::new(&mut stream)
+                .read_line(&mut line)
+                .await
+                .unwrap();
+            let _ = stream
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await;
+            line.trim_end().to_string()
+        };
+
+        tokio::select! {
+            line = accept => Some(line),
+            _ = client.get(url).send() => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_outbound_proxy_routes_requests() {
+        let line = capture_proxied_request(true, "http://example.test/page").await;
+        assert_eq!(
+            line.as_deref(),
+            Some("GET http://example.test/page HTTP/1.1")
+        );
+
+        // No-proxy hosts and unconfigured clients never reach the proxy
+        assert_eq!(
+            capture_proxied_request(true, "http://internal.test/").await,
+            None
+        );
+        assert_eq!(
+            capture_proxied_request(false, "http://example.test/").await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outbound_limiter_caps_requests_in_flight() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let limiter = OutboundLimiter::new(3);
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..12)
+            .map(|_| {
+                let (limiter, in_flight, peak) =
+                    (limiter.clone(), Arc::clone(&in_flight), Arc::clone(&peak));
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire().await;
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(limiter.semaphore.available_permits(), 3);
+    }
+
+    #[test]
+    fn test_invalid_outbound_proxy_rejected() {
+        assert!(build_outbound_proxy("not a url", &[]).is_err());
+        assert!(build_outbound_proxy("http://proxy.internal:3128", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_secret_encryption_round_trip() {
+        let key = "0123456789abcdef0123456789abcdef";
+        let stored = encrypt_secret(key, "ghp_example").unwrap();
+        assert!(!stored
+            .windows("ghp_example".len())
+            .any(|w| w == b"ghp_example"));
+        assert_eq!(decrypt_secret(key, &stored).unwrap(), "ghp_example");
+
+        // Fresh nonce each time
+        assert_ne!(encrypt_secret(key, "ghp_example").unwrap(), stored);
+    }
+
+    #[test]
+    fn test_secret_decryption_rejects_wrong_key_and_tampering() {
+        let key = "0123456789abcdef0123456789abcdef";
+        let mut stored = encrypt_secret(key, "ghp_example").unwrap();
+
+        assert!(decrypt_secret("another key of at least thirty-two chars", &stored).is_err());
+        assert!(decrypt_secret(key, &stored[..8]).is_err());
+
+        let last = stored.len() - 1;
+        stored[last] ^= 1;
+        assert!(decrypt_secret(key, &stored).is_err());
+    }
+
     #[test]
     fn test_ssrf_ipv6_loopback() {
         let ip = IpAddr::V6(std::net::Ipv6Addr::LOCALHOST);
@@ -0,0 +1,577 @@
+//! Link operations shared by API handlers and background workers
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::models::{Link, UserSettings};
+use crate::scraper;
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Hosts refreshed at the same time by `refresh_links`
+const BULK_REFRESH_CONCURRENCY: usize = 4;
+
+/// Pause between consecutive refreshes of links on the same host
+const SAME_HOST_DELAY: Duration = Duration::from_secs(1);
+
+/// A piece of link metadata that a refresh can overwrite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshField {
+    /// Scraped page title
+    Title,
+    /// Scraped page description
+    Description,
+    /// Scraped favicon
+    Logo,
+    /// GitHub repository metadata (stars, languages, license, ...)
+    Github,
+}
+
+/// Which metadata a refresh is allowed to overwrite
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct RefreshOptions {
+    /// Fields to refresh; `None` refreshes everything
+    #[serde(default)]
+    pub fields: Option<Vec<RefreshField>>,
+    /// Write scraped values as-is, replacing manually edited titles and
+    /// clearing fields the page no longer provides (see
+    /// `Link::update_scraped_metadata`)
+    #[serde(default)]
+    pub force_overwrite: bool,
+}
+
+impl RefreshOptions {
+    /// Whether `field` should be overwritten by this refresh
+    pub fn includes(&self, field: RefreshField) -> bool {
+        self.fields
+            .as_ref()
+            .is_none_or(|fields| fields.contains(&field))
+    }
+
+    /// Refresh only the fields `provided` leaves missing or blank
+    ///
+    /// Used for the background fetch of a newly created link, so values the
+    /// user supplied survive just as they do when the fetch runs inline.
+    pub fn filling(provided: &scraper::ScrapedMetadata) -> Self {
+        let missing = |value: &Option<String>| value.as_deref().is_none_or(|v| v.trim().is_empty());
+        let fields = [
+            (RefreshField::Title, &provided.title),
+            (RefreshField::Description, &provided.description),
+            (RefreshField::Logo, &provided.favicon),
+        ]
+        .into_iter()
+        .filter(|(_, value)| missing(value))
+        .map(|(field, _)| field)
+        .chain([RefreshField::Github])
+        .collect();
+
+        Self {
+            fields: Some(fields),
+            force_overwrite: false,
+        }
+    }
+
+    /// Whether any scraped page field is in scope
+    fn scrapes_page(&self) -> bool {
+        [
+            RefreshField::Title,
+            RefreshField::Description,
+            RefreshField::Logo,
+        ]
+        .into_iter()
+        .any(|field| self.includes(field))
+    }
+
+    /// Put the stored values back for fields that are out of scope
+    ///
+    /// `current` holds the link's stored title, description and logo, so
+    /// out-of-scope fields are written back unchanged even with
+    /// `force_overwrite`.
+    fn scope_scraped(
+        &self,
+        mut metadata: scraper::ScrapedMetadata,
+        current: scraper::ScrapedMetadata,
+    ) -> scraper::ScrapedMetadata {
+        if !self.includes(RefreshField::Title) {
+            metadata.title = current.title;
+        }
+        if !self.includes(RefreshField::Description) {
+            metadata.description = current.description;
+        }
+        if !self.includes(RefreshField::Logo) {
+            metadata.favicon = current.favicon;
+        }
+        metadata
+    }
+}
+
+/// Refresh all metadata for a link (web scraping + GitHub if applicable)
+///
+/// `options` limits which fields are overwritten; the default refreshes
+/// everything.
+///
+/// Fetches GitHub metadata when the link (or its source code URL) points at a
+/// GitHub repository, scrapes the page for non-GitHub links, then marks the
+/// link as refreshed. Fetch failures are logged and do not fail the refresh,
+/// except that a private GitHub repository moves the link to `private_repo` and
+/// a taken down one to `repo_takedown` (and a successful fetch moves it back
+/// to `active`).
+///
+/// # Returns
+/// The updated link, or `NotFound` if it doesn't exist or belong to the user
+pub async fn refresh_link(
+    pool: &PgPool,
+    config: &Config,
+    id: Uuid,
+    user_id: Uuid,
+    options: &RefreshOptions,
+) -> Result<Link, AppError> {
+    tracing::info!(
+        user_id = %user_id,
+        link_id = %id,
+        "Refreshing metadata for link"
+    );
+
+    // Verify link exists and belongs to user
+    let link = Link::get_by_id(pool, id, user_id).await?;
+
+    // Determine the GitHub URL to use (main URL or source_code_url)
+    // Only accept https URLs on the configured GitHub host
+    let github_url = if link.is_github_repo && config.github.is_host_url(&link.url) {
+        Some(link.url.clone())
+    } else {
+        link.source_code_url
+            .as_ref()
+            .filter(|url| config.github.is_host_url(url))
+            .cloned()
+    };
+
+    // If we have a GitHub URL, fetch GitHub metadata
+    if let Some(ref gh_url) = github_url.filter(|_| options.includes(RefreshField::Github)) {
+        if let Some((owner, repo)) = config.github.parse_repo_from_url(gh_url) {
+            let user_token = UserSettings::github_token(pool, config, user_id).await?;
+            match crate::github::fetch_repo_metadata_as(
+                config,
+                &owner,
+                &repo,
+                user_token.as_deref(),
+            )
+            .await
+            {
+                Ok(metadata) => {
+                    tracing::info!(
+                        link_id = %id,
+                        owner = %owner,
+                        repo = %repo,
+                        stars = metadata.stars,
+                        "Successfully fetched GitHub metadata"
+                    );
+
+                    if let Err(e) =
+                        Link::update_github_metadata(pool, config, id, user_id, metadata).await
+                    {
+                        tracing::warn!(
+                            link_id = %id,
+                            error = %e,
+                            "Failed to update GitHub metadata during refresh"
+                        );
+                    }
+
+                    // The repo is readable again (e.g. made public, token granted or
+                    // takedown lifted)
+                    if link.status == "private_repo" || link.status == "repo_takedown" {
+                        Link::update_status(pool, id, "active").await?;
+                    }
+                }
+                Err(AppError::Forbidden(msg)) if link.is_github_repo => {
+                    tracing::warn!(
+                        link_id = %id,
+                        owner = %owner,
+                        repo = %repo,
+                        error = %msg,
+                        "GitHub repository is private, marking as private_repo"
+                    );
+                    Link::update_status(pool, id, "private_repo").await?;
+                }
+                Err(AppError::UnavailableForLegalReasons(msg)) if link.is_github_repo => {
+                    tracing::warn!(
+                        link_id = %id,
+                        owner = %owner,
+                        repo = %repo,
+                        error = %msg,
+                        "GitHub repository was taken down, marking as repo_takedown"
+                    );
+                    Link::update_status(pool, id, "repo_takedown").await?;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        link_id = %id,
+                        owner = %owner,
+                        repo = %repo,
+                        error = %e,
+                        "Failed to fetch GitHub metadata during refresh"
+                    );
+                }
+            }
+        }
+    }
+
+    // Scrape the main URL for metadata (title, description, logo)
+    if !link.is_github_repo && options.scrapes_page() {
+        match scraper::scrape_url(&link.url, config).await {
+            Ok(mut metadata) => {
+                tracing::info!(
+                    link_id = %id,
+                    has_title = metadata.title.is_some(),
+                    has_description = metadata.description.is_some(),
+                    "Successfully scraped metadata"
+                );
+
+                // With favicon scraping off the scrape has no logo; keep the stored one
+                if !config.scrape_favicon {
+                    metadata.favicon = link.logo.clone();
+                }
+
+                let current = scraper::ScrapedMetadata {
+                    title: link.title.clone(),
+                    description: link.description.clone(),
+                    favicon: link.logo.clone(),
+                    ..Default::default()
+                };
+                let metadata = options.scope_scraped(metadata, current);
+                if let Err(e) = Link::update_scraped_metadata(
+                    pool,
+                    id,
+                    user_id,
+                    metadata,
+                    options.force_overwrite,
+                )
+                .await
+                {
+                    tracing::warn!(
+                        link_id = %id,
+                        error = %e,
+                        "Failed to update scraped metadata during refresh"
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    link_id = %id,
+                    error = %e,
+                    "Failed to scrape URL during refresh"
+                );
+            }
+        }
+    }
+
+    // Mark the link as refreshed
+    Link::mark_refreshed(pool, id, user_id).await?;
+
+    // Fetch and return updated link
+    let updated_link = Link::get_by_id(pool, id, user_id).await?;
+
+    tracing::info!(
+        link_id = %id,
+        "Link metadata refreshed successfully"
+    );
+
+    Ok(updated_link)
+}
+
+/// Refresh several links, running different hosts concurrently
+///
+/// Links on the same host are refreshed one after another with
+/// `SAME_HOST_DELAY` between them, so a selection from one site doesn't hit it
+/// in parallel. Duplicate ids are refreshed once.
+///
+/// # Returns
+/// One result per distinct id, in request order
+pub async fn refresh_links(
+    pool: &PgPool,
+    config: &Config,
+    link_ids: &[Uuid],
+    user_id: Uuid,
+) -> Result<Vec<(Uuid, Result<Link, AppError>)>, AppError> {
+    let domains = Link::get_domains(pool, user_id, link_ids).await?;
+    let groups = group_by_host(link_ids, &domains);
+
+    let mut results: HashMap<Uuid, Result<Link, AppError>> = stream::iter(groups)
+        .map(|group| async move {
+            let mut results = Vec::with_capacity(group.len());
+            for (i, id) in group.into_iter().enumerate() {
+                if i > 0 {
+                    tokio::time::sleep(SAME_HOST_DELAY).await;
+                }
+                results.push((
+                    id,
+                    refresh_link(pool, config, id, user_id, &RefreshOptions::default()).await,
+                ));
+            }
+            results
+        })
+        .buffer_unordered(BULK_REFRESH_CONCURRENCY)
+        .flat_map(stream::iter)
+        .collect()
+        .await;
+
+    let mut ordered = Vec::with_capacity(results.len());
+    for id in link_ids {
+        if let Some(result) = results.remove(id) {
+            ordered.push((*id, result));
+        }
+    }
+
+    Ok(ordered)
+}
+
+/// Fetch GitHub metadata for newly imported repository links
+///
+/// Runs up to `GITHUB_IMPORT_CONCURRENCY` fetches at once with the user's
+/// GitHub token, if they saved one. Best effort: failures are logged and
+/// skipped, and once GitHub reports the rate limit as exhausted the remaining
+/// links are left for the scheduler.
+///
+/// # Returns
+/// How many links got GitHub metadata
+pub async fn enrich_github_links(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    links: &[(Uuid, String)],
+) -> u32 {
+    if config.github_import_concurrency == 0 || links.is_empty() {
+        return 0;
+    }
+
+    let user_token = UserSettings::github_token(pool, config, user_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load GitHub token for import enrichment");
+            None
+        });
+    let user_token = user_token.as_deref();
+    let rate_limited = AtomicBool::new(false);
+    let rate_limited = &rate_limited;
+
+    let enriched = stream::iter(links)
+        .map(|(link_id, url)| async move {
+            if rate_limited.load(Ordering::Relaxed) {
+                return false;
+            }
+            let Some((owner, repo)) = config.github.parse_repo_from_url(url) else {
+                return false;
+            };
+
+            let result = match crate::github::fetch_repo_metadata_as(
+                config, &owner, &repo, user_token,
+            )
+            .await
+            {
+                Ok(metadata) => {
+                    Link::update_github_metadata(pool, config, *link_id, user_id, metadata).await
+                }
+                Err(e) => {
+                    if matches!(e, AppError::RateLimited(_)) {
+                        rate_limited.store(true, Ordering::Relaxed);
+                    }
+                    Err(e)
+                }
+            };
+
+            match result {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!(
+                        link_id = %link_id,
+                        owner = %owner,
+                        repo = %repo,
+                        error = %e,
+                        "Failed to fetch GitHub metadata for imported link"
+                    );
+                    false
+                }
+            }
+        })
+        .buffer_unordered(config.github_import_concurrency)
+        .filter(|enriched| std::future::ready(*enriched))
+        .count()
+        .await;
+
+    if rate_limited.load(Ordering::Relaxed) {
+        tracing::warn!(
+            enriched,
+            total = links.len(),
+            "GitHub rate limit reached during import, remaining repositories left for the scheduler"
+        );
+    }
+
+    enriched as u32
+}
+
+/// Group link ids by host, in order of first appearance
+///
+/// Ids without a known domain (missing or not owned) each get their own group
+/// so they fail fast with `NotFound`.
+fn group_by_host(link_ids: &[Uuid], domains: &HashMap<Uuid, String>) -> Vec<Vec<Uuid>> {
+    let mut groups: Vec<Vec<Uuid>> = Vec::new();
+    let mut group_index: HashMap<&str, usize> = HashMap::new();
+    let mut seen = HashSet::new();
+
+    for id in link_ids {
+        if !seen.insert(*id) {
+            continue;
+        }
+        match domains.get(id) {
+            Some(domain) => match group_index.get(domain.as_str()) {
+                Some(&index) => groups[index].push(*id),
+                None => {
+                    group_index.insert(domain, groups.len());
+                    groups.push(vec![*id]);
+                }
+            },
+            None => groups.push(vec![*id]),
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_host() {
+        let (a1, b1, a2, missing) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let domains = HashMap::from([
+            (a1, "a.example".to_string()),
+            (b1, "b.example".to_string()),
+            (a2, "a.example".to_string()),
+        ]);
+
+        let groups = group_by_host(&[a1, b1, missing, a2, a1], &domains);
+
+        assert_eq!(groups, vec![vec![a1, a2], vec![b1], vec![missing]]);
+    }
+
+    fn scraped() -> scraper::ScrapedMetadata {
+        scraper::ScrapedMetadata {
+            title: Some("New title".to_string()),
+            description: Some("New description".to_string()),
+            favicon: Some("https://example.com/new.ico".to_string()),
+            final_url: Some("https://example.com/final".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn stored() -> scraper::ScrapedMetadata {
+        scraper::ScrapedMetadata {
+            title: Some("Edited title".to_string()),
+            description: None,
+            favicon: Some("old.ico".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_refresh_options_default_refreshes_everything() {
+        let options: RefreshOptions = serde_json::from_str("{}").unwrap();
+        assert!(options.fields.is_none());
+        assert!(!options.force_overwrite);
+        assert!(options.includes(RefreshField::Github));
+        assert!(options.scrapes_page());
+
+        let metadata = options.scope_scraped(scraped(), stored());
+        assert_eq!(metadata.title.as_deref(), Some("New title"));
+        assert_eq!(metadata.description.as_deref(), Some("New description"));
+        assert_eq!(
+            metadata.favicon.as_deref(),
+            Some("https://example.com/new.ico")
+        );
+    }
+
+    #[test]
+    fn test_refresh_options_description_only() {
+        let options: RefreshOptions =
+            serde_json::from_str(r#"{"fields": ["description"]}"#).unwrap();
+        assert!(!options.includes(RefreshField::Title));
+        assert!(!options.includes(RefreshField::Github));
+        assert!(options.scrapes_page());
+
+        let metadata = options.scope_scraped(scraped(), stored());
+        // Stored values are written back unchanged
+        assert_eq!(metadata.title.as_deref(), Some("Edited title"));
+        assert_eq!(metadata.description.as_deref(), Some("New description"));
+        assert_eq!(metadata.favicon.as_deref(), Some("old.ico"));
+        assert_eq!(
+            metadata.final_url.as_deref(),
+            Some("https://example.com/final")
+        );
+    }
+
+    #[test]
+    fn test_refresh_options_force_overwrite_keeps_out_of_scope_fields() {
+        let options: RefreshOptions =
+            serde_json::from_str(r#"{"fields": ["title"], "force_overwrite": true}"#).unwrap();
+        assert!(options.force_overwrite);
+
+        let mut page = scraped();
+        page.title = None;
+        let metadata = options.scope_scraped(page, stored());
+        // The missing title is written as-is (cleared); the rest keeps stored values
+        assert_eq!(metadata.title, None);
+        assert_eq!(metadata.description, None);
+        assert_eq!(metadata.favicon.as_deref(), Some("old.ico"));
+    }
+
+    #[test]
+    fn test_refresh_options_github_only_skips_scrape() {
+        let options: RefreshOptions = serde_json::from_str(r#"{"fields": ["github"]}"#).unwrap();
+        assert!(options.includes(RefreshField::Github));
+        assert!(!options.scrapes_page());
+    }
+
+    #[test]
+    fn test_refresh_options_filling_skips_provided_fields() {
+        let provided = scraper::ScrapedMetadata {
+            title: Some("My title".to_string()),
+            description: Some("  ".to_string()),
+            ..Default::default()
+        };
+        let options = RefreshOptions::filling(&provided);
+        assert!(!options.includes(RefreshField::Title));
+        assert!(options.includes(RefreshField::Description));
+        assert!(options.includes(RefreshField::Logo));
+        assert!(options.includes(RefreshField::Github));
+
+        let metadata = options.scope_scraped(scraped(), stored());
+        assert_eq!(metadata.title.as_deref(), Some("Edited title"));
+        assert_eq!(metadata.description.as_deref(), Some("New description"));
+
+        let options = RefreshOptions::filling(&scraper::ScrapedMetadata::default());
+        assert!([
+            RefreshField::Title,
+            RefreshField::Description,
+            RefreshField::Logo,
+            RefreshField::Github
+        ]
+        .into_iter()
+        .all(|field| options.includes(field)));
+    }
+
+    #[test]
+    fn test_refresh_options_rejects_unknown_field() {
+        assert!(serde_json::from_str::<RefreshOptions>(r#"{"fields": ["stars"]}"#).is_err());
+    }
+}
@@ -118,6 +118,8 @@ pub struct CategoryNode {
     pub name: String,
     pub parent_id: Option<String>,
     pub depth: i32,
+    #[serde(default)]
+    pub sort_order: Option<i32>,
     pub link_count: i64,
     #[serde(default)]
     pub children: Vec<CategoryNode>,
@@ -170,6 +172,24 @@ pub async fn move_category(
     http::put(&url, &body).await
 }
 
+/// Set the manual order of categories to the order of `ids`
+pub async fn reorder_categories(ids: Vec<String>) -> Result<Vec<CategoryNode>, String> {
+    let body = serde_json::json!({ "ids": ids });
+    let categories: Vec<CategoryNode> = http::put("/api/categories/reorder", &body).await?;
+    Ok(build_category_tree(categories))
+}
+
+/// Display order of sibling categories: manual order first, then by name
+fn category_order(a: &CategoryNode, b: &CategoryNode) -> std::cmp::Ordering {
+    match (a.sort_order, b.sort_order) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+    .then_with(|| a.name.cmp(&b.name))
+}
+
 /// Build a tree structure from flat list of categories
 fn build_category_tree(categories: Vec<CategoryNode>) -> Vec<CategoryNode> {
     use std::collections::HashMap;
@@ -202,10 +222,11 @@ fn build_category_tree(categories: Vec<CategoryNode>) -> Vec<CategoryNode> {
 
     for id in &sorted_ids {
         if let Some(children_ids) = child_ids.get(id) {
-            let children: Vec<CategoryNode> = children_ids
+            let mut children: Vec<CategoryNode> = children_ids
                 .iter()
                 .filter_map(|cid| map.remove(cid))
                 .collect();
+            children.sort_by(category_order);
             if let Some(parent) = map.get_mut(id) {
                 parent.children = children;
             }
@@ -214,7 +235,7 @@ fn build_category_tree(categories: Vec<CategoryNode>) -> Vec<CategoryNode> {
 
     // Remaining entries are roots
     let mut roots: Vec<CategoryNode> = map.into_values().collect();
-    roots.sort_by(|a, b| a.name.cmp(&b.name));
+    roots.sort_by(category_order);
     roots
 }
 
@@ -333,6 +354,22 @@ pub async fn create_tag(name: &str) -> Result<TagItem, String> {
     http::post("/api/tags", &body).await
 }
 
+/// Find tags by name (case-insensitive), creating the ones that don't exist
+pub async fn get_or_create_tags(names: &[String]) -> Result<Vec<TagItem>, String> {
+    let existing = fetch_tags().await?;
+    let mut tags = Vec::with_capacity(names.len());
+    for name in names {
+        match existing
+            .iter()
+            .find(|tag| tag.name.to_lowercase() == name.to_lowercase())
+        {
+            Some(tag) => tags.push(tag.clone()),
+            None => tags.push(create_tag(name).await?),
+        }
+    }
+    Ok(tags)
+}
+
 /// Update a tag's name
 pub async fn update_tag(id: &str, name: &str) -> Result<TagItem, String> {
     let url = format!("/api/tags/{}", id);
@@ -360,6 +397,18 @@ pub struct LinkPreview {
     pub github_description: Option<String>,
     pub github_languages: Vec<String>,
     pub github_license: Option<String>,
+    #[serde(default)]
+    pub final_url: Option<String>,
+    #[serde(default = "default_reachable")]
+    pub reachable: bool,
+    #[serde(default)]
+    pub reachable_detail: Option<String>,
+    #[serde(default)]
+    pub keyword_suggestions: Vec<String>,
+}
+
+fn default_reachable() -> bool {
+    true
 }
 
 /// Fetch metadata preview for a URL without creating the link
@@ -6,11 +6,14 @@ use dioxus::prelude::*;
 pub fn CategoryTreeNode(
     node: CategoryNode,
     editing_id: Option<String>,
+    is_first: bool,
+    is_last: bool,
     on_edit_start: EventHandler<(String, String)>,
     on_edit_save: EventHandler<(String, String)>,
     on_edit_cancel: EventHandler<()>,
     on_delete: EventHandler<String>,
     on_add_child: EventHandler<String>,
+    on_move: EventHandler<(String, i32)>,
 ) -> Element {
     let is_editing = editing_id.as_ref() == Some(&node.id);
     let mut edit_value = use_signal(|| node.name.clone());
@@ -20,6 +23,9 @@ pub fn CategoryTreeNode(
     let node_id_for_edit = node.id.clone();
     let node_id_for_add = node.id.clone();
     let node_id_for_delete = node.id.clone();
+    let node_id_for_up = node.id.clone();
+    let node_id_for_down = node.id.clone();
+    let child_count = node.children.len();
     let node_name = node.name.clone();
 
     rsx! {
@@ -62,6 +68,22 @@ pub fn CategoryTreeNode(
 
                 // Action buttons
                 div { class: "tree-node-actions",
+                    // Move among siblings
+                    button {
+                        class: "btn-icon btn-move",
+                        title: "Move up",
+                        disabled: is_first,
+                        onclick: move |_| on_move.call((node_id_for_up.clone(), -1)),
+                        "↑"
+                    }
+                    button {
+                        class: "btn-icon btn-move",
+                        title: "Move down",
+                        disabled: is_last,
+                        onclick: move |_| on_move.call((node_id_for_down.clone(), 1)),
+                        "↓"
+                    }
+
                     // Add child button (only if depth < 2)
                     if node.depth < 2 {
                         button {
@@ -85,15 +107,18 @@ pub fn CategoryTreeNode(
             // Children
             if !node.children.is_empty() {
                 div { class: "tree-children",
-                    for child in &node.children {
+                    for (i, child) in node.children.iter().enumerate() {
                         CategoryTreeNode {
                             node: child.clone(),
                             editing_id: editing_id.clone(),
+                            is_first: i == 0,
+                            is_last: i + 1 == child_count,
                             on_edit_start: on_edit_start,
                             on_edit_save: on_edit_save,
                             on_edit_cancel: on_edit_cancel,
                             on_delete: on_delete,
-                            on_add_child: on_add_child
+                            on_add_child: on_add_child,
+                            on_move: on_move
                         }
                     }
                 }
@@ -35,6 +35,7 @@ pub fn MetadataBadges(
     is_github_repo: Option<bool>,
     github_stars: Option<i32>,
     github_archived: Option<bool>,
+    github_owner_type: Option<String>,
 ) -> Element {
     let has_metadata = !categories.is_empty()
         || !tags.is_empty()
@@ -66,6 +67,10 @@ pub fn MetadataBadges(
                         span { class: "badge badge-stars", "⭐ {format_stars(stars)}" }
                     }
 
+                    if github_owner_type.as_deref() == Some("Organization") {
+                        span { class: "badge badge-org", "🏢 Org" }
+                    }
+
                     if github_archived.unwrap_or(false) {
                         span { class: "badge badge-archived", "🗄️ Archived" }
                     }
@@ -1,6 +1,6 @@
 use crate::ui::api_client::{
     check_duplicate_url, create_link_with_categories, fetch_languages, fetch_licenses,
-    preview_link, CreateLinkWithCategoriesRequest, LinkPreview,
+    get_or_create_tags, preview_link, CreateLinkWithCategoriesRequest, LinkPreview,
 };
 use crate::ui::components::category_select::CategorySelect;
 use crate::ui::components::language_select::LanguageSelect;
@@ -21,7 +21,11 @@ fn format_error(error: &str) -> String {
     }
 
     // Timeout errors
-    if error.contains("timeout") || error.contains("Timeout") {
+    if error.contains("timeout")
+        || error.contains("Timeout")
+        || error.contains("timed out")
+        || error.contains("TIMEOUT")
+    {
         return "The request timed out. The server might be busy - please try again.".to_string();
     }
 
@@ -195,6 +199,8 @@ pub fn AddLinkDialog(
     // Track auto-suggested items
     let mut auto_suggested_languages = use_signal(|| false);
     let mut auto_suggested_license = use_signal(|| false);
+    // Page keywords the user accepted as tags; created when the link is saved
+    let mut accepted_keywords = use_signal(Vec::<String>::new);
 
     // Check if we're in a loading state
     let is_loading = move || progress_step().is_some();
@@ -203,6 +209,7 @@ pub fn AddLinkDialog(
     let selection_count = move || {
         selected_categories().len()
             + selected_tags().len()
+            + accepted_keywords().len()
             + selected_languages().len()
             + selected_licenses().len()
     };
@@ -353,7 +360,8 @@ pub fn AddLinkDialog(
 
         let url = url_input();
         let categories = selected_categories();
-        let tags = selected_tags();
+        let mut tags = selected_tags();
+        let keywords = accepted_keywords();
         let languages = selected_languages();
         let licenses = selected_licenses();
 
@@ -361,6 +369,26 @@ pub fn AddLinkDialog(
             creating.set(true);
             error.set(None);
 
+            if !keywords.is_empty() {
+                match get_or_create_tags(&keywords).await {
+                    Ok(keyword_tags) => {
+                        for id in keyword_tags
+                            .iter()
+                            .filter_map(|tag| Uuid::parse_str(&tag.id).ok())
+                        {
+                            if !tags.contains(&id) {
+                                tags.push(id);
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        error.set(Some(format_error(&err)));
+                        creating.set(false);
+                        return;
+                    }
+                }
+            }
+
             let request = CreateLinkWithCategoriesRequest {
                 url,
                 category_ids: categories,
@@ -398,6 +426,7 @@ pub fn AddLinkDialog(
         can_retry_preview.set(false);
         selected_categories.set(Vec::new());
         selected_tags.set(Vec::new());
+        accepted_keywords.set(Vec::new());
         selected_languages.set(Vec::new());
         selected_licenses.set(Vec::new());
         auto_suggested_languages.set(false);
@@ -412,6 +441,7 @@ pub fn AddLinkDialog(
         // Reset categorization
         selected_categories.set(Vec::new());
         selected_tags.set(Vec::new());
+        accepted_keywords.set(Vec::new());
         selected_languages.set(Vec::new());
         selected_licenses.set(Vec::new());
         // Reset auto-suggested flags
@@ -538,6 +568,28 @@ pub fn AddLinkDialog(
                                     "{preview_data.url}"
                                 }
                             }
+
+                            if !preview_data.reachable {
+                                div { class: "preview-unreachable",
+                                    "This URL could not be reached from the server"
+                                    if let Some(detail) = preview_data.reachable_detail.clone() {
+                                        " ({detail})"
+                                    }
+                                    ". It will still be saved."
+                                }
+                            }
+
+                            if let Some(final_url) = preview_data.final_url.clone() {
+                                div { class: "preview-redirect",
+                                    "Redirects to "
+                                    a {
+                                        href: "{final_url}",
+                                        target: "_blank",
+                                        rel: "noopener noreferrer",
+                                        "{final_url}"
+                                    }
+                                }
+                            }
                         }
 
                         // Categorization Accordion
@@ -568,6 +620,31 @@ pub fn AddLinkDialog(
                                         selected_ids: selected_tags(),
                                         on_change: move |ids| selected_tags.set(ids)
                                     }
+                                    if !preview_data.keyword_suggestions.is_empty() {
+                                        div { class: "keyword-suggestions",
+                                            span { class: "keyword-suggestions-label", "From the page:" }
+                                            for keyword in preview_data.keyword_suggestions.clone() {
+                                                button {
+                                                    r#type: "button",
+                                                    class: if accepted_keywords().contains(&keyword) { "keyword-suggestion selected" } else { "keyword-suggestion" },
+                                                    title: "Add as a tag",
+                                                    onclick: {
+                                                        let keyword = keyword.clone();
+                                                        move |_| {
+                                                            let mut keywords = accepted_keywords();
+                                                            if let Some(index) = keywords.iter().position(|k| *k == keyword) {
+                                                                keywords.remove(index);
+                                                            } else {
+                                                                keywords.push(keyword.clone());
+                                                            }
+                                                            accepted_keywords.set(keywords);
+                                                        }
+                                                    },
+                                                    "{keyword}"
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
 
                                 div { class: "form-group",
@@ -5,6 +5,7 @@ use crate::ui::components::metadata_badges::{CategoryInfo, LanguageInfo, License
 use crate::ui::components::modal::{ConfirmDialog, ModalBase, ModalSection};
 use crate::ui::components::tag_select::TagSelect;
 use crate::ui::http;
+use crate::ui::utils::write_clipboard;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -24,6 +25,10 @@ struct LinkDetails {
     pub github_stars: Option<i32>,
     pub github_archived: Option<bool>,
     pub github_last_commit: Option<String>,
+    #[serde(default)]
+    pub github_owner_type: Option<String>,
+    #[serde(default)]
+    pub renamed_from: Option<String>,
     pub is_github_repo: bool,
     pub created_at: String,
     pub updated_at: String,
@@ -65,6 +70,16 @@ async fn delete_link(link_id: Uuid) -> Result<(), String> {
     http::delete(&url).await
 }
 
+async fn fetch_markdown(link_id: Uuid) -> Result<String, String> {
+    let url = format!("/api/links/{}/markdown", link_id);
+    let response = http::get_response(&url).await?;
+    if response.is_success() {
+        Ok(response.body)
+    } else {
+        Err(response.error_message())
+    }
+}
+
 async fn refresh_metadata(link_id: Uuid) -> Result<LinkDetails, String> {
     let url = format!("/api/links/{}/refresh", link_id);
     let response = http::post_empty(&url).await?;
@@ -116,6 +131,9 @@ pub fn LinkDetailsModal(
     // Unsaved changes warning
     let mut show_unsaved_warning = use_signal(|| false);
 
+    // Copy-as-Markdown state
+    let mut copied = use_signal(|| false);
+
     // Fetch link on mount or when link_id changes
     use_effect(move || {
         if is_open {
@@ -236,6 +254,22 @@ pub fn LinkDetailsModal(
         });
     };
 
+    // Handle copy as Markdown
+    let handle_copy_markdown = move |_| {
+        let link_id_clone = link_id;
+        spawn(async move {
+            let result = match fetch_markdown(link_id_clone).await {
+                Ok(markdown) => write_clipboard(&markdown).await,
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(()) => copied.set(true),
+                Err(err) => save_error.set(Some(err)),
+            }
+        });
+    };
+
     rsx! {
         if is_open {
             ModalBase {
@@ -400,6 +434,9 @@ pub fn LinkDetailsModal(
                                                         "✅ Active"
                                                     }
                                                 }
+                                                if link_data.github_owner_type.as_deref() == Some("Organization") {
+                                                    span { class: "github-stat", "🏢 Organization" }
+                                                }
                                             }
 
                                             div { class: "readonly-field",
@@ -408,6 +445,12 @@ pub fn LinkDetailsModal(
                                                 }
                                             }
 
+                                            if let Some(renamed_from) = link_data.renamed_from.clone() {
+                                                div { class: "readonly-field",
+                                                    label { "Moved from: {renamed_from}" }
+                                                }
+                                            }
+
                                             button {
                                                 class: "btn-refresh",
                                                 disabled: refreshing(),
@@ -481,6 +524,15 @@ pub fn LinkDetailsModal(
                             }
 
                             div { class: "footer-actions",
+                                button {
+                                    class: "btn-secondary",
+                                    onclick: handle_copy_markdown,
+                                    if copied() {
+                                        "Copied!"
+                                    } else {
+                                        "Copy as Markdown"
+                                    }
+                                }
                                 button {
                                     class: "btn-secondary",
                                     onclick: move |_| {
@@ -9,6 +9,8 @@ pub struct Link {
     pub id: Uuid,
     pub url: String,
     pub domain: String,
+    #[serde(default)]
+    pub domain_display: Option<String>,
     pub path: Option<String>,
     pub title: Option<String>,
     pub description: Option<String>,
@@ -57,6 +59,8 @@ fn get_status_class(status: &str) -> &'static str {
         "archived" => "status-archived",
         "inaccessible" => "status-inaccessible",
         "repo_unavailable" => "status-repo-unavailable",
+        "private_repo" => "status-private-repo",
+        "repo_takedown" => "status-repo-takedown",
         _ => "status-active",
     }
 }
@@ -210,10 +214,11 @@ pub fn LinksTable(
                             // Domain
                             td { class: "cell-domain",
                                 {
+                                    let domain = link.domain_display.as_ref().unwrap_or(&link.domain);
                                     if let Some(path) = &link.path {
-                                        format!("{}{}", link.domain, path)
+                                        format!("{}{}", domain, path)
                                     } else {
-                                        link.domain.clone()
+                                        domain.clone()
                                     }
                                 }
                             }
@@ -0,0 +1,45 @@
+//! Live link events from the server's WebSocket (`/api/ws`).
+//!
+//! The socket authenticates with the session cookie, so live updates are
+//! only available in saas mode; elsewhere the connection is refused and pages
+//! simply keep their fetched data.
+
+use serde::Deserialize;
+
+/// Event pushed by the server
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveEvent {
+    LinkStatusChanged { id: String, status: String },
+    LinkCreated { id: String },
+    LinkUpdated { id: String },
+    LinkDeleted { id: String },
+}
+
+/// Open the event socket for the current page's origin
+///
+/// Returns `None` if the socket can't be opened. The stream ends when the
+/// connection closes; unknown or malformed messages are skipped.
+#[cfg(target_arch = "wasm32")]
+pub fn connect() -> Option<impl futures::Stream<Item = LiveEvent>> {
+    use futures::StreamExt;
+    use gloo_net::websocket::{futures::WebSocket, Message};
+
+    let location = web_sys::window()?.location();
+    let scheme = if location.protocol().ok()? == "https:" {
+        "wss"
+    } else {
+        "ws"
+    };
+    let url = format!("{}://{}/api/ws", scheme, location.host().ok()?);
+
+    let socket = WebSocket::open(&url).ok()?;
+    let (_sink, stream) = socket.split();
+
+    Some(stream.filter_map(|message| async move {
+        match message {
+            Ok(Message::Text(text)) => serde_json::from_str(&text).ok(),
+            _ => None,
+        }
+    }))
+}
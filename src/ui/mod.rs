@@ -3,6 +3,7 @@ pub mod app;
 pub mod auth_state;
 pub mod components;
 pub mod http;
+pub mod live_events;
 pub mod pages;
 pub mod performance;
 pub mod utils;
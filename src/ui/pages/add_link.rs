@@ -20,7 +20,11 @@ fn format_error(error: &str) -> String {
             .to_string();
     }
 
-    if error.contains("timeout") || error.contains("Timeout") {
+    if error.contains("timeout")
+        || error.contains("Timeout")
+        || error.contains("timed out")
+        || error.contains("TIMEOUT")
+    {
         return "The request timed out. The server might be busy - please try again.".to_string();
     }
 
@@ -474,6 +478,28 @@ pub fn AddLinkPage(initial_url: Option<String>) -> Element {
                                     "{preview_data.url}"
                                 }
                             }
+
+                            if !preview_data.reachable {
+                                div { class: "preview-unreachable",
+                                    "This URL could not be reached from the server"
+                                    if let Some(detail) = preview_data.reachable_detail.clone() {
+                                        " ({detail})"
+                                    }
+                                    ". It will still be saved."
+                                }
+                            }
+
+                            if let Some(final_url) = preview_data.final_url.clone() {
+                                div { class: "preview-redirect",
+                                    "Redirects to "
+                                    a {
+                                        href: "{final_url}",
+                                        target: "_blank",
+                                        rel: "noopener noreferrer",
+                                        "{final_url}"
+                                    }
+                                }
+                            }
                         }
 
                         // Categorization Section
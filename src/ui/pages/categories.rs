@@ -1,6 +1,6 @@
 use crate::ui::api_client::{
-    create_category, delete_category, fetch_categories, fetch_category, update_category,
-    CategoryNode,
+    create_category, delete_category, fetch_categories, fetch_category, reorder_categories,
+    update_category, CategoryNode,
 };
 use crate::ui::components::loading::{LoadingSpinner, SpinnerSize};
 use crate::ui::components::management::{AddCategoryInput, CategoryTreeNode};
@@ -92,6 +92,21 @@ pub fn CategoriesPage() -> Element {
         });
     };
 
+    // Handle moving a category up (-1) or down (+1) among its siblings
+    let handle_move = move |(id, offset): (String, i32)| {
+        let Some(ids) = reordered_siblings(&categories(), &id, offset) else {
+            return;
+        };
+        spawn(async move {
+            error.set(None);
+
+            match reorder_categories(ids).await {
+                Ok(cats) => categories.set(cats),
+                Err(err) => error.set(Some(err)),
+            }
+        });
+    };
+
     // Handle delete request
     let handle_delete_request = move |id: String| {
         let category_id = id.clone();
@@ -194,10 +209,12 @@ pub fn CategoriesPage() -> Element {
                         }
                     } else {
                         div { class: "category-tree",
-                            for node in categories() {
+                            for (i, node) in categories().iter().enumerate() {
                                 CategoryTreeNode {
                                     node: node.clone(),
                                     editing_id: editing_id(),
+                                    is_first: i == 0,
+                                    is_last: i + 1 == categories().len(),
                                     on_edit_start: move |(id, _name): (String, String)| {
                                         editing_id.set(Some(id));
                                     },
@@ -209,7 +226,8 @@ pub fn CategoriesPage() -> Element {
                                     on_add_child: move |parent_id: String| {
                                         new_category_parent.set(Some(parent_id));
                                         show_add_input.set(true);
-                                    }
+                                    },
+                                    on_move: handle_move
                                 }
                             }
                         }
@@ -246,3 +264,21 @@ pub fn CategoriesPage() -> Element {
         }
     }
 }
+
+/// Ids of the siblings of `id`, in order, with `id` moved by `offset`
+///
+/// Returns `None` if the category isn't found or can't move further.
+fn reordered_siblings(nodes: &[CategoryNode], id: &str, offset: i32) -> Option<Vec<String>> {
+    if let Some(pos) = nodes.iter().position(|n| n.id == id) {
+        let target = pos as i64 + offset as i64;
+        if target < 0 || target >= nodes.len() as i64 {
+            return None;
+        }
+        let mut ids: Vec<String> = nodes.iter().map(|n| n.id.clone()).collect();
+        ids.swap(pos, target as usize);
+        return Some(ids);
+    }
+    nodes
+        .iter()
+        .find_map(|n| reordered_siblings(&n.children, id, offset))
+}
@@ -10,7 +10,7 @@ use crate::ui::components::tag_select::TagSelect;
 use crate::ui::http;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -18,6 +18,8 @@ struct Link {
     id: String,
     url: String,
     domain: String,
+    #[serde(default)]
+    domain_display: Option<String>,
     title: Option<String>,
     description: Option<String>,
     status: String,
@@ -25,6 +27,8 @@ struct Link {
     is_github_repo: bool,
     github_stars: Option<i32>,
     github_archived: Option<bool>,
+    #[serde(default)]
+    github_owner_type: Option<String>,
     created_at: String,
     refreshed_at: Option<String>,
     #[serde(default)]
@@ -72,6 +76,7 @@ struct Filters {
     language_id: Option<Uuid>,
     license_id: Option<Uuid>,
     is_github: Option<bool>,
+    is_pinned: Option<bool>,
 }
 
 #[component]
@@ -111,6 +116,7 @@ pub fn Links() -> Element {
     let mut tags = use_signal(Vec::<TagInfo>::new);
     let mut languages = use_signal(Vec::<LanguageInfo>::new);
     let mut licenses = use_signal(Vec::<LicenseInfo>::new);
+    let mut status_counts = use_signal(BTreeMap::<String, i64>::new);
 
     // Search state
     let mut search_query = use_signal(String::new);
@@ -175,6 +181,12 @@ pub fn Links() -> Element {
                 }
             }
 
+            if let Some(is_pinned) = current_filters.is_pinned {
+                if is_pinned {
+                    params.push("is_pinned=true".to_string());
+                }
+            }
+
             // Add sort parameters (only if not default)
             if current_sort_by != "created_at" || current_sort_order != "desc" {
                 params.push(format!("sort_by={}", current_sort_by));
@@ -225,6 +237,38 @@ pub fn Links() -> Element {
         fetch_links();
     });
 
+    // Apply link changes pushed by the server (scheduler status changes, and
+    // edits made in other tabs) while the page is open
+    #[cfg(target_arch = "wasm32")]
+    use_effect(move || {
+        spawn(async move {
+            use crate::ui::live_events::{self, LiveEvent};
+            use futures::StreamExt;
+
+            let Some(events) = live_events::connect() else {
+                return;
+            };
+            futures::pin_mut!(events);
+            while let Some(event) = events.next().await {
+                match event {
+                    LiveEvent::LinkStatusChanged { id, status } => {
+                        if let Some(link) = links.write().iter_mut().find(|l| l.id == id) {
+                            link.status = status;
+                        }
+                    }
+                    LiveEvent::LinkDeleted { id } => {
+                        links.write().retain(|l| l.id != id);
+                        selected_ids.write().remove(&id);
+                    }
+                    // Pick up new links and edited fields with the current filters
+                    LiveEvent::LinkCreated { .. } | LiveEvent::LinkUpdated { .. } => {
+                        fetch_links();
+                    }
+                }
+            }
+        });
+    });
+
     // Fetch filter options on mount
     use_effect(move || {
         spawn(async move {
@@ -247,6 +291,11 @@ pub fn Links() -> Element {
             if let Ok(data) = http::get::<Vec<LicenseInfo>>("/api/licenses").await {
                 licenses.set(data);
             }
+
+            // Fetch link counts per status
+            if let Ok(data) = http::get::<BTreeMap<String, i64>>("/api/links/status-counts").await {
+                status_counts.set(data);
+            }
         });
     });
 
@@ -371,6 +420,8 @@ pub fn Links() -> Element {
                                 option { value: "archived", "Archived" }
                                 option { value: "inaccessible", "Inaccessible" }
                                 option { value: "repo_unavailable", "Repo Unavailable" }
+                                option { value: "private_repo", "Private Repo" }
+                                option { value: "repo_takedown", "Repo Taken Down" }
                             }
                             button {
                                 class: "btn btn-secondary",
@@ -588,10 +639,17 @@ pub fn Links() -> Element {
                                             fetch_links();
                                         },
                                         option { value: "", "All statuses" }
-                                        option { value: "active", "Active" }
-                                        option { value: "archived", "Archived" }
-                                        option { value: "inaccessible", "Inaccessible" }
-                                        option { value: "repo_unavailable", "Repo Unavailable" }
+                                        if status_counts().is_empty() {
+                                            option { value: "active", "Active" }
+                                            option { value: "archived", "Archived" }
+                                            option { value: "inaccessible", "Inaccessible" }
+                                            option { value: "repo_unavailable", "Repo Unavailable" }
+                                            option { value: "private_repo", "Private Repo" }
+                                            option { value: "repo_takedown", "Repo Taken Down" }
+                                        }
+                                        for (status, count) in status_counts() {
+                                            option { value: "{status}", "{status_display_name(&status)} ({count})" }
+                                        }
                                     }
                                 }
 
@@ -693,6 +751,24 @@ pub fn Links() -> Element {
                                     }
                                 }
 
+                                // Pinned only checkbox
+                                div { class: "filter-group filter-group-checkbox",
+                                    label {
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: filters().is_pinned.unwrap_or(false),
+                                            onchange: move |evt| {
+                                                let mut f = filters();
+                                                f.is_pinned = if evt.checked() { Some(true) } else { None };
+                                                filters.set(f);
+                                                current_page.set(1);
+                                                fetch_links();
+                                            },
+                                        }
+                                        " Pinned only"
+                                    }
+                                }
+
                                 // Clear filters button
                                 button {
                                     class: "btn btn-secondary",
@@ -777,6 +853,38 @@ pub fn Links() -> Element {
                                 "Delete Selected"
                             }
 
+                            button {
+                                class: "btn btn-secondary btn-sm",
+                                onclick: move |_| {
+                                    let ids: Vec<String> = selected_ids().iter().cloned().collect();
+                                    spawn(async move {
+                                        let body = serde_json::json!({"link_ids": ids});
+                                        match http::post_response("/api/links/bulk/refresh", &body).await {
+                                            Ok(resp) => {
+                                                if resp.is_success() {
+                                                    let failed = resp
+                                                        .json::<serde_json::Value>()
+                                                        .ok()
+                                                        .and_then(|v| v["failed"].as_u64())
+                                                        .unwrap_or(0);
+                                                    if failed > 0 {
+                                                        error.set(Some(format!("Failed to refresh {} of the selected links", failed)));
+                                                    }
+                                                    // Reload to show refreshed metadata
+                                                    fetch_links();
+                                                } else {
+                                                    error.set(Some("Failed to refresh selected links".to_string()));
+                                                }
+                                            }
+                                            Err(e) => {
+                                                error.set(Some(e));
+                                            }
+                                        }
+                                    });
+                                },
+                                "Refresh Selected"
+                            }
+
                             select {
                                 class: "bulk-select",
                                 onchange: move |evt| {
@@ -848,6 +956,145 @@ pub fn Links() -> Element {
                                     option { value: "{tag.id}", "{tag.name}" }
                                 }
                             }
+
+                            select {
+                                class: "bulk-select",
+                                onchange: move |evt| {
+                                    let value = evt.value();
+                                    if !value.is_empty() {
+                                        if let Ok(category_id) = value.parse::<Uuid>() {
+                                            let ids: Vec<String> = selected_ids().iter().cloned().collect();
+                                            spawn(async move {
+                                                let body = serde_json::json!({
+                                                    "link_ids": ids,
+                                                    "category_id": category_id,
+                                                    "action": "remove"
+                                                });
+                                                match http::post_response("/api/links/bulk/categories", &body).await {
+                                                    Ok(resp) => {
+                                                        if resp.is_success() {
+                                                            fetch_links();
+                                                        } else {
+                                                            error.set(Some("Failed to remove category from selected links".to_string()));
+                                                        }
+                                                    }
+                                                    Err(e) => {
+                                                        error.set(Some(e));
+                                                    }
+                                                }
+                                            });
+                                        }
+                                    }
+                                },
+                                option { value: "", "Remove from category..." }
+                                for cat in categories() {
+                                    option { value: "{cat.id}", "{cat.name}" }
+                                }
+                            }
+
+                            select {
+                                class: "bulk-select",
+                                onchange: move |evt| {
+                                    let value = evt.value();
+                                    if !value.is_empty() {
+                                        if let Ok(tag_id) = value.parse::<Uuid>() {
+                                            let ids: Vec<String> = selected_ids().iter().cloned().collect();
+                                            spawn(async move {
+                                                let body = serde_json::json!({
+                                                    "link_ids": ids,
+                                                    "tag_id": tag_id
+                                                });
+                                                match http::post_response("/api/links/bulk/untag", &body).await {
+                                                    Ok(resp) => {
+                                                        if resp.is_success() {
+                                                            fetch_links();
+                                                        } else {
+                                                            error.set(Some("Failed to remove tag from selected links".to_string()));
+                                                        }
+                                                    }
+                                                    Err(e) => {
+                                                        error.set(Some(e));
+                                                    }
+                                                }
+                                            });
+                                        }
+                                    }
+                                },
+                                option { value: "", "Remove tag..." }
+                                for tag in tags() {
+                                    option { value: "{tag.id}", "{tag.name}" }
+                                }
+                            }
+
+                            select {
+                                class: "bulk-select",
+                                onchange: move |evt| {
+                                    let value = evt.value();
+                                    if !value.is_empty() {
+                                        if let Ok(language_id) = value.parse::<Uuid>() {
+                                            let ids: Vec<String> = selected_ids().iter().cloned().collect();
+                                            spawn(async move {
+                                                let body = serde_json::json!({
+                                                    "link_ids": ids,
+                                                    "language_id": language_id,
+                                                    "action": "add"
+                                                });
+                                                match http::post_response("/api/links/bulk/languages", &body).await {
+                                                    Ok(resp) => {
+                                                        if resp.is_success() {
+                                                            fetch_links();
+                                                        } else {
+                                                            error.set(Some("Failed to add language to selected links".to_string()));
+                                                        }
+                                                    }
+                                                    Err(e) => {
+                                                        error.set(Some(e));
+                                                    }
+                                                }
+                                            });
+                                        }
+                                    }
+                                },
+                                option { value: "", "Add language..." }
+                                for lang in languages() {
+                                    option { value: "{lang.id}", "{lang.name}" }
+                                }
+                            }
+
+                            select {
+                                class: "bulk-select",
+                                onchange: move |evt| {
+                                    let value = evt.value();
+                                    if !value.is_empty() {
+                                        if let Ok(license_id) = value.parse::<Uuid>() {
+                                            let ids: Vec<String> = selected_ids().iter().cloned().collect();
+                                            spawn(async move {
+                                                let body = serde_json::json!({
+                                                    "link_ids": ids,
+                                                    "license_id": license_id,
+                                                    "action": "add"
+                                                });
+                                                match http::post_response("/api/links/bulk/licenses", &body).await {
+                                                    Ok(resp) => {
+                                                        if resp.is_success() {
+                                                            fetch_links();
+                                                        } else {
+                                                            error.set(Some("Failed to add license to selected links".to_string()));
+                                                        }
+                                                    }
+                                                    Err(e) => {
+                                                        error.set(Some(e));
+                                                    }
+                                                }
+                                            });
+                                        }
+                                    }
+                                },
+                                option { value: "", "Add license..." }
+                                for lic in licenses() {
+                                    option { value: "{lic.id}", "{lic.name}" }
+                                }
+                            }
                         }
                     }
 
@@ -1359,6 +1606,19 @@ fn LinkForm(
     }
 }
 
+/// Human-readable name of a link status; unknown statuses are shown as-is
+fn status_display_name(status: &str) -> &str {
+    match status {
+        "active" => "Active",
+        "archived" => "Archived",
+        "inaccessible" => "Inaccessible",
+        "repo_unavailable" => "Repo Unavailable",
+        "private_repo" => "Private Repo",
+        "repo_takedown" => "Repo Taken Down",
+        _ => status,
+    }
+}
+
 #[component]
 fn LinkCard(
     link: Link,
@@ -1402,20 +1662,18 @@ fn LinkCard(
         "archived" => "📦",
         "inaccessible" => "⚠️",
         "repo_unavailable" => "⚠️",
+        "private_repo" => "🔒",
+        "repo_takedown" => "⛔",
         _ => "●",
     };
-    let status_label = match link.status.as_str() {
-        "active" => "Active",
-        "archived" => "Archived",
-        "inaccessible" => "Inaccessible",
-        "repo_unavailable" => "Repo Unavailable",
-        _ => link.status.as_str(),
-    };
+    let status_label = status_display_name(&link.status);
     let status_class = match link.status.as_str() {
         "active" => "status-active",
         "archived" => "status-archived",
         "inaccessible" => "status-inaccessible",
         "repo_unavailable" => "status-repo-unavailable",
+        "private_repo" => "status-private-repo",
+        "repo_takedown" => "status-repo-takedown",
         _ => "status-unknown",
     };
 
@@ -1450,7 +1708,10 @@ fn LinkCard(
             }
             div { class: "link-card-header",
                 h3 {
-                    class: if link.status == "inaccessible" || link.status == "repo_unavailable" {
+                    class: if link.status == "inaccessible"
+                        || link.status == "repo_unavailable"
+                        || link.status == "repo_takedown"
+                    {
                         "link-title link-title-unavailable"
                     } else {
                         "link-title"
@@ -1526,7 +1787,11 @@ fn LinkCard(
                             if is_refreshing { "..." } else { "🔄" }
                         }
                         // Add "Mark as Active" button for inaccessible links
-                        if link.status == "inaccessible" || link.status == "repo_unavailable" {
+                        if link.status == "inaccessible"
+                            || link.status == "repo_unavailable"
+                            || link.status == "private_repo"
+                            || link.status == "repo_takedown"
+                        {
                             button {
                                 class: "btn-icon btn-success",
                                 title: "Mark as Active",
@@ -1610,7 +1875,7 @@ fn LinkCard(
             }
 
             div { class: "link-domain",
-                "{link.domain}"
+                {link.domain_display.clone().unwrap_or_else(|| link.domain.clone())}
                 if link.is_github_repo {
                     if let Some(stars) = link.github_stars {
                         span { class: "github-stars", " ⭐ {stars}" }
@@ -1630,6 +1895,7 @@ fn LinkCard(
                 is_github_repo: Some(link.is_github_repo),
                 github_stars: link.github_stars,
                 github_archived: link.github_archived,
+                github_owner_type: link.github_owner_type.clone(),
             }
 
             div { class: "link-meta",
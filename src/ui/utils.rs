@@ -41,3 +41,22 @@ pub async fn read_clipboard() -> Result<String, String> {
 pub async fn read_clipboard() -> Result<String, String> {
     Err("Clipboard not available in non-browser environment".to_string())
 }
+
+/// Write text to the clipboard (browser only)
+#[cfg(target_arch = "wasm32")]
+pub async fn write_clipboard(text: &str) -> Result<(), String> {
+    let window = web_sys::window().ok_or("No window available")?;
+    let clipboard = window.navigator().clipboard();
+
+    JsFuture::from(clipboard.write_text(text))
+        .await
+        .map_err(|_| "Failed to write clipboard")?;
+
+    Ok(())
+}
+
+/// Fallback for non-browser environments
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn write_clipboard(_text: &str) -> Result<(), String> {
+    Err("Clipboard not available in non-browser environment".to_string())
+}
@@ -0,0 +1,248 @@
+//! Typed API client for integration tests
+//!
+//! Drives the real `/api` router in-process through `tower::ServiceExt::oneshot`,
+//! so no server or port is needed. After `setup` or `login` the client sends
+//! the returned JWT as a bearer token on every request, like the web UI does.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::sync::{Arc, RwLock};
+
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use rusty_links::api;
+use rusty_links::auth::oidc_rs::OidcVerifier;
+use rusty_links::events::EventBus;
+use rusty_links::jobs::JobQueue;
+use rusty_links::models::Link;
+use rusty_links::server_functions::auth::{AuthResponse, LoginRequest, SetupRequest};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tower::ServiceExt; // for `oneshot`
+
+/// One page of `GET /api/links`
+///
+/// Each entry deserializes as a plain `Link`; the categories, tags,
+/// languages and licenses attached to it are ignored.
+#[derive(Debug, Deserialize)]
+pub struct LinkPage {
+    pub links: Vec<Link>,
+    pub total: i64,
+    pub page: u32,
+    pub per_page: u32,
+    pub total_pages: u32,
+}
+
+/// Body of `POST /api/links`
+#[derive(Debug, Default, Serialize)]
+pub struct NewLink {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl NewLink {
+    pub fn url(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A non-2xx response, kept whole so tests can assert on it
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: serde_json::Value,
+}
+
+/// In-process client for the `/api` router
+pub struct ApiClient {
+    router: axum::Router,
+    token: Option<String>,
+}
+
+impl ApiClient {
+    /// Build a standalone-mode client backed by `pool`, e.g. from
+    /// [`setup_test_db`](super::setup_test_db)
+    pub fn new(pool: PgPool) -> Self {
+        let config = super::config_with_issuer("");
+        let verifier = Arc::new(OidcVerifier::new(&config));
+        let jobs = JobQueue::start(pool.clone(), config.clone());
+        let router = api::create_router(
+            pool,
+            config,
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(AtomicUsize::new(0)),
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(RwLock::new(None)),
+            verifier,
+            jobs,
+            EventBus::new(),
+        );
+
+        Self {
+            router,
+            token: None,
+        }
+    }
+
+    /// Create the first (admin) user and keep its token
+    pub async fn setup(&mut self, email: &str, password: &str) -> Result<AuthResponse, ApiError> {
+        let request = SetupRequest {
+            email: email.to_string(),
+            password: password.to_string(),
+            name: "Test User".to_string(),
+        };
+        let auth: AuthResponse = self
+            .send(Method::POST, "/auth/setup", Some(&request))
+            .await?;
+        self.token = Some(auth.token.clone());
+        Ok(auth)
+    }
+
+    /// Log in and keep the token for later requests
+    pub async fn login(&mut self, email: &str, password: &str) -> Result<AuthResponse, ApiError> {
+        let request = LoginRequest {
+            email: email.to_string(),
+            password: password.to_string(),
+        };
+        let auth: AuthResponse = self
+            .send(Method::POST, "/auth/login", Some(&request))
+            .await?;
+        self.token = Some(auth.token.clone());
+        Ok(auth)
+    }
+
+    /// Forget the token, so requests go out unauthenticated
+    pub fn logout(&mut self) {
+        self.token = None;
+    }
+
+    pub async fn create_link(&self, link: &NewLink) -> Result<Link, ApiError> {
+        self.send(Method::POST, "/links", Some(link)).await
+    }
+
+    /// Create a link, sending `key` as the `Idempotency-Key` header
+    pub async fn create_link_with_idempotency_key(
+        &self,
+        link: &NewLink,
+        key: &str,
+    ) -> Result<Link, ApiError> {
+        let bytes = self
+            .send_raw_with_headers(
+                Method::POST,
+                "/links",
+                Some(link),
+                &[("idempotency-key", key)],
+            )
+            .await?;
+        Ok(serde_json::from_slice(&bytes).unwrap())
+    }
+
+    pub async fn get_link(&self, id: uuid::Uuid) -> Result<Link, ApiError> {
+        self.send(Method::GET, &format!("/links/{}", id), None::<&()>)
+            .await
+    }
+
+    pub async fn delete_link(&self, id: uuid::Uuid) -> Result<(), ApiError> {
+        self.send_raw(Method::DELETE, &format!("/links/{}", id), None::<&()>)
+            .await
+            .map(|_| ())
+    }
+
+    /// `POST /api/links/import` with a JSON export, returning the import
+    /// result as JSON
+    pub async fn import_links(
+        &self,
+        data: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        self.send(Method::POST, "/links/import", Some(data)).await
+    }
+
+    /// First page of links with the default sort
+    pub async fn list_links(&self) -> Result<LinkPage, ApiError> {
+        self.send(Method::GET, "/links", None::<&()>).await
+    }
+
+    /// Links matching a text query, with any other `/api/links` query
+    /// parameters, e.g. `&[("status", "active")]`
+    pub async fn search(&self, query: &str, params: &[(&str, &str)]) -> Result<LinkPage, ApiError> {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        serializer.append_pair("query", query);
+        for (key, value) in params {
+            serializer.append_pair(key, value);
+        }
+        self.send(
+            Method::GET,
+            &format!("/links?{}", serializer.finish()),
+            None::<&()>,
+        )
+        .await
+    }
+
+    /// Send a request and deserialize a successful JSON response
+    pub async fn send<B: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<&B>,
+    ) -> Result<T, ApiError> {
+        let bytes = self.send_raw(method, uri, body).await?;
+        Ok(serde_json::from_slice(&bytes)
+            .unwrap_or_else(|e| panic!("unexpected response body from {}: {}", uri, e)))
+    }
+
+    /// Send a request, returning the body of a successful response
+    pub async fn send_raw<B: Serialize>(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<&B>,
+    ) -> Result<Vec<u8>, ApiError> {
+        self.send_raw_with_headers(method, uri, body, &[]).await
+    }
+
+    /// Like `send_raw`, adding extra request headers
+    pub async fn send_raw_with_headers<B: Serialize>(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<&B>,
+        headers: &[(&str, &str)],
+    ) -> Result<Vec<u8>, ApiError> {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(ref token) = self.token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_vec(body).unwrap())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec();
+
+        if status.is_success() {
+            Ok(bytes)
+        } else {
+            Err(ApiError {
+                status,
+                body: serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+            })
+        }
+    }
+}